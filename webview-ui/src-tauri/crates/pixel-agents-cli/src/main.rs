@@ -0,0 +1,184 @@
+use pixel_agents_core::settings::read_monitor_settings;
+use pixel_agents_core::{Monitor, MonitorSnapshot};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+const MIN_WATCH_INTERVAL_MS: u64 = 500;
+const MAX_TEXT_CHARS: usize = 60;
+const USAGE: &str = "Usage: pixel-agents [--json] [--watch] [--interval <ms>]
+
+Print the current agent snapshot.
+
+Options:
+  --json            Print the snapshot as JSON (one line per update with --watch)
+  --watch           Keep printing updates until interrupted
+  --interval <ms>   Poll interval for --watch (defaults to the source poll interval)
+  -h, --help        Show this help";
+
+struct Options {
+    json: bool,
+    watch: bool,
+    interval_ms: Option<u64>,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let monitor = Monitor::default();
+    if !options.watch {
+        return match print_tick(&monitor, &options) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("{}", message);
+                ExitCode::FAILURE
+            }
+        };
+    }
+    loop {
+        if let Err(message) = print_tick(&monitor, &options) {
+            eprintln!("{}", message);
+        }
+        let interval_ms = options
+            .interval_ms
+            .unwrap_or_else(|| read_monitor_settings().source_poll_interval_ms.max(0) as u64);
+        thread::sleep(Duration::from_millis(
+            interval_ms.max(MIN_WATCH_INTERVAL_MS),
+        ));
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        json: false,
+        watch: false,
+        interval_ms: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--watch" | "-w" => options.watch = true,
+            "--interval" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--interval needs a value".to_string())?;
+                let interval = value
+                    .parse()
+                    .map_err(|_| format!("invalid interval `{}`", value))?;
+                options.interval_ms = Some(interval);
+            }
+            "-h" | "--help" => return Ok(None),
+            other => return Err(format!("unknown argument `{}`", other)),
+        }
+    }
+    Ok(Some(options))
+}
+
+fn print_tick(monitor: &Monitor, options: &Options) -> Result<(), String> {
+    let payload = monitor.tick(true).map_err(|e| e.to_string())?;
+    let Some(snapshot) = payload.snapshot else {
+        return Err("monitor returned no snapshot".to_string());
+    };
+    if options.json {
+        let text = serde_json::to_string(&snapshot).map_err(|e| e.to_string())?;
+        println!("{}", text);
+        return Ok(());
+    }
+    if options.watch {
+        print!("\x1b[2J\x1b[H");
+    }
+    print!("{}", render_table(&snapshot));
+    Ok(())
+}
+
+fn render_table(snapshot: &MonitorSnapshot) -> String {
+    let summary = &snapshot.summary;
+    let mut out = format!(
+        "{} agents: {} active, {} waiting, {} done, {} error, {} limited\n",
+        summary.total,
+        summary.active,
+        summary.waiting,
+        summary.done,
+        summary.error,
+        summary.limited
+    );
+    for source in &snapshot.source_status {
+        if source.status != "error" && source.status != "degraded" {
+            continue;
+        }
+        match &source.error {
+            Some(error) => out.push_str(&format!(
+                "! {} {}: {}\n",
+                source.source, source.status, error
+            )),
+            None => out.push_str(&format!(
+                "! {} {}: {} parse errors\n",
+                source.source, source.status, source.parse_errors
+            )),
+        }
+    }
+    if snapshot.agents.is_empty() {
+        return out;
+    }
+    let header = ["SOURCE", "AGENT", "STATE", "AGE", "BRANCH", "LAST"];
+    let rows: Vec<[String; 6]> = snapshot
+        .agents
+        .iter()
+        .map(|agent| {
+            [
+                agent.source.clone(),
+                agent.display_name.clone(),
+                agent.state.clone(),
+                format_age(snapshot.now_ms - agent.last_ts_ms),
+                agent.branch.clone().unwrap_or_default(),
+                truncate(agent.last_text.as_deref().unwrap_or_default()),
+            ]
+        })
+        .collect();
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let header = header.map(str::to_string);
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn format_age(ms: i64) -> String {
+    let secs = ms.max(0) / 1000;
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3_599 => format!("{}m", secs / 60),
+        3_600..=86_399 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+fn truncate(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() <= MAX_TEXT_CHARS {
+        return line.to_string();
+    }
+    let mut out: String = line.chars().take(MAX_TEXT_CHARS - 3).collect();
+    out.push_str("...");
+    out
+}
@@ -0,0 +1,115 @@
+use crate::error::Error;
+use crate::paths::{
+    agent_prefs_file, agent_seats_file, layout_file, monitor_settings_file, repo_bindings_file,
+    sound_settings_file,
+};
+use crate::settings::write_desktop_setting;
+use crate::store;
+use crate::util::now_ms;
+use serde_json::{json, Map, Value};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+const BUNDLE_KIND: &str = "pixel-agents-bundle";
+const BUNDLE_VERSION: u64 = 1;
+const DESKTOP_SETTINGS: &str = "desktopSettings";
+/// Desktop settings that are secret or only make sense on this machine.
+const LOCAL_DESKTOP_KEYS: [&str; 6] = [
+    "githubToken",
+    "webhooks",
+    "push",
+    "activeLayout",
+    "pipGeometry",
+    "lastUpdateCheckMs",
+];
+
+fn bundle_files() -> [(&'static str, PathBuf); 6] {
+    [
+        ("layout", layout_file()),
+        ("agentSeats", agent_seats_file()),
+        ("monitorSettings", monitor_settings_file()),
+        (DESKTOP_SETTINGS, sound_settings_file()),
+        ("agentPrefs", agent_prefs_file()),
+        ("repoBindings", repo_bindings_file()),
+    ]
+}
+
+pub fn export_bundle() -> Result<Value, Error> {
+    let mut files = Map::new();
+    for (name, path) in bundle_files() {
+        let mut value = match store::read_json(&path) {
+            Ok(value) => value,
+            Err(Error::Io(e)) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        if name == DESKTOP_SETTINGS {
+            if let Some(map) = value.as_object_mut() {
+                map.retain(|key, _| !LOCAL_DESKTOP_KEYS.contains(&key.as_str()));
+            }
+        }
+        files.insert(name.to_string(), value);
+    }
+    Ok(json!({
+        "kind": BUNDLE_KIND,
+        "version": BUNDLE_VERSION,
+        "exportedAtMs": now_ms(),
+        "files": files,
+    }))
+}
+
+/// Writes every file present in `bundle` (the previous copies go to the
+/// usual backups) and returns the names of the restored entries. Desktop
+/// settings are merged so machine-local keys survive.
+pub fn import_bundle(bundle: &Value) -> Result<Vec<String>, Error> {
+    if bundle.get("kind").and_then(Value::as_str) != Some(BUNDLE_KIND) {
+        return Err(Error::Invalid("not a pixel-agents bundle".to_string()));
+    }
+    match bundle.get("version").and_then(Value::as_u64) {
+        Some(version) if version <= BUNDLE_VERSION => {}
+        other => {
+            return Err(Error::Invalid(format!(
+                "unsupported bundle version {:?}",
+                other
+            )))
+        }
+    }
+    let files = bundle
+        .get("files")
+        .and_then(Value::as_object)
+        .ok_or_else(|| Error::Invalid("bundle has no files".to_string()))?;
+    let mut restored = Vec::new();
+    for (name, path) in bundle_files() {
+        let Some(value) = files.get(name) else {
+            continue;
+        };
+        if name == DESKTOP_SETTINGS {
+            let settings = value
+                .as_object()
+                .ok_or_else(|| Error::Invalid("invalid desktop settings".to_string()))?;
+            for (key, value) in settings {
+                if key != "version" && !LOCAL_DESKTOP_KEYS.contains(&key.as_str()) {
+                    write_desktop_setting(key, value.clone())?;
+                }
+            }
+        } else {
+            store::write_json(&path, value)?;
+        }
+        restored.push(name.to_string());
+    }
+    tracing::info!(?restored, "imported settings bundle");
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_foreign_or_newer_bundles() {
+        assert!(import_bundle(&json!({ "version": 1, "tiles": [] })).is_err());
+        assert!(
+            import_bundle(&json!({ "kind": BUNDLE_KIND, "version": 99, "files": {} })).is_err()
+        );
+        assert!(import_bundle(&json!({ "kind": BUNDLE_KIND, "version": 1 })).is_err());
+    }
+}
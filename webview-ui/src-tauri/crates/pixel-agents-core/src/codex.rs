@@ -0,0 +1,275 @@
+use crate::codex_record::CodexLine;
+use crate::error::Error;
+use crate::model::MonitorEventView;
+use crate::monitor::Monitor;
+use crate::paths::codex_sessions_root;
+use crate::scan::{cap_recent_events, upsert_agent, AgentTemp, ScanCounts};
+use crate::settings::ScanLimits;
+use crate::transcript::find_codex_session_file;
+use crate::util::{
+    collect_files, complete_lines_len, modified_ms, now_ms, parse_session_from_filename,
+    read_range, read_tail_lines, system_time_to_ms,
+};
+use crate::watch::WatchedSource;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+pub(crate) struct CodexScanState {
+    pub(crate) source: WatchedSource<HashMap<String, AgentTemp>>,
+    pub(crate) limits: Option<ScanLimits>,
+    pub(crate) files: Vec<PathBuf>,
+    pub(crate) parsed: HashMap<PathBuf, CodexFileScan>,
+}
+
+pub(crate) struct CodexFileScan {
+    pub(crate) modified: i64,
+    pub(crate) created: Option<i64>,
+    pub(crate) len: u64,
+    pub(crate) offset: u64,
+    pub(crate) parse_errors: usize,
+    pub(crate) agents: HashMap<String, AgentTemp>,
+}
+
+pub(crate) fn scan_codex_source(
+    state: &Monitor,
+    limits: &ScanLimits,
+    counts: &mut ScanCounts,
+) -> Result<HashMap<String, AgentTemp>, Error> {
+    let mut cache = state
+        .codex_scan
+        .lock()
+        .map_err(|_| Error::lock("codex scan"))?;
+    if cache.limits != Some(*limits) {
+        cache.source.reset();
+        cache.files.clear();
+        cache.parsed.clear();
+        cache.limits = Some(*limits);
+    }
+    let mut agents = HashMap::new();
+    scan_codex(&mut agents, &mut cache, limits, counts);
+    Ok(agents)
+}
+
+pub(crate) fn scan_codex(
+    map: &mut HashMap<String, AgentTemp>,
+    cache: &mut CodexScanState,
+    limits: &ScanLimits,
+    counts: &mut ScanCounts,
+) {
+    let root = codex_sessions_root();
+    if !root.exists() {
+        return;
+    }
+    let CodexScanState {
+        source,
+        files,
+        parsed,
+        ..
+    } = cache;
+    let agents = source.scan(&root, |changes| {
+        let new_file = changes.paths.iter().any(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("jsonl"))
+                && !files.contains(path)
+        });
+        if changes.full_rescan || new_file || files.is_empty() {
+            *files = collect_files(&root, "jsonl", limits.codex_files);
+        }
+        parsed.retain(|path, _| files.contains(path));
+        counts.rescanned = true;
+        counts.files = files.len();
+
+        let mut out: HashMap<String, AgentTemp> = HashMap::new();
+        for file in files.iter() {
+            let meta = match fs::metadata(file) {
+                Ok(meta) => meta,
+                Err(_) => continue,
+            };
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(system_time_to_ms)
+                .unwrap_or_else(now_ms);
+            let created = meta.created().ok().and_then(system_time_to_ms);
+            let len = meta.len();
+            let scan = match parsed.remove(file) {
+                Some(scan) if scan.modified == modified && scan.len == len => Some(scan),
+                Some(mut scan)
+                    if scan.created == created
+                        && len >= scan.offset
+                        && len - scan.offset <= limits.codex_tail_bytes as u64 =>
+                {
+                    if let Ok(chunk) = read_range(file, scan.offset, len) {
+                        let consumed = complete_lines_len(&chunk);
+                        scan.parse_errors += apply_codex_lines(
+                            &mut scan.agents,
+                            file,
+                            &String::from_utf8_lossy(&chunk[..consumed]),
+                            modified,
+                            limits.recent_events,
+                        );
+                        scan.offset += consumed as u64;
+                    }
+                    scan.modified = modified;
+                    scan.len = len;
+                    Some(scan)
+                }
+                _ => read_tail_lines(file, len, limits.codex_tail_bytes)
+                    .ok()
+                    .map(|(start, chunk)| {
+                        let consumed = complete_lines_len(&chunk);
+                        let mut agents = HashMap::new();
+                        let parse_errors = apply_codex_lines(
+                            &mut agents,
+                            file,
+                            &String::from_utf8_lossy(&chunk[..consumed]),
+                            modified,
+                            limits.recent_events,
+                        );
+                        CodexFileScan {
+                            modified,
+                            created,
+                            len,
+                            offset: start + consumed as u64,
+                            parse_errors,
+                            agents,
+                        }
+                    }),
+            };
+            if let Some(scan) = scan {
+                parsed.insert(file.clone(), scan);
+            }
+            if let Some(scan) = parsed.get(file) {
+                counts.parse_errors += scan.parse_errors;
+                for agent in scan.agents.values() {
+                    upsert_agent(&mut out, agent.clone());
+                }
+            }
+        }
+        cap_recent_events(out.values_mut(), limits.total_recent_events);
+        out
+    });
+    map.extend(agents);
+}
+
+pub(crate) fn read_codex_session_events(session_id: &str) -> Result<Vec<MonitorEventView>, Error> {
+    let file = find_codex_session_file(session_id)
+        .ok_or_else(|| Error::Invalid(format!("codex session not found: {}", session_id)))?;
+    let raw = fs::read_to_string(&file)?;
+    let mut agents = HashMap::new();
+    apply_codex_lines(&mut agents, &file, &raw, modified_ms(&file), usize::MAX);
+    Ok(agents
+        .remove(&format!("codex:{}", session_id))
+        .map(|agent| agent.recent_events)
+        .unwrap_or_default())
+}
+
+pub(crate) fn apply_codex_lines(
+    map: &mut HashMap<String, AgentTemp>,
+    file: &Path,
+    text: &str,
+    modified: i64,
+    max_events: usize,
+) -> usize {
+    let mut parse_errors = 0;
+    let fallback_session = parse_session_from_filename(file).unwrap_or_else(|| {
+        file.file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "unknown".to_string())
+    });
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let line: CodexLine = match serde_json::from_str(trimmed) {
+            Ok(line) => line,
+            Err(_) => {
+                parse_errors += 1;
+                continue;
+            }
+        };
+        let session_id = line
+            .session_id()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| fallback_session.clone());
+        let key = format!("codex:{}", session_id);
+        let ts = line.ts_ms().unwrap_or(modified);
+        let repo_path = line.cwd().map(|s| s.to_string());
+        let agent_name = line.agent_name();
+        let activity = line.activity();
+
+        let existing = map.entry(key.clone()).or_insert(AgentTemp {
+            key: key.clone(),
+            source: "codex".to_string(),
+            session_id: session_id.clone(),
+            agent_name: None,
+            state: "idle".to_string(),
+            started_ts_ms: ts,
+            last_ts_ms: ts,
+            last_text: Some("Session discovered".to_string()),
+            repo_path: repo_path.clone(),
+            recent_events: Vec::new(),
+        });
+
+        existing.started_ts_ms = existing.started_ts_ms.min(ts);
+        if existing.repo_path.is_none() && repo_path.is_some() {
+            existing.repo_path = repo_path;
+        }
+        if existing.agent_name.is_none() && agent_name.is_some() {
+            existing.agent_name = agent_name.clone();
+        }
+        let Some(activity) = activity else {
+            continue;
+        };
+        existing.recent_events.insert(
+            0,
+            MonitorEventView {
+                ts_ms: ts,
+                event_type: activity.event_type.to_string(),
+                state_hint: activity.state.to_string(),
+                text: Some(activity.text.clone()),
+                files_touched: Vec::new(),
+            },
+        );
+        existing.recent_events.truncate(max_events);
+        if ts >= existing.last_ts_ms {
+            existing.last_ts_ms = ts;
+            existing.state = activity.state.to_string();
+            existing.last_text = Some(activity.text);
+            if agent_name.is_some() {
+                existing.agent_name = agent_name;
+            }
+        }
+    }
+    parse_errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_rollout_fixture() {
+        let mut map = HashMap::new();
+        let parse_errors = apply_codex_lines(
+            &mut map,
+            Path::new("rollout-abc.jsonl"),
+            include_str!("../tests/fixtures/codex-rollout.jsonl"),
+            0,
+            20,
+        );
+        assert_eq!(parse_errors, 1);
+        let agent = &map["codex:abc"];
+        assert_eq!(agent.state, "waiting");
+        assert_eq!(agent.agent_name.as_deref(), Some("Fix the flaky test"));
+        assert_eq!(agent.repo_path.as_deref(), Some("/work/repo"));
+        assert_eq!(agent.started_ts_ms, 1_735_689_600_000);
+        assert_eq!(agent.last_ts_ms, 1_735_689_603_000);
+        assert_eq!(agent.recent_events.len(), 3);
+        assert_eq!(agent.recent_events[0].event_type, "permission");
+    }
+}
@@ -0,0 +1,525 @@
+use crate::scan::is_rate_limit_text;
+use crate::util::{de_epoch_ms, de_lenient_string, truncate_text};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CodexLine {
+    #[serde(default, deserialize_with = "de_epoch_ms")]
+    pub(crate) ts: Option<i64>,
+    #[serde(default, deserialize_with = "de_epoch_ms")]
+    pub(crate) timestamp: Option<i64>,
+    #[serde(default, alias = "sessionId")]
+    pub(crate) session_id: Option<String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(flatten)]
+    pub(crate) record: CodexRecord,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum CodexRecord {
+    SessionMeta {
+        payload: CodexSessionMeta,
+    },
+    TurnContext {
+        payload: CodexTurnContext,
+    },
+    EventMsg {
+        payload: CodexEvent,
+    },
+    ResponseItem {
+        payload: CodexResponseItem,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CodexSessionMeta {
+    #[serde(default)]
+    pub(crate) id: Option<String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(default, deserialize_with = "de_epoch_ms")]
+    pub(crate) timestamp: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CodexTurnContext {
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum CodexEvent {
+    UserMessage {
+        #[serde(default)]
+        message: Option<String>,
+    },
+    AgentMessage {
+        #[serde(default)]
+        message: Option<String>,
+    },
+    AgentReasoning {},
+    TokenCount {
+        #[serde(default)]
+        info: Option<CodexTokenInfo>,
+    },
+    TaskStarted {},
+    TaskComplete {},
+    #[serde(alias = "task_aborted")]
+    TurnAborted {},
+    ExecApprovalRequest {
+        #[serde(default)]
+        command: Option<CodexCommand>,
+    },
+    ApplyPatchApprovalRequest {
+        #[serde(default)]
+        changes: Map<String, Value>,
+    },
+    #[serde(alias = "stream_error")]
+    Error {
+        #[serde(default)]
+        message: Option<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum CodexResponseItem {
+    Message {},
+    Reasoning {},
+    #[serde(alias = "custom_tool_call")]
+    FunctionCall {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        function: Option<CodexFunctionRef>,
+        #[serde(default, alias = "input", deserialize_with = "de_lenient_string")]
+        arguments: Option<String>,
+    },
+    #[serde(alias = "custom_tool_call_output")]
+    FunctionCallOutput {
+        #[serde(default)]
+        output: Option<Value>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CodexTokenInfo {
+    #[serde(default)]
+    pub(crate) total_token_usage: Option<CodexTokenUsage>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+pub(crate) struct CodexTokenUsage {
+    #[serde(default)]
+    pub(crate) input_tokens: u64,
+    #[serde(default)]
+    pub(crate) cached_input_tokens: u64,
+    #[serde(default)]
+    pub(crate) output_tokens: u64,
+    #[serde(default)]
+    pub(crate) reasoning_output_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CodexFunctionRef {
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum CodexCommand {
+    Argv(Vec<String>),
+    Line(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CodexActivity {
+    pub(crate) state: &'static str,
+    pub(crate) event_type: &'static str,
+    pub(crate) text: String,
+}
+
+impl CodexActivity {
+    fn new(state: &'static str, event_type: &'static str, text: impl Into<String>) -> Self {
+        Self {
+            state,
+            event_type,
+            text: text.into(),
+        }
+    }
+}
+
+impl CodexLine {
+    pub(crate) fn ts_ms(&self) -> Option<i64> {
+        let meta_ts = match &self.record {
+            CodexRecord::SessionMeta { payload } => payload.timestamp,
+            _ => None,
+        };
+        self.ts.or(self.timestamp).or(meta_ts)
+    }
+
+    pub(crate) fn session_id(&self) -> Option<&str> {
+        match &self.record {
+            CodexRecord::SessionMeta {
+                payload: CodexSessionMeta { id: Some(id), .. },
+            } => Some(id),
+            _ => self.session_id.as_deref(),
+        }
+    }
+
+    pub(crate) fn cwd(&self) -> Option<&str> {
+        let payload_cwd = match &self.record {
+            CodexRecord::SessionMeta { payload } => payload.cwd.as_deref(),
+            CodexRecord::TurnContext { payload } => payload.cwd.as_deref(),
+            _ => None,
+        };
+        payload_cwd.or(self.cwd.as_deref())
+    }
+
+    pub(crate) fn model(&self) -> Option<&str> {
+        match &self.record {
+            CodexRecord::TurnContext { payload } => payload.model.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn total_token_usage(&self) -> Option<CodexTokenUsage> {
+        match &self.record {
+            CodexRecord::EventMsg {
+                payload:
+                    CodexEvent::TokenCount {
+                        info:
+                            Some(CodexTokenInfo {
+                                total_token_usage, ..
+                            }),
+                    },
+            } => *total_token_usage,
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_user_message(&self) -> bool {
+        matches!(
+            self.record,
+            CodexRecord::EventMsg {
+                payload: CodexEvent::UserMessage { .. }
+            }
+        )
+    }
+
+    pub(crate) fn agent_name(&self) -> Option<String> {
+        let CodexRecord::EventMsg {
+            payload: CodexEvent::UserMessage {
+                message: Some(message),
+            },
+        } = &self.record
+        else {
+            return None;
+        };
+        let first_line = message.lines().next().unwrap_or_default().trim();
+        (!first_line.is_empty()).then(|| first_line.to_string())
+    }
+
+    pub(crate) fn activity(&self) -> Option<CodexActivity> {
+        match &self.record {
+            CodexRecord::EventMsg { payload } => payload.activity(),
+            CodexRecord::ResponseItem { payload } => payload.activity(),
+            CodexRecord::SessionMeta { .. }
+            | CodexRecord::TurnContext { .. }
+            | CodexRecord::Other => None,
+        }
+    }
+}
+
+impl CodexEvent {
+    fn activity(&self) -> Option<CodexActivity> {
+        Some(match self {
+            Self::UserMessage { .. } => {
+                CodexActivity::new("waiting", "message", "Waiting for input")
+            }
+            Self::AgentMessage { message } => match message.as_deref() {
+                Some(message) if is_rate_limit_text(message) => {
+                    CodexActivity::new("limited", "limit", message)
+                }
+                message => {
+                    CodexActivity::new("running", "message", message.unwrap_or("Assistant message"))
+                }
+            },
+            Self::AgentReasoning {} | Self::TokenCount { .. } => {
+                CodexActivity::new("thinking", "status", "Thinking")
+            }
+            Self::TaskStarted {} => CodexActivity::new("running", "status", "Task started"),
+            Self::TaskComplete {} => CodexActivity::new("done", "status", "Turn completed"),
+            Self::TurnAborted {} => CodexActivity::new("waiting", "status", "Turn aborted"),
+            Self::ExecApprovalRequest { command } => {
+                let command = match command {
+                    Some(CodexCommand::Argv(parts)) => parts.join(" "),
+                    Some(CodexCommand::Line(line)) => line.clone(),
+                    None => String::new(),
+                };
+                let text = if command.trim().is_empty() {
+                    "Approve command".to_string()
+                } else {
+                    truncate_text(format!("Approve: {}", command.trim()))
+                };
+                CodexActivity::new("waiting", "permission", text)
+            }
+            Self::ApplyPatchApprovalRequest { changes } => {
+                let text = if changes.is_empty() {
+                    "Approve patch".to_string()
+                } else {
+                    let files: Vec<&str> = changes.keys().map(String::as_str).collect();
+                    truncate_text(format!("Approve patch: {}", files.join(", ")))
+                };
+                CodexActivity::new("waiting", "permission", text)
+            }
+            Self::Error { message } => match message.as_deref() {
+                Some(message) if is_rate_limit_text(message) => {
+                    CodexActivity::new("limited", "limit", message)
+                }
+                _ => CodexActivity::new("error", "error", "Codex error"),
+            },
+            Self::Other => return None,
+        })
+    }
+}
+
+impl CodexResponseItem {
+    fn activity(&self) -> Option<CodexActivity> {
+        Some(match self {
+            Self::Message {} => CodexActivity::new("running", "message", "Assistant message"),
+            Self::Reasoning {} => CodexActivity::new("thinking", "status", "Thinking"),
+            Self::FunctionCall { .. } => CodexActivity::new(
+                "running",
+                "tool",
+                format!("{}: running", self.tool_name().unwrap_or("tool")),
+            ),
+            Self::FunctionCallOutput { .. } => CodexActivity::new("running", "tool", "Tool output"),
+            Self::Other => return None,
+        })
+    }
+}
+
+impl CodexResponseItem {
+    pub(crate) fn tool_name(&self) -> Option<&str> {
+        let Self::FunctionCall { name, function, .. } = self else {
+            return None;
+        };
+        name.as_deref()
+            .or_else(|| function.as_ref().and_then(|f| f.name.as_deref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> CodexLine {
+        serde_json::from_str(line).unwrap()
+    }
+
+    fn activity(line: &str) -> Option<(&'static str, &'static str, String)> {
+        parse(line)
+            .activity()
+            .map(|activity| (activity.state, activity.event_type, activity.text))
+    }
+
+    #[test]
+    fn session_meta_sets_identity_without_activity() {
+        let line = parse(
+            r#"{"timestamp":"2025-01-01T00:00:00Z","type":"session_meta","payload":{"id":"abc","cwd":"/repo","timestamp":1735689600}}"#,
+        );
+        assert_eq!(line.session_id(), Some("abc"));
+        assert_eq!(line.cwd(), Some("/repo"));
+        assert_eq!(line.ts_ms(), Some(1_735_689_600_000));
+        assert_eq!(line.activity(), None);
+    }
+
+    #[test]
+    fn turn_context_sets_cwd() {
+        let line = parse(r#"{"type":"turn_context","payload":{"cwd":"/repo","model":"gpt"}}"#);
+        assert_eq!(line.cwd(), Some("/repo"));
+        assert_eq!(line.activity(), None);
+    }
+
+    #[test]
+    fn user_message_waits_and_names_agent() {
+        let raw = r#"{"type":"event_msg","payload":{"type":"user_message","message":"Fix tests\nplease"}}"#;
+        assert_eq!(parse(raw).agent_name().as_deref(), Some("Fix tests"));
+        assert_eq!(
+            activity(raw),
+            Some(("waiting", "message", "Waiting for input".to_string()))
+        );
+    }
+
+    #[test]
+    fn token_count_reads_totals() {
+        let line = parse(
+            r#"{"type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1200,"cached_input_tokens":1000,"output_tokens":300,"reasoning_output_tokens":100,"total_tokens":1500}}}}"#,
+        );
+        assert_eq!(
+            line.total_token_usage(),
+            Some(CodexTokenUsage {
+                input_tokens: 1200,
+                cached_input_tokens: 1000,
+                output_tokens: 300,
+                reasoning_output_tokens: 100,
+            })
+        );
+        assert_eq!(
+            parse(r#"{"type":"event_msg","payload":{"type":"token_count","info":null}}"#)
+                .total_token_usage(),
+            None
+        );
+    }
+
+    #[test]
+    fn agent_message_runs_with_text() {
+        assert_eq!(
+            activity(
+                r#"{"type":"event_msg","payload":{"type":"agent_message","message":"On it"}}"#
+            ),
+            Some(("running", "message", "On it".to_string()))
+        );
+    }
+
+    #[test]
+    fn reasoning_and_token_count_think() {
+        for payload in ["agent_reasoning", "token_count"] {
+            let raw = format!(
+                r#"{{"type":"event_msg","payload":{{"type":"{}"}}}}"#,
+                payload
+            );
+            assert_eq!(activity(&raw).map(|a| a.0), Some("thinking"));
+        }
+        assert_eq!(
+            activity(r#"{"type":"response_item","payload":{"type":"reasoning","summary":[]}}"#)
+                .map(|a| a.0),
+            Some("thinking")
+        );
+    }
+
+    #[test]
+    fn task_lifecycle() {
+        assert_eq!(
+            activity(r#"{"type":"event_msg","payload":{"type":"task_started"}}"#),
+            Some(("running", "status", "Task started".to_string()))
+        );
+        assert_eq!(
+            activity(r#"{"type":"event_msg","payload":{"type":"task_complete"}}"#),
+            Some(("done", "status", "Turn completed".to_string()))
+        );
+        assert_eq!(
+            activity(
+                r#"{"type":"event_msg","payload":{"type":"turn_aborted","reason":"interrupted"}}"#
+            ),
+            Some(("waiting", "status", "Turn aborted".to_string()))
+        );
+    }
+
+    #[test]
+    fn exec_approval_request_waits_for_permission() {
+        assert_eq!(
+            activity(
+                r#"{"type":"event_msg","payload":{"type":"exec_approval_request","command":["cargo","test"]}}"#
+            ),
+            Some(("waiting", "permission", "Approve: cargo test".to_string()))
+        );
+        assert_eq!(
+            activity(r#"{"type":"event_msg","payload":{"type":"exec_approval_request"}}"#),
+            Some(("waiting", "permission", "Approve command".to_string()))
+        );
+    }
+
+    #[test]
+    fn apply_patch_approval_request_lists_files() {
+        assert_eq!(
+            activity(
+                r#"{"type":"event_msg","payload":{"type":"apply_patch_approval_request","changes":{"src/lib.rs":{}}}}"#
+            ),
+            Some((
+                "waiting",
+                "permission",
+                "Approve patch: src/lib.rs".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn errors_distinguish_rate_limits() {
+        assert_eq!(
+            activity(r#"{"type":"event_msg","payload":{"type":"error","message":"boom"}}"#),
+            Some(("error", "error", "Codex error".to_string()))
+        );
+        assert_eq!(
+            activity(r#"{"type":"event_msg","payload":{"type":"stream_error","message":"Rate limit exceeded"}}"#)
+                .map(|a| a.0),
+            Some("limited")
+        );
+    }
+
+    #[test]
+    fn tool_calls_run() {
+        assert_eq!(
+            activity(
+                r#"{"type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{}"}}"#
+            ),
+            Some(("running", "tool", "shell: running".to_string()))
+        );
+        assert_eq!(
+            activity(
+                r#"{"type":"response_item","payload":{"type":"custom_tool_call_output","output":"ok"}}"#
+            ),
+            Some(("running", "tool", "Tool output".to_string()))
+        );
+    }
+
+    #[test]
+    fn function_call_falls_back_to_nested_name() {
+        assert_eq!(
+            activity(
+                r#"{"type":"response_item","payload":{"type":"function_call","function":{"name":"apply_patch"}}}"#
+            ),
+            Some(("running", "tool", "apply_patch: running".to_string()))
+        );
+    }
+
+    #[test]
+    fn assistant_response_message_runs() {
+        assert_eq!(
+            activity(
+                r#"{"type":"response_item","payload":{"type":"message","role":"assistant","content":[]}}"#
+            ),
+            Some(("running", "message", "Assistant message".to_string()))
+        );
+    }
+
+    #[test]
+    fn unknown_kinds_are_ignored() {
+        assert_eq!(
+            activity(r#"{"type":"event_msg","payload":{"type":"brand_new_event"}}"#),
+            None
+        );
+        assert_eq!(
+            activity(r#"{"type":"response_item","payload":{"type":"web_search_call"}}"#),
+            None
+        );
+        assert_eq!(
+            activity(r#"{"type":"compacted","payload":{"message":"x"}}"#),
+            None
+        );
+    }
+}
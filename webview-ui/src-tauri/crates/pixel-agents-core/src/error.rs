@@ -0,0 +1,59 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Parse(#[from] serde_json::Error),
+    #[error(transparent)]
+    Db(#[from] rusqlite::Error),
+    #[error("{0}")]
+    Git(String),
+    #[error("{0}")]
+    Github(String),
+    #[error("{0}")]
+    Invalid(String),
+    #[error("{0}")]
+    State(String),
+}
+
+impl Error {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Io(_) => "io",
+            Self::Parse(_) => "parse",
+            Self::Db(_) => "db",
+            Self::Git(_) => "git",
+            Self::Github(_) => "github",
+            Self::Invalid(_) => "invalid",
+            Self::State(_) => "state",
+        }
+    }
+
+    pub fn lock(name: &str) -> Self {
+        Self::State(format!("{} lock failed", name))
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(error: ureq::Error) -> Self {
+        Self::Github(error.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for Error {
+    fn from(error: zip::result::ZipError) -> Self {
+        Self::Io(error.into())
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Error", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
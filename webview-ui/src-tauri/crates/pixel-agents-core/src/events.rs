@@ -0,0 +1,31 @@
+use crate::codex::read_codex_session_events;
+use crate::error::Error;
+use crate::model::MonitorEventView;
+use crate::opencode::read_opencode_session_events;
+use crate::scan::normalize_source_name;
+
+pub fn read_agent_events(
+    agent_key: &str,
+    limit: usize,
+    before_ts: Option<i64>,
+) -> Result<Vec<MonitorEventView>, Error> {
+    let (source, session_id) = agent_key
+        .split_once(':')
+        .ok_or_else(|| Error::Invalid(format!("invalid agent key: {}", agent_key)))?;
+    let mut events = match normalize_source_name(source).as_str() {
+        "codex" => read_codex_session_events(session_id)?,
+        "opencode" => read_opencode_session_events(session_id)?,
+        other => {
+            return Err(Error::Invalid(format!(
+                "event history is not available for {} sessions",
+                other
+            )))
+        }
+    };
+    if let Some(before_ts) = before_ts {
+        events.retain(|event| event.ts_ms < before_ts);
+    }
+    events.sort_by(|a, b| b.ts_ms.cmp(&a.ts_ms));
+    events.truncate(limit);
+    Ok(events)
+}
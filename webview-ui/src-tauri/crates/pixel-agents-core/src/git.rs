@@ -0,0 +1,483 @@
+use crate::error::Error;
+use crate::jj;
+use git2::{Branch, Repository, Status, StatusOptions};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+const RECENT_COMMIT_SCAN: usize = 20;
+const MIN_POLL_INTERVAL_MS: i64 = 2_000;
+const MAX_REFRESHES_PER_TICK: usize = 8;
+const UNUSED_ENTRY_TTL_MS: i64 = 300_000;
+
+#[derive(Debug, Clone)]
+pub(crate) struct GitWorktree {
+    pub(crate) path: String,
+    pub(crate) branch: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GitCommit {
+    pub(crate) hash: String,
+    pub(crate) subject: String,
+    pub(crate) ts_ms: i64,
+    pub(crate) author: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GitStatus {
+    pub(crate) vcs: &'static str,
+    pub(crate) dirty: bool,
+    pub(crate) changed_files: usize,
+    pub(crate) branch: Option<String>,
+    pub(crate) change_id: Option<String>,
+    pub(crate) ahead: Option<usize>,
+    pub(crate) behind: Option<usize>,
+    pub(crate) stash_ts_ms: Vec<i64>,
+}
+
+struct CacheEntry<T> {
+    checked_ms: i64,
+    used_ms: i64,
+    value: T,
+}
+
+#[derive(Default)]
+struct CacheClock {
+    now_ms: i64,
+    interval_ms: i64,
+    refresh_budget: usize,
+}
+
+#[derive(Default)]
+pub(crate) struct GitCache {
+    clock: CacheClock,
+    worktrees: HashMap<String, CacheEntry<Vec<GitWorktree>>>,
+    commits: HashMap<String, CacheEntry<Vec<GitCommit>>>,
+    status: HashMap<String, CacheEntry<Option<GitStatus>>>,
+}
+
+impl GitCache {
+    pub(crate) fn begin_tick(&mut self, now_ms: i64, interval_ms: i64) {
+        self.clock = CacheClock {
+            now_ms,
+            interval_ms: interval_ms.max(MIN_POLL_INTERVAL_MS),
+            refresh_budget: MAX_REFRESHES_PER_TICK,
+        };
+        let evict_before = now_ms - self.clock.interval_ms.max(UNUSED_ENTRY_TTL_MS);
+        self.worktrees
+            .retain(|_, entry| entry.used_ms >= evict_before);
+        self.commits
+            .retain(|_, entry| entry.used_ms >= evict_before);
+        self.status.retain(|_, entry| entry.used_ms >= evict_before);
+    }
+
+    pub(crate) fn worktrees_for(&mut self, repo_path: &str) -> Vec<GitWorktree> {
+        cached(&mut self.clock, &mut self.worktrees, repo_path, || {
+            list_worktrees(repo_path)
+        })
+    }
+
+    pub(crate) fn known_worktrees(&self) -> impl Iterator<Item = &GitWorktree> {
+        self.worktrees.values().flat_map(|entry| entry.value.iter())
+    }
+
+    pub(crate) fn commits_for(&mut self, repo_path: &str) -> Vec<GitCommit> {
+        cached(&mut self.clock, &mut self.commits, repo_path, || {
+            recent_commits(repo_path)
+        })
+    }
+
+    pub(crate) fn status_for(&mut self, repo_path: &str) -> Option<GitStatus> {
+        cached(&mut self.clock, &mut self.status, repo_path, || {
+            repo_status(repo_path)
+        })
+    }
+}
+
+fn cached<T: Clone + Default>(
+    clock: &mut CacheClock,
+    entries: &mut HashMap<String, CacheEntry<T>>,
+    repo_path: &str,
+    load: impl FnOnce() -> T,
+) -> T {
+    let fresh = entries
+        .get(repo_path)
+        .map(|entry| clock.now_ms - entry.checked_ms < clock.interval_ms)
+        .unwrap_or(false);
+    if !fresh && clock.refresh_budget > 0 {
+        clock.refresh_budget -= 1;
+        let value = load();
+        entries.insert(
+            repo_path.to_string(),
+            CacheEntry {
+                checked_ms: clock.now_ms,
+                used_ms: clock.now_ms,
+                value,
+            },
+        );
+    }
+    match entries.get_mut(repo_path) {
+        Some(entry) => {
+            entry.used_ms = clock.now_ms;
+            entry.value.clone()
+        }
+        None => T::default(),
+    }
+}
+
+pub(crate) fn list_worktrees(repo_path: &str) -> Vec<GitWorktree> {
+    if let Some(worktrees) = open_repo(repo_path).and_then(|repo| list_worktrees_libgit2(&repo)) {
+        return worktrees;
+    }
+    list_worktrees_cli(repo_path)
+}
+
+fn list_worktrees_libgit2(repo: &Repository) -> Option<Vec<GitWorktree>> {
+    let main = Repository::open(repo.commondir()).ok()?;
+    let mut out = Vec::new();
+    if let Some(workdir) = main.workdir() {
+        out.push(GitWorktree {
+            path: path_string(workdir),
+            branch: head_branch(&main),
+        });
+    }
+    let names = main.worktrees().ok()?;
+    for name in names.iter().flatten() {
+        let Ok(worktree) = main.find_worktree(name) else {
+            continue;
+        };
+        let branch = Repository::open_from_worktree(&worktree)
+            .ok()
+            .and_then(|repo| head_branch(&repo));
+        out.push(GitWorktree {
+            path: path_string(worktree.path()),
+            branch,
+        });
+    }
+    Some(out)
+}
+
+fn list_worktrees_cli(repo_path: &str) -> Vec<GitWorktree> {
+    let Some(output) = git_output(repo_path, &["worktree", "list", "--porcelain"]) else {
+        return Vec::new();
+    };
+
+    let mut out = Vec::new();
+    let mut current: Option<GitWorktree> = None;
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            if let Some(done) = current.take() {
+                out.push(done);
+            }
+            current = Some(GitWorktree {
+                path: path.trim().to_string(),
+                branch: None,
+            });
+        } else if let Some(branch) = line.strip_prefix("branch ") {
+            if let Some(worktree) = current.as_mut() {
+                let branch = branch.trim();
+                worktree.branch = Some(
+                    branch
+                        .strip_prefix("refs/heads/")
+                        .unwrap_or(branch)
+                        .to_string(),
+                );
+            }
+        }
+    }
+    if let Some(done) = current.take() {
+        out.push(done);
+    }
+    out
+}
+
+pub fn add_worktree(repo_path: &str, branch_name: &str) -> Result<String, Error> {
+    if !Branch::name_is_valid(branch_name).unwrap_or(false) {
+        return Err(Error::Invalid(format!(
+            "Invalid branch name `{}`",
+            branch_name
+        )));
+    }
+    let root =
+        find_repo_root(repo_path).ok_or_else(|| Error::Git("Not a git repository".to_string()))?;
+    let root_path = Path::new(&root);
+    let repo_name = root_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let dir_name = format!("{}-{}", repo_name, branch_name.replace(['/', '\\'], "-"));
+    let worktree_path = root_path.parent().unwrap_or(root_path).join(dir_name);
+    if worktree_path.exists() {
+        return Err(Error::Git(format!(
+            "Worktree path already exists: {}",
+            worktree_path.to_string_lossy()
+        )));
+    }
+
+    let worktree_arg = worktree_path.to_string_lossy().into_owned();
+    let output = Command::new("git")
+        .args(["worktree", "add", "-b", branch_name, &worktree_arg])
+        .current_dir(&root)
+        .output()?;
+    if !output.status.success() {
+        return Err(Error::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(worktree_arg)
+}
+
+pub(crate) fn find_repo_root(cwd: &str) -> Option<String> {
+    let start = Path::new(cwd.trim());
+    if !start.is_absolute() {
+        return None;
+    }
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists() || dir.join(".jj").is_dir())
+        .map(path_string)
+}
+
+pub(crate) fn find_worktree<'a>(
+    worktrees: &'a [GitWorktree],
+    cwd: &str,
+) -> Option<&'a GitWorktree> {
+    let cwd = Path::new(cwd);
+    worktrees
+        .iter()
+        .filter(|worktree| cwd.starts_with(Path::new(&worktree.path)))
+        .max_by_key(|worktree| Path::new(&worktree.path).components().count())
+}
+
+pub(crate) fn recent_commits(repo_path: &str) -> Vec<GitCommit> {
+    if let Some(commits) = open_repo(repo_path).and_then(|repo| recent_commits_libgit2(&repo)) {
+        return commits;
+    }
+    recent_commits_cli(repo_path)
+}
+
+fn recent_commits_libgit2(repo: &Repository) -> Option<Vec<GitCommit>> {
+    if repo.head().is_err() {
+        return Some(Vec::new());
+    }
+    let mut walk = repo.revwalk().ok()?;
+    walk.push_head().ok()?;
+    let mut out = Vec::new();
+    for oid in walk.take(RECENT_COMMIT_SCAN) {
+        let commit = repo.find_commit(oid.ok()?).ok()?;
+        out.push(GitCommit {
+            hash: commit.id().to_string(),
+            subject: commit.summary().unwrap_or_default().to_string(),
+            ts_ms: commit.time().seconds().saturating_mul(1000),
+            author: commit.author().name().unwrap_or_default().to_string(),
+        });
+    }
+    Some(out)
+}
+
+fn recent_commits_cli(repo_path: &str) -> Vec<GitCommit> {
+    let limit = format!("--max-count={}", RECENT_COMMIT_SCAN);
+    let Some(output) = git_output(
+        repo_path,
+        &["log", &limit, "--format=%H%x1f%s%x1f%ct%x1f%an"],
+    ) else {
+        return Vec::new();
+    };
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x1f');
+            let hash = fields.next()?.trim().to_string();
+            let subject = fields.next()?.to_string();
+            let ts_ms = fields
+                .next()?
+                .trim()
+                .parse::<i64>()
+                .ok()?
+                .saturating_mul(1000);
+            let author = fields.next().unwrap_or_default().to_string();
+            if hash.is_empty() {
+                return None;
+            }
+            Some(GitCommit {
+                hash,
+                subject,
+                ts_ms,
+                author,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn repo_status(repo_path: &str) -> Option<GitStatus> {
+    if jj::is_jj_repo(repo_path) {
+        if let Some(status) = jj::repo_status(repo_path) {
+            return Some(status);
+        }
+    }
+    if let Some(status) = open_repo(repo_path).and_then(|mut repo| repo_status_libgit2(&mut repo)) {
+        return Some(status);
+    }
+    repo_status_cli(repo_path)
+}
+
+fn repo_status_libgit2(repo: &mut Repository) -> Option<GitStatus> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .include_ignored(false)
+        .exclude_submodules(true);
+    let statuses = repo.statuses(Some(&mut options)).ok()?;
+    let changed_files = statuses
+        .iter()
+        .filter(|entry| entry.status() != Status::CURRENT)
+        .count();
+    drop(statuses);
+    let (ahead, behind) = match ahead_behind_libgit2(repo) {
+        Some((ahead, behind)) => (Some(ahead), Some(behind)),
+        None => (None, None),
+    };
+    let stash_ts_ms = stash_times_libgit2(repo);
+    Some(GitStatus {
+        vcs: "git",
+        dirty: changed_files > 0,
+        changed_files,
+        branch: head_branch(repo),
+        change_id: None,
+        ahead,
+        behind,
+        stash_ts_ms,
+    })
+}
+
+fn stash_times_libgit2(repo: &mut Repository) -> Vec<i64> {
+    let mut oids = Vec::new();
+    let _ = repo.stash_foreach(|_, _, oid| {
+        oids.push(*oid);
+        true
+    });
+    oids.into_iter()
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        .map(|commit| commit.time().seconds().saturating_mul(1000))
+        .collect()
+}
+
+fn ahead_behind_libgit2(repo: &Repository) -> Option<(usize, usize)> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    let local = head.target()?;
+    let upstream = Branch::wrap(head).upstream().ok()?;
+    let remote = upstream.get().target()?;
+    repo.graph_ahead_behind(local, remote).ok()
+}
+
+fn repo_status_cli(repo_path: &str) -> Option<GitStatus> {
+    let output = git_output(repo_path, &["status", "--porcelain"])?;
+    let changed_files = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count();
+    let (ahead, behind) = match ahead_behind_cli(repo_path) {
+        Some((ahead, behind)) => (Some(ahead), Some(behind)),
+        None => (None, None),
+    };
+    let branch = git_output(repo_path, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .map(|output| output.trim().to_string())
+        .filter(|branch| !branch.is_empty() && branch != "HEAD");
+    let stash_ts_ms = git_output(repo_path, &["stash", "list", "--format=%ct"])
+        .map(|output| {
+            output
+                .lines()
+                .filter_map(|line| line.trim().parse::<i64>().ok())
+                .map(|secs| secs.saturating_mul(1000))
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(GitStatus {
+        vcs: "git",
+        dirty: changed_files > 0,
+        changed_files,
+        branch,
+        change_id: None,
+        ahead,
+        behind,
+        stash_ts_ms,
+    })
+}
+
+fn ahead_behind_cli(repo_path: &str) -> Option<(usize, usize)> {
+    let output = git_output(
+        repo_path,
+        &["rev-list", "--left-right", "--count", "@{upstream}...HEAD"],
+    )?;
+    let mut parts = output.split_whitespace();
+    let behind = parts.next()?.parse::<usize>().ok()?;
+    let ahead = parts.next()?.parse::<usize>().ok()?;
+    Some((ahead, behind))
+}
+
+pub(crate) fn remote_url(repo_path: &str, remote: &str) -> Option<String> {
+    if let Some(repo) = open_repo(repo_path) {
+        if let Some(url) = repo
+            .find_remote(remote)
+            .ok()
+            .and_then(|r| r.url().map(|u| u.to_string()))
+        {
+            return Some(url);
+        }
+    }
+    git_output(repo_path, &["remote", "get-url", remote])
+        .map(|output| output.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+fn open_repo(path: &str) -> Option<Repository> {
+    if !Path::new(path).is_dir() {
+        return None;
+    }
+    let repo = Repository::discover(path).ok()?;
+    if repo.is_bare() {
+        return None;
+    }
+    Some(repo)
+}
+
+fn head_branch(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(|s| s.to_string())
+}
+
+fn path_string(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    let trimmed = text.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        return text.into_owned();
+    }
+    trimmed.to_string()
+}
+
+fn git_output(cwd: &str, args: &[&str]) -> Option<String> {
+    command_output("git", cwd, args)
+}
+
+pub(crate) fn command_output(program: &str, cwd: &str, args: &[&str]) -> Option<String> {
+    if !Path::new(cwd).is_dir() {
+        return None;
+    }
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,368 @@
+use crate::error::Error;
+use crate::git;
+use crate::pr::{CheckRollupItem, PrAuthor, PrComment, PullRequest, WorkflowRun};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub(crate) const API_TIMEOUT_SECS: u64 = 10;
+pub(crate) const USER_AGENT: &str = "pixel-agents-desktop";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GithubRepo {
+    api_base: String,
+    owner: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RestPull {
+    node_id: String,
+    number: i64,
+    title: String,
+    html_url: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    head: RestHead,
+    #[serde(default)]
+    requested_reviewers: Vec<RestUser>,
+}
+
+#[derive(Deserialize)]
+struct RestPullDetail {
+    mergeable: Option<bool>,
+    mergeable_state: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RestHead {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct RestUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct RestComment {
+    user: Option<RestUser>,
+    #[serde(default)]
+    body: Option<String>,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+struct RestReview {
+    user: Option<RestUser>,
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct RestWorkflowRuns {
+    workflow_runs: Vec<RestWorkflowRun>,
+}
+
+#[derive(Deserialize)]
+struct RestWorkflowRun {
+    #[serde(default)]
+    name: Option<String>,
+    status: Option<String>,
+    conclusion: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    head_branch: Option<String>,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+struct RestCheckRuns {
+    check_runs: Vec<RestCheckRun>,
+}
+
+#[derive(Deserialize)]
+struct RestCheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RestCombinedStatus {
+    statuses: Vec<RestStatus>,
+}
+
+#[derive(Deserialize)]
+struct RestStatus {
+    state: String,
+}
+
+pub(crate) struct GithubClient {
+    agent: ureq::Agent,
+    token: String,
+}
+
+impl GithubClient {
+    pub(crate) fn new(token: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(API_TIMEOUT_SECS))
+            .user_agent(USER_AGENT)
+            .build();
+        Self {
+            agent,
+            token: token.to_string(),
+        }
+    }
+
+    pub(crate) fn list_open_prs(
+        &self,
+        repo: &GithubRepo,
+        branches: &[String],
+        limit: usize,
+    ) -> Option<Vec<PullRequest>> {
+        let pulls: Vec<RestPull> = self.get(
+            repo,
+            &format!("pulls?state=open&per_page={}", limit.min(100)),
+        )?;
+        let mut prs = Vec::new();
+        for pull in pulls.into_iter().take(limit) {
+            let tracked = branches.iter().any(|b| b == &pull.head.ref_name);
+            let (review_decision, status_check_rollup, detail, comments) = if tracked {
+                (
+                    self.review_decision(repo, &pull),
+                    self.check_rollup(repo, &pull.head.sha),
+                    self.get::<RestPullDetail>(repo, &format!("pulls/{}", pull.number)),
+                    self.comments(repo, pull.number),
+                )
+            } else {
+                (None, Vec::new(), None, Vec::new())
+            };
+            let mergeable = detail.as_ref().and_then(|d| d.mergeable).map(|m| {
+                if m {
+                    "MERGEABLE".to_string()
+                } else {
+                    "CONFLICTING".to_string()
+                }
+            });
+            let merge_state_status = detail
+                .and_then(|d| d.mergeable_state)
+                .map(|s| s.to_uppercase());
+            prs.push(PullRequest {
+                id: pull.node_id,
+                number: pull.number,
+                title: pull.title,
+                url: pull.html_url,
+                state: pull.state.to_uppercase(),
+                head_ref_name: pull.head.ref_name,
+                is_draft: pull.draft,
+                review_decision,
+                mergeable,
+                merge_state_status,
+                status_check_rollup,
+                comments,
+                reviews: Vec::new(),
+            });
+        }
+        Some(prs)
+    }
+
+    pub(crate) fn list_workflow_runs(
+        &self,
+        repo: &GithubRepo,
+        limit: usize,
+    ) -> Option<Vec<WorkflowRun>> {
+        let runs: RestWorkflowRuns =
+            self.get(repo, &format!("actions/runs?per_page={}", limit.min(100)))?;
+        Some(
+            runs.workflow_runs
+                .into_iter()
+                .map(|run| WorkflowRun {
+                    name: run.name.unwrap_or_default(),
+                    status: run.status.unwrap_or_default(),
+                    conclusion: run.conclusion.unwrap_or_default(),
+                    url: run.html_url,
+                    head_branch: run.head_branch.unwrap_or_default(),
+                    created_at: run.created_at,
+                })
+                .collect(),
+        )
+    }
+
+    fn review_decision(&self, repo: &GithubRepo, pull: &RestPull) -> Option<String> {
+        let reviews: Vec<RestReview> =
+            self.get(repo, &format!("pulls/{}/reviews?per_page=100", pull.number))?;
+        let mut latest: HashMap<String, String> = HashMap::new();
+        for review in reviews {
+            let state = review.state.to_uppercase();
+            if state != "APPROVED" && state != "CHANGES_REQUESTED" && state != "DISMISSED" {
+                continue;
+            }
+            if let Some(user) = review.user {
+                latest.insert(user.login, state);
+            }
+        }
+        if latest.values().any(|state| state == "CHANGES_REQUESTED") {
+            Some("CHANGES_REQUESTED".to_string())
+        } else if latest.values().any(|state| state == "APPROVED") {
+            Some("APPROVED".to_string())
+        } else if !pull.requested_reviewers.is_empty() {
+            Some("REVIEW_REQUIRED".to_string())
+        } else {
+            None
+        }
+    }
+
+    fn comments(&self, repo: &GithubRepo, number: i64) -> Vec<PrComment> {
+        let paths = [
+            format!("issues/{}/comments?per_page=100", number),
+            format!("pulls/{}/comments?per_page=100", number),
+        ];
+        paths
+            .iter()
+            .filter_map(|path| self.get::<Vec<RestComment>>(repo, path))
+            .flatten()
+            .map(|comment| PrComment {
+                author: comment.user.map(|user| PrAuthor { login: user.login }),
+                body: comment.body.unwrap_or_default(),
+                created_at: comment.created_at,
+            })
+            .collect()
+    }
+
+    fn check_rollup(&self, repo: &GithubRepo, sha: &str) -> Vec<CheckRollupItem> {
+        let mut items = Vec::new();
+        if let Some(runs) =
+            self.get::<RestCheckRuns>(repo, &format!("commits/{}/check-runs?per_page=100", sha))
+        {
+            items.extend(runs.check_runs.into_iter().map(|run| CheckRollupItem {
+                status: Some(run.status),
+                conclusion: run.conclusion,
+                state: None,
+            }));
+        }
+        if let Some(combined) =
+            self.get::<RestCombinedStatus>(repo, &format!("commits/{}/status", sha))
+        {
+            items.extend(combined.statuses.into_iter().map(|status| CheckRollupItem {
+                status: None,
+                conclusion: None,
+                state: Some(status.state),
+            }));
+        }
+        items
+    }
+
+    pub(crate) fn mark_ready(&self, repo: &GithubRepo, node_id: &str) -> Result<(), Error> {
+        if node_id.is_empty() {
+            return Err(Error::Github("PR node id unavailable".to_string()));
+        }
+        let body = json!({
+            "query": "mutation($id: ID!) { markPullRequestReadyForReview(input: {pullRequestId: $id}) { pullRequest { isDraft } } }",
+            "variables": { "id": node_id },
+        });
+        let response = self
+            .agent
+            .post(&repo.graphql_url())
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string())?
+            .into_string()?;
+        let value: Value = serde_json::from_str(&response)?;
+        if let Some(message) = value
+            .get("errors")
+            .and_then(Value::as_array)
+            .and_then(|errors| errors.first())
+            .and_then(|error| error.get("message"))
+            .and_then(Value::as_str)
+        {
+            return Err(Error::Github(message.to_string()));
+        }
+        Ok(())
+    }
+
+    fn get<T: DeserializeOwned>(&self, repo: &GithubRepo, path: &str) -> Option<T> {
+        let url = format!(
+            "{}/repos/{}/{}/{}",
+            repo.api_base, repo.owner, repo.name, path
+        );
+        let body = self
+            .agent
+            .get(&url)
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("Accept", "application/vnd.github+json")
+            .set("X-GitHub-Api-Version", "2022-11-28")
+            .call()
+            .ok()?
+            .into_string()
+            .ok()?;
+        serde_json::from_str(&body).ok()
+    }
+}
+
+impl GithubRepo {
+    pub(crate) fn compare_url(&self, branch: &str) -> String {
+        let web_base = if self.api_base == "https://api.github.com" {
+            "https://github.com"
+        } else {
+            self.api_base.trim_end_matches("/api/v3")
+        };
+        format!(
+            "{}/{}/{}/compare/{}?expand=1",
+            web_base, self.owner, self.name, branch
+        )
+    }
+
+    fn graphql_url(&self) -> String {
+        match self.api_base.strip_suffix("/v3") {
+            Some(base) => format!("{}/graphql", base),
+            None => format!("{}/graphql", self.api_base),
+        }
+    }
+}
+
+pub(crate) fn repo_for_path(repo_path: &str) -> Option<GithubRepo> {
+    parse_remote_url(&git::remote_url(repo_path, "origin")?)
+}
+
+fn parse_remote_url(url: &str) -> Option<GithubRepo> {
+    let url = url.trim();
+    let rest = if let Some((_, rest)) = url.split_once("://") {
+        rest
+    } else if let Some((user_host, path)) = url.split_once(':') {
+        return build_repo(user_host, path);
+    } else {
+        return None;
+    };
+    let (user_host, path) = rest.split_once('/')?;
+    build_repo(user_host, path)
+}
+
+fn build_repo(user_host: &str, path: &str) -> Option<GithubRepo> {
+    let host = user_host.rsplit('@').next()?;
+    let host = host.split(':').next()?.to_lowercase();
+    let mut segments = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let owner = segments.next()?.to_string();
+    let name = segments.next()?.trim_end_matches(".git").to_string();
+    if segments.next().is_some() || name.is_empty() {
+        return None;
+    }
+    let api_base = if host == "github.com" || host == "ssh.github.com" {
+        "https://api.github.com".to_string()
+    } else if host.contains("github") {
+        format!("https://{}/api/v3", host)
+    } else {
+        return None;
+    };
+    Some(GithubRepo {
+        api_base,
+        owner,
+        name,
+    })
+}
@@ -0,0 +1,65 @@
+use crate::error::Error;
+use crate::model::{MonitorNotification, NotificationHistoryEntry};
+use crate::paths::notification_history_file;
+use crate::store;
+use std::fs;
+use std::io::{ErrorKind, Write};
+
+/// Entries kept after a trim; the file is allowed to grow to twice this first.
+const MAX_HISTORY_ENTRIES: usize = 1_000;
+
+pub(crate) fn history_entry(
+    notification: &MonitorNotification,
+    status: &str,
+    ts_ms: i64,
+) -> NotificationHistoryEntry {
+    NotificationHistoryEntry {
+        ts_ms,
+        title: notification.title.clone(),
+        message: notification.message.clone(),
+        kind: notification.kind.clone(),
+        key: notification.key.clone(),
+        status: status.to_string(),
+    }
+}
+
+pub fn append_notification_history(entries: &[NotificationHistoryEntry]) -> Result<(), Error> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let path = notification_history_file();
+    store::with_lock(&path, || {
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?
+            .write_all(lines.as_bytes())?;
+        let raw = fs::read_to_string(&path)?;
+        let count = raw.lines().count();
+        if count > MAX_HISTORY_ENTRIES * 2 {
+            let kept: Vec<&str> = raw.lines().skip(count - MAX_HISTORY_ENTRIES).collect();
+            store::write_atomic(&path, format!("{}\n", kept.join("\n")).as_bytes())?;
+        }
+        Ok(())
+    })
+}
+
+/// Newest-first notification history, including ones that were not shown.
+pub fn read_notification_history(limit: usize) -> Result<Vec<NotificationHistoryEntry>, Error> {
+    let raw = match fs::read_to_string(notification_history_file()) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(raw
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit)
+        .collect())
+}
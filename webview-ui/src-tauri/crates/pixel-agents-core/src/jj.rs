@@ -0,0 +1,66 @@
+use crate::git::{command_output, GitStatus};
+use std::path::Path;
+
+pub(crate) fn is_jj_repo(repo_path: &str) -> bool {
+    Path::new(repo_path).join(".jj").is_dir()
+}
+
+pub(crate) fn repo_status(repo_path: &str) -> Option<GitStatus> {
+    // The first query snapshots the agent's edits into @; the rest read that snapshot.
+    let change_id = jj_output(
+        repo_path,
+        true,
+        &["log", "-r", "@", "--no-graph", "-T", "change_id.short()"],
+    )
+    .map(|output| output.trim().to_string())
+    .filter(|id| !id.is_empty())?;
+
+    let bookmark = jj_output(
+        repo_path,
+        false,
+        &[
+            "log",
+            "-r",
+            "latest(::@ & bookmarks())",
+            "--no-graph",
+            "-T",
+            "bookmarks.map(|b| b.name()).join(\",\")",
+        ],
+    )
+    .and_then(|output| {
+        output
+            .trim()
+            .split(',')
+            .map(|name| name.trim().trim_end_matches('*').to_string())
+            .find(|name| !name.is_empty())
+    });
+
+    let changed_files = jj_output(repo_path, false, &["diff", "-r", "@", "--summary"])
+        .map(|output| {
+            output
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count()
+        })
+        .unwrap_or(0);
+
+    Some(GitStatus {
+        vcs: "jj",
+        dirty: changed_files > 0,
+        changed_files,
+        branch: bookmark,
+        change_id: Some(change_id),
+        ahead: None,
+        behind: None,
+        stash_ts_ms: Vec::new(),
+    })
+}
+
+fn jj_output(repo_path: &str, snapshot: bool, args: &[&str]) -> Option<String> {
+    let mut full_args = vec!["--color", "never"];
+    if !snapshot {
+        full_args.push("--ignore-working-copy");
+    }
+    full_args.extend_from_slice(args);
+    command_output("jj", repo_path, &full_args)
+}
@@ -0,0 +1,119 @@
+use crate::error::Error;
+use crate::model::{LayoutBackupView, LayoutListView};
+use crate::paths::{layout_file, layouts_dir};
+use crate::settings::{
+    read_desktop_settings, read_json_file, write_desktop_setting, write_json_file,
+};
+use crate::store;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+const MAX_LAYOUT_NAME_LEN: usize = 64;
+const MAX_LAYOUT_BACKUPS: usize = 20;
+
+pub fn write_layout(layout: &Value) -> Result<(), Error> {
+    store::write_json_keeping(&layout_file(), layout, MAX_LAYOUT_BACKUPS)
+}
+
+pub fn list_layout_backups() -> Vec<LayoutBackupView> {
+    store::list_backups(&layout_file())
+        .into_iter()
+        .enumerate()
+        .map(|(index, (ts_ms, _))| LayoutBackupView { index, ts_ms })
+        .collect()
+}
+
+pub fn read_layout_backup(index: usize) -> Result<Value, Error> {
+    let (_, path) = store::list_backups(&layout_file())
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| Error::Invalid(format!("layout backup {} does not exist", index)))?;
+    read_json_file(&path)
+}
+
+pub fn list_layouts() -> LayoutListView {
+    let mut names: Vec<String> = fs::read_dir(layouts_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| {
+                    path.file_stem()
+                        .map(|stem| stem.to_string_lossy().into_owned())
+                })
+                .filter(|name| validate_layout_name(name).is_ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    LayoutListView {
+        names,
+        active: read_active_layout_name(),
+    }
+}
+
+pub fn read_active_layout_name() -> Option<String> {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| {
+            v.get("activeLayout")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .filter(|name| validate_layout_name(name).is_ok())
+}
+
+pub fn write_active_layout_name(name: &str) -> Result<(), Error> {
+    validate_layout_name(name)?;
+    write_desktop_setting("activeLayout", Value::String(name.to_string()))
+}
+
+pub fn read_named_layout(name: &str) -> Result<Value, Error> {
+    let path = named_layout_file(name)?;
+    if !path.exists() {
+        return Err(Error::Invalid(format!("layout {} does not exist", name)));
+    }
+    read_json_file(&path)
+}
+
+pub fn write_named_layout(name: &str, layout: &Value) -> Result<(), Error> {
+    write_json_file(&named_layout_file(name)?, layout)
+}
+
+fn named_layout_file(name: &str) -> Result<PathBuf, Error> {
+    validate_layout_name(name)?;
+    Ok(layouts_dir().join(format!("{}.json", name)))
+}
+
+fn validate_layout_name(name: &str) -> Result<(), Error> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_LAYOUT_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Invalid(format!(
+            "invalid layout name {:?}: use letters, digits, '-' or '_'",
+            name
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_names_are_plain_file_stems() {
+        assert!(validate_layout_name("office").is_ok());
+        assert!(validate_layout_name("focus_2-b").is_ok());
+        assert!(validate_layout_name("").is_err());
+        assert!(validate_layout_name("../layout").is_err());
+        assert!(validate_layout_name("my layout").is_err());
+        assert!(validate_layout_name(&"a".repeat(65)).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+mod bundle;
+mod codex;
+mod codex_record;
+mod error;
+mod events;
+pub mod git;
+pub mod github;
+mod history;
+mod jj;
+pub mod layouts;
+mod migrate;
+mod model;
+mod monitor;
+mod opencode;
+mod opencode_record;
+pub mod paths;
+mod pr;
+pub mod prefs;
+mod process;
+mod push;
+mod reset;
+mod scan;
+mod search;
+pub mod settings;
+mod snapshot;
+mod store;
+mod transcript;
+mod updates;
+mod usage;
+mod util;
+pub mod watch;
+mod webhooks;
+mod windsurf;
+
+pub use bundle::{export_bundle, import_bundle};
+pub use error::Error;
+pub use events::read_agent_events;
+pub use history::{append_notification_history, read_notification_history};
+pub use model::*;
+pub use monitor::{apply_quiet_hours, disabled_tick, test_notification, Monitor};
+pub use push::{
+    dispatch_push, read_push_settings, send_push, write_push_settings, PushSettings, PushTarget,
+};
+pub use reset::{reset_data, ResetScope};
+pub use scan::{normalize_source_name, AgentTemp};
+pub use search::search_sessions;
+pub use settings::{
+    ErrorPatterns, Hotkeys, MonitorSettings, PipCorner, PipGeometry, QuietHours, QuietMode,
+    ScanLimits,
+};
+pub use snapshot::{render_snapshot, SnapshotFormat};
+pub use transcript::{read_transcript, render_transcript, session_location, TranscriptFormat};
+pub use updates::check_for_updates;
+pub use usage::{read_usage_stats, render_session_usage, StatsFormat, UsageRange};
+pub use util::{agent_key_from_link, now_ms};
+pub use webhooks::{
+    dispatch_webhooks, read_webhooks, send_webhook, write_webhooks, Webhook, WebhookFormat,
+    WEBHOOK_EVENTS,
+};
@@ -0,0 +1,78 @@
+use serde_json::{json, Map, Value};
+
+type Migration = fn(Value) -> Value;
+
+pub(crate) struct Schema {
+    name: &'static str,
+    migrations: &'static [Migration],
+}
+
+pub(crate) const MONITOR_SETTINGS: Schema = Schema {
+    name: "monitor-settings",
+    migrations: &[identity],
+};
+
+pub(crate) const DESKTOP_SETTINGS: Schema = Schema {
+    name: "desktop-settings",
+    migrations: &[identity],
+};
+
+pub(crate) const REPO_BINDINGS: Schema = Schema {
+    name: "repo-bindings",
+    migrations: &[repo_bindings_v1],
+};
+
+pub(crate) const AGENT_PREFS: Schema = Schema {
+    name: "agent-prefs",
+    migrations: &[identity],
+};
+
+impl Schema {
+    pub(crate) fn version(&self) -> u64 {
+        self.migrations.len() as u64
+    }
+
+    pub(crate) fn migrate(&self, mut value: Value) -> (Value, bool) {
+        let from = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+        if from > self.version() {
+            tracing::warn!(
+                schema = self.name,
+                version = from,
+                supported = self.version(),
+                "settings file is newer than this build"
+            );
+            return (value, false);
+        }
+        if from == self.version() {
+            return (value, false);
+        }
+        for migration in &self.migrations[from as usize..] {
+            value = migration(value);
+        }
+        self.stamp(&mut value);
+        tracing::info!(
+            schema = self.name,
+            from,
+            to = self.version(),
+            "migrated settings file"
+        );
+        (value, true)
+    }
+
+    pub(crate) fn stamp(&self, value: &mut Value) {
+        if !value.is_object() {
+            *value = Value::Object(Map::new());
+        }
+        if let Some(map) = value.as_object_mut() {
+            map.insert("version".to_string(), json!(self.version()));
+        }
+    }
+}
+
+fn identity(value: Value) -> Value {
+    value
+}
+
+fn repo_bindings_v1(value: Value) -> Value {
+    json!({ "bindings": value })
+}
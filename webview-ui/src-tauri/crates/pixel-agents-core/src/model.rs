@@ -0,0 +1,336 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorAlert {
+    pub kind: String,
+    pub message: String,
+    pub ts_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorEventView {
+    pub ts_ms: i64,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub state_hint: String,
+    pub text: Option<String>,
+    pub files_touched: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptEntry {
+    pub ts_ms: Option<i64>,
+    pub role: String,
+    pub text: String,
+    pub tool: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorCommitView {
+    pub hash: String,
+    pub subject: String,
+    pub ts_ms: i64,
+    pub author: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorPrView {
+    pub number: i64,
+    pub title: String,
+    pub url: String,
+    pub state: String,
+    pub checks: Option<String>,
+    pub review: Option<String>,
+    pub conflicts: bool,
+    pub comments: usize,
+    pub draft: bool,
+    pub match_confidence: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorSourceDiagnostics {
+    pub source: String,
+    pub enabled: bool,
+    pub roots: Vec<MonitorDataRootView>,
+    pub last_scan_ms: Option<i64>,
+    pub duration_ms: Option<u64>,
+    pub agents: usize,
+    pub files: usize,
+    pub parse_errors: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorSourceStatus {
+    pub source: String,
+    pub status: String,
+    pub last_scan_ms: Option<i64>,
+    pub agents: usize,
+    pub parse_errors: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorDataRootView {
+    pub path: String,
+    pub found: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorWorkflowRunView {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MonitorAgentView {
+    pub key: String,
+    pub source: String,
+    pub session_id: String,
+    pub agent_id: String,
+    pub display_name: String,
+    pub pinned: bool,
+    pub note: Option<String>,
+    pub muted: bool,
+    pub state: String,
+    pub last_ts_ms: i64,
+    pub last_text: Option<String>,
+    pub repo_path: Option<String>,
+    pub vcs: Option<String>,
+    pub branch: Option<String>,
+    pub change_id: Option<String>,
+    pub worktree_branch: Option<String>,
+    pub ahead: Option<usize>,
+    pub behind: Option<usize>,
+    pub stash_count: Option<usize>,
+    pub pid: Option<u32>,
+    pub cpu_percent: Option<f32>,
+    pub rss_bytes: Option<u64>,
+    pub files_touched: Vec<String>,
+    pub alerts: Vec<MonitorAlert>,
+    pub recent_events: Vec<MonitorEventView>,
+    pub commits: Vec<MonitorCommitView>,
+    pub prs: Vec<MonitorPrView>,
+    pub workflow_run: Option<MonitorWorkflowRunView>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorRepoView {
+    pub path: String,
+    pub last_activity_ms: Option<i64>,
+    pub session_count: usize,
+    pub bound: bool,
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoBindingView {
+    pub key: String,
+    pub source: String,
+    pub session_id: String,
+    pub repo_path: String,
+    pub exists: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutListView {
+    pub names: Vec<String>,
+    pub active: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    pub release_notes: Option<String>,
+    pub download_url: String,
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataDirView {
+    pub current: String,
+    pub configured: Option<String>,
+    pub env_override: Option<String>,
+    pub default_dir: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResetReport {
+    pub backup_path: String,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutBackupView {
+    pub index: usize,
+    pub ts_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorSummary {
+    pub total: usize,
+    pub active: usize,
+    pub waiting: usize,
+    pub done: usize,
+    pub error: usize,
+    pub limited: usize,
+    pub pr_pending: usize,
+    pub alerts: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorSnapshot {
+    pub summary: MonitorSummary,
+    pub agents: Vec<MonitorAgentView>,
+    pub source_status: Vec<MonitorSourceStatus>,
+    pub now_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationPriority {
+    Low,
+    Normal,
+    High,
+    Urgent,
+}
+
+impl NotificationPriority {
+    pub fn of(kind: &str) -> Self {
+        match kind {
+            "error" => Self::Urgent,
+            "waiting" | "approval" | "stuck" | "limited" => Self::High,
+            "done" => Self::Normal,
+            _ => Self::Low,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorNotification {
+    pub title: String,
+    pub message: String,
+    pub kind: String,
+    pub key: String,
+    pub silent: bool,
+    pub priority: NotificationPriority,
+    /// Every agent coalesced into a grouped notification; `key` is the first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub grouped_keys: Vec<String>,
+}
+
+impl MonitorNotification {
+    pub fn new(
+        title: impl Into<String>,
+        message: impl Into<String>,
+        kind: impl Into<String>,
+        key: impl Into<String>,
+    ) -> Self {
+        let kind = kind.into();
+        Self {
+            title: title.into(),
+            message: message.into(),
+            priority: NotificationPriority::of(&kind),
+            kind,
+            key: key.into(),
+            silent: false,
+            grouped_keys: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationHistoryEntry {
+    pub ts_ms: i64,
+    pub title: String,
+    pub message: String,
+    pub kind: String,
+    pub key: String,
+    /// `delivered`, `silent`, `snoozed`, `quiet`, `muted` or `cooldown`.
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorTickPayload {
+    pub snapshot: Option<MonitorSnapshot>,
+    pub delta: Option<MonitorSnapshotDelta>,
+    pub notifications: Vec<MonitorNotification>,
+    /// Entries for the notification history; the caller that delivers the
+    /// notifications records them.
+    #[serde(skip)]
+    pub history: Vec<NotificationHistoryEntry>,
+    /// The notifications before grouping, for webhooks and push, which should
+    /// name each agent.
+    #[serde(skip)]
+    pub remote_notifications: Vec<MonitorNotification>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorSnapshotDelta {
+    pub summary: MonitorSummary,
+    pub added: Vec<MonitorAgentView>,
+    pub updated: Vec<MonitorAgentView>,
+    pub removed: Vec<String>,
+    pub source_status: Vec<MonitorSourceStatus>,
+    pub now_ms: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageTotals {
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_tokens: u64,
+    pub cost_usd: f64,
+    pub turns: u64,
+    pub active_ms: i64,
+    pub sessions: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageBucket {
+    pub key: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionUsage {
+    pub key: String,
+    pub source: String,
+    pub session_id: String,
+    pub repo_path: Option<String>,
+    pub model: Option<String>,
+    pub started_ts_ms: i64,
+    pub ended_ts_ms: i64,
+    pub state: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageStatsPayload {
+    pub since_ms: Option<i64>,
+    pub until_ms: Option<i64>,
+    pub total: UsageTotals,
+    pub sessions: Vec<SessionUsage>,
+    pub by_source: Vec<UsageBucket>,
+    pub by_repo: Vec<UsageBucket>,
+    pub by_day: Vec<UsageBucket>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSearchHit {
+    pub key: String,
+    pub source: String,
+    pub session_id: String,
+    pub title: Option<String>,
+    pub repo_path: Option<String>,
+    pub last_ts_ms: Option<i64>,
+    pub matches: usize,
+    pub snippets: Vec<String>,
+}
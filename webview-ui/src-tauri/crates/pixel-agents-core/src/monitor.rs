@@ -0,0 +1,1656 @@
+use crate::codex::{scan_codex_source, CodexScanState};
+use crate::error::Error;
+use crate::git::{GitCache, GitStatus};
+use crate::history::history_entry;
+use crate::model::{
+    MonitorAgentView, MonitorAlert, MonitorCommitView, MonitorDataRootView, MonitorEventView,
+    MonitorNotification, MonitorPrView, MonitorRepoView, MonitorSnapshot, MonitorSnapshotDelta,
+    MonitorSourceDiagnostics, MonitorSourceStatus, MonitorSummary, MonitorTickPayload,
+    MonitorWorkflowRunView,
+};
+use crate::opencode::{scan_opencode_source, OpencodeScanState};
+use crate::paths::{
+    codex_sessions_root, opencode_db_file, opencode_message_root, opencode_part_root,
+    windsurf_cascade_root, windsurf_workspace_storage_root,
+};
+use crate::pr::PrCache;
+use crate::prefs::{read_agent_prefs, update_agent_prefs, ArchivedAgent};
+use crate::process::ProcessTable;
+use crate::scan::{
+    cap_recent_events, format_agent_display_name, normalize_source_name, AgentTemp, ScanCounts,
+    SourceScanStats,
+};
+use crate::settings::{
+    quiet_mode_now, read_github_token, read_monitor_settings, read_notifications_snoozed_until,
+    read_repo_bindings, write_repo_binding, ErrorClassifier, ErrorPatterns, MonitorSettings,
+    QuietMode, ScanLimits,
+};
+use crate::util::{now_ms, truncate_text};
+use crate::watch::WatchedSource;
+use crate::windsurf::scan_windsurf_source;
+use crate::{git, github, pr, process};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub(crate) const IDLE_AFTER_MS: i64 = 20_000;
+pub(crate) const DONE_AFTER_MS: i64 = 90_000;
+pub(crate) const MAX_AGENT_COMMITS: usize = 5;
+pub(crate) const PENDING_BIND_TTL_MS: i64 = 600_000;
+const SOURCES: [&str; 3] = ["opencode", "codex", "windsurf"];
+
+type CachedClassifier = (HashMap<String, ErrorPatterns>, Arc<ErrorClassifier>);
+
+#[derive(Default)]
+pub struct Monitor {
+    previous_states: Mutex<HashMap<String, String>>,
+    git_cache: Mutex<GitCache>,
+    pr_cache: Mutex<PrCache>,
+    pending_binds: Mutex<Vec<PendingRepoBind>>,
+    last_agents: Mutex<Vec<MonitorAgentView>>,
+    notification_history: Mutex<HashMap<(String, String), i64>>,
+    alert_first_seen: Mutex<HashMap<(String, String, String), i64>>,
+    process_table: Mutex<ProcessTable>,
+    pub(crate) opencode_scan: Mutex<OpencodeScanState>,
+    pub(crate) codex_scan: Mutex<CodexScanState>,
+    pub(crate) windsurf_scan: Mutex<WatchedSource<HashMap<String, AgentTemp>>>,
+    scan_stats: Mutex<HashMap<&'static str, SourceScanStats>>,
+    /// Compiled error patterns, rebuilt only when the patterns change.
+    error_classifier: Mutex<Option<CachedClassifier>>,
+}
+
+impl Monitor {
+    pub fn enabled_sources(settings: &MonitorSettings) -> Vec<&'static str> {
+        SOURCES
+            .into_iter()
+            .filter(|source| settings.source_enabled(source))
+            .collect()
+    }
+
+    pub fn scan_source(
+        &self,
+        source: &'static str,
+        limits: &ScanLimits,
+    ) -> Result<HashMap<String, AgentTemp>, Error> {
+        let scan: SourceScanner = match source {
+            "opencode" => scan_opencode_source,
+            "codex" => scan_codex_source,
+            "windsurf" => scan_windsurf_source,
+            _ => return Err(Error::Invalid(format!("unknown source {}", source))),
+        };
+        let started = Instant::now();
+        let mut counts = ScanCounts::default();
+        let result = scan(self, limits, &mut counts);
+        record_scan_stats(self, source, &result, counts, started.elapsed());
+        result
+    }
+
+    pub fn tick(&self, full_snapshot: bool) -> Result<MonitorTickPayload, Error> {
+        let settings = read_monitor_settings();
+        if !settings.enabled {
+            return Ok(disabled_tick());
+        }
+        let limits = settings.scan_limits();
+        let results: Vec<_> = std::thread::scope(|scope| {
+            Self::enabled_sources(&settings)
+                .into_iter()
+                .map(|source| {
+                    (
+                        source,
+                        scope.spawn(move || self.scan_source(source, &limits)),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|(source, task)| (source, task.join()))
+                .collect()
+        });
+        let mut map: HashMap<String, AgentTemp> = HashMap::new();
+        for (source, result) in results {
+            match result.map_err(|_| Error::State(format!("{} scanner failed", source)))? {
+                Ok(agents) => map.extend(agents),
+                Err(e) => tracing::warn!(source, error = %e, "scanner failed"),
+            }
+        }
+        self.build_tick(settings, map, read_repo_bindings(), full_snapshot)
+    }
+
+    pub fn deep_scan(&self) -> Result<MonitorTickPayload, Error> {
+        self.reset_scans()?;
+        self.tick(true)
+    }
+
+    pub fn reset_scans(&self) -> Result<(), Error> {
+        *self
+            .opencode_scan
+            .lock()
+            .map_err(|_| Error::lock("opencode scan"))? = OpencodeScanState::default();
+        *self
+            .codex_scan
+            .lock()
+            .map_err(|_| Error::lock("codex scan"))? = CodexScanState::default();
+        self.windsurf_scan
+            .lock()
+            .map_err(|_| Error::lock("windsurf scan"))?
+            .reset();
+        Ok(())
+    }
+
+    pub fn build_tick(
+        &self,
+        settings: MonitorSettings,
+        map: HashMap<String, AgentTemp>,
+        repo_bindings: HashMap<String, String>,
+        full_snapshot: bool,
+    ) -> Result<MonitorTickPayload, Error> {
+        build_monitor_tick(self, settings, map, repo_bindings, full_snapshot)
+    }
+
+    pub fn diagnostics(
+        &self,
+        settings: &MonitorSettings,
+    ) -> Result<Vec<MonitorSourceDiagnostics>, Error> {
+        let stats = self
+            .scan_stats
+            .lock()
+            .map_err(|_| Error::lock("scan stats"))?;
+        Ok(SOURCES
+            .into_iter()
+            .map(|source| {
+                let stat = stats.get(source);
+                MonitorSourceDiagnostics {
+                    source: source.to_string(),
+                    enabled: settings.source_enabled(source) && settings.enabled,
+                    roots: source_data_roots(source)
+                        .into_iter()
+                        .map(|root| MonitorDataRootView {
+                            found: root.exists(),
+                            path: root.to_string_lossy().into_owned(),
+                        })
+                        .collect(),
+                    last_scan_ms: stat.map(|s| s.last_scan_ms),
+                    duration_ms: stat.map(|s| s.duration_ms),
+                    agents: stat.map(|s| s.agents).unwrap_or(0),
+                    files: stat.map(|s| s.files).unwrap_or(0),
+                    parse_errors: stat.map(|s| s.parse_errors).unwrap_or(0),
+                    error: stat.and_then(|s| s.error.clone().or_else(|| s.warning.clone())),
+                }
+            })
+            .collect())
+    }
+
+    fn source_status(&self, settings: &MonitorSettings) -> Result<Vec<MonitorSourceStatus>, Error> {
+        let stats = self
+            .scan_stats
+            .lock()
+            .map_err(|_| Error::lock("scan stats"))?;
+        Ok(SOURCES
+            .into_iter()
+            .map(|source| {
+                let stat = stats.get(source);
+                let status = match stat {
+                    _ if !settings.source_enabled(source) => "disabled",
+                    None => "pending",
+                    Some(s) if s.error.is_some() => "error",
+                    Some(s) if s.warning.is_some() || s.parse_errors > 0 => "degraded",
+                    Some(_) => "ok",
+                };
+                MonitorSourceStatus {
+                    source: source.to_string(),
+                    status: status.to_string(),
+                    last_scan_ms: stat.map(|s| s.last_scan_ms),
+                    agents: stat.map(|s| s.agents).unwrap_or(0),
+                    parse_errors: stat.map(|s| s.parse_errors).unwrap_or(0),
+                    error: stat.and_then(|s| s.error.clone().or_else(|| s.warning.clone())),
+                }
+            })
+            .collect())
+    }
+
+    pub fn snapshot(&self) -> Result<MonitorSnapshot, Error> {
+        let agents = self
+            .last_agents
+            .lock()
+            .map_err(|_| Error::lock("agent"))?
+            .clone();
+        Ok(MonitorSnapshot {
+            summary: summarize(&agents),
+            source_status: self.source_status(&read_monitor_settings())?,
+            agents,
+            now_ms: now_ms(),
+        })
+    }
+
+    fn error_classifier(&self, settings: &MonitorSettings) -> Result<Arc<ErrorClassifier>, Error> {
+        let mut cache = self
+            .error_classifier
+            .lock()
+            .map_err(|_| Error::lock("error classifier"))?;
+        if let Some((patterns, classifier)) = cache.as_ref() {
+            if *patterns == settings.error_patterns {
+                return Ok(classifier.clone());
+            }
+        }
+        let classifier = Arc::new(settings.error_classifier().unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "ignoring error patterns");
+            Default::default()
+        }));
+        *cache = Some((settings.error_patterns.clone(), classifier.clone()));
+        Ok(classifier)
+    }
+
+    pub fn find_agent(&self, agent_key: &str) -> Result<MonitorAgentView, Error> {
+        self.last_agents
+            .lock()
+            .map_err(|_| Error::lock("agent"))?
+            .iter()
+            .find(|agent| agent.key == agent_key)
+            .cloned()
+            .ok_or_else(|| Error::Invalid(format!("unknown agent {}", agent_key)))
+    }
+
+    pub fn kill_agent(&self, agent_key: &str, force: bool) -> Result<u32, Error> {
+        let agent = self.find_agent(agent_key)?;
+        let mut table = self
+            .process_table
+            .lock()
+            .map_err(|_| Error::lock("process table"))?;
+        table.refresh();
+        // Only a session-id match is trusted with a signal; a cwd match could be
+        // another session working in the same repo.
+        let pid = match process::agent_pids(table.system(), &agent, false)[..] {
+            [pid] => pid,
+            [] => {
+                return Err(Error::Invalid(format!(
+                    "No process names the session of {}; stop it from its terminal",
+                    agent.display_name
+                )))
+            }
+            _ => {
+                return Err(Error::Invalid(format!(
+                    "Several processes match {}; stop it from its terminal",
+                    agent.display_name
+                )))
+            }
+        };
+        let signalled = table
+            .system()
+            .process(pid)
+            .is_some_and(|found| process::kill_process(found, force));
+        if !signalled {
+            return Err(Error::State(format!("Failed to signal process {}", pid)));
+        }
+        tracing::info!(
+            agent = agent_key,
+            pid = pid.as_u32(),
+            force,
+            "agent process signalled"
+        );
+        Ok(pid.as_u32())
+    }
+
+    pub fn list_repos(&self) -> Result<Vec<MonitorRepoView>, Error> {
+        let mut repos: HashMap<String, MonitorRepoView> = HashMap::new();
+        for agent in self
+            .last_agents
+            .lock()
+            .map_err(|_| Error::lock("agent"))?
+            .iter()
+        {
+            let Some(path) = agent.repo_path.as_deref() else {
+                continue;
+            };
+            let repo = repo_entry(&mut repos, path);
+            repo.session_count += 1;
+            if repo.last_activity_ms.is_none_or(|ts| agent.last_ts_ms > ts) {
+                repo.last_activity_ms = Some(agent.last_ts_ms);
+                repo.branch = agent.branch.clone().or(repo.branch.take());
+            }
+        }
+        for path in read_repo_bindings().values() {
+            repo_entry(&mut repos, path).bound = true;
+        }
+        for worktree in self
+            .git_cache
+            .lock()
+            .map_err(|_| Error::lock("git cache"))?
+            .known_worktrees()
+        {
+            let repo = repo_entry(&mut repos, &worktree.path);
+            if repo.branch.is_none() {
+                repo.branch = worktree.branch.clone();
+            }
+        }
+        let mut repos: Vec<MonitorRepoView> = repos.into_values().collect();
+        repos.sort_by(|a, b| {
+            b.last_activity_ms
+                .cmp(&a.last_activity_ms)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        Ok(repos)
+    }
+
+    pub fn archive_agent(&self, agent_key: &str) -> Result<ArchivedAgent, Error> {
+        let mut last_agents = self.last_agents.lock().map_err(|_| Error::lock("agent"))?;
+        let agent = last_agents.iter().find(|agent| agent.key == agent_key);
+        let archived = ArchivedAgent {
+            key: agent_key.to_string(),
+            source: agent
+                .map(|agent| agent.source.clone())
+                .or_else(|| {
+                    agent_key
+                        .split_once(':')
+                        .map(|(source, _)| source.to_string())
+                })
+                .unwrap_or_default(),
+            display_name: agent.map(|agent| agent.display_name.clone()),
+            archived_ms: now_ms(),
+        };
+        update_agent_prefs(|prefs| {
+            prefs.archived.retain(|a| a.key != agent_key);
+            prefs.archived.push(archived.clone());
+        })?;
+        last_agents.retain(|agent| agent.key != agent_key);
+        Ok(archived)
+    }
+
+    pub fn set_agent_name(&self, agent_key: &str, name: Option<&str>) -> Result<(), Error> {
+        let agent = self.find_agent(agent_key)?;
+        update_agent_prefs(|prefs| prefs.set_name(agent_key, agent.repo_path.as_deref(), name))
+    }
+
+    pub fn bind_pending(&self, source: String, repo_path: String) -> Result<(), Error> {
+        let mut pending = self
+            .pending_binds
+            .lock()
+            .map_err(|_| Error::lock("pending bind"))?;
+        pending.push(PendingRepoBind {
+            source,
+            repo_path,
+            created_ms: now_ms(),
+        });
+        Ok(())
+    }
+
+    pub fn mark_pr_ready(&self, agent_key: &str) -> Result<String, Error> {
+        let agent = self.find_agent(agent_key)?;
+        if agent.state != "done" && agent.state != "idle" {
+            return Err(Error::Invalid("agent is still working".to_string()));
+        }
+        let view = agent
+            .prs
+            .iter()
+            .find(|pr| pr.draft)
+            .ok_or_else(|| Error::Invalid("agent has no draft PR".to_string()))?;
+        if view.checks.as_deref().is_some_and(|c| c != "passing") {
+            return Err(Error::Invalid(format!(
+                "checks are {} for #{}",
+                view.checks.as_deref().unwrap_or_default(),
+                view.number
+            )));
+        }
+        let (Some(repo_path), Some(branch)) = (agent.repo_path.as_deref(), agent.branch.as_deref())
+        else {
+            return Err(Error::Invalid("agent has no repo branch".to_string()));
+        };
+        let mut pr_cache = self.pr_cache.lock().map_err(|_| Error::lock("pr cache"))?;
+        let pr = pr_cache
+            .prs_for_branch(repo_path, branch)
+            .into_iter()
+            .find(|pr| pr.url == view.url)
+            .ok_or_else(|| Error::Github("PR is no longer cached".to_string()))?;
+        pr::mark_ready(repo_path, &pr, read_github_token().as_deref())?;
+        pr_cache.mark_ready_cached(&pr.url);
+        Ok(pr.url)
+    }
+
+    pub fn pr_url(&self, agent_key: &str) -> Result<String, Error> {
+        let agent = self.find_agent(agent_key)?;
+        if let Some(pr) = agent.prs.first() {
+            return Ok(pr.url.clone());
+        }
+        let (Some(repo_path), Some(branch)) = (agent.repo_path.as_deref(), agent.branch.as_deref())
+        else {
+            return Err(Error::Invalid("agent has no repo branch".to_string()));
+        };
+        Ok(github::repo_for_path(repo_path)
+            .ok_or_else(|| Error::Github("repo has no GitHub remote".to_string()))?
+            .compare_url(branch))
+    }
+}
+
+pub fn disabled_tick() -> MonitorTickPayload {
+    MonitorTickPayload {
+        snapshot: Some(MonitorSnapshot {
+            summary: MonitorSummary {
+                total: 0,
+                active: 0,
+                waiting: 0,
+                done: 0,
+                error: 0,
+                limited: 0,
+                pr_pending: 0,
+                alerts: 0,
+            },
+            agents: Vec::new(),
+            source_status: Vec::new(),
+            now_ms: now_ms(),
+        }),
+        delta: None,
+        notifications: Vec::new(),
+        history: Vec::new(),
+        remote_notifications: Vec::new(),
+    }
+}
+
+pub fn test_notification(kind: &str) -> Result<MonitorNotification, Error> {
+    let (title, message) = match kind {
+        "done" => ("Agent done", "Completed"),
+        "error" => ("Agent error", "Error"),
+        "limited" => ("Agent rate limited", "Rate limit reached"),
+        "waiting" => ("Agent waiting for input", "Waiting for input"),
+        "approval" => ("Agent needs approval", "Approval requested"),
+        "stuck" => ("Agent may be stuck", "No new events while running"),
+        "uncommitted" => ("Uncommitted work", "Uncommitted changes in repo"),
+        "checks_failed" => ("PR checks failed", "CI checks failed"),
+        "pr_comment" => ("New PR comment", "New review comment"),
+        "changes_requested" => ("Changes requested", "Reviewer requested changes"),
+        other => {
+            return Err(Error::Invalid(format!(
+                "unknown notification kind: {}",
+                other
+            )))
+        }
+    };
+    Ok(MonitorNotification::new(
+        title,
+        format!("Test notification - {}", message),
+        kind,
+        format!("test:{}", kind),
+    ))
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PrTransition {
+    pub(crate) key: String,
+    pub(crate) title: &'static str,
+    pub(crate) kind: &'static str,
+    pub(crate) detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PendingRepoBind {
+    pub(crate) source: String,
+    pub(crate) repo_path: String,
+    pub(crate) created_ms: i64,
+}
+
+pub(crate) fn build_monitor_tick(
+    state: &Monitor,
+    settings: MonitorSettings,
+    mut map: HashMap<String, AgentTemp>,
+    repo_bindings: HashMap<String, String>,
+    full_snapshot: bool,
+) -> Result<MonitorTickPayload, Error> {
+    let limits = settings.scan_limits();
+    let prefs = read_agent_prefs();
+    map.retain(|key, agent| {
+        if prefs.is_archived(key) {
+            return false;
+        }
+        let source = normalize_source_name(&agent.source);
+        if source == "claude" {
+            return settings.enable_claude;
+        }
+        true
+    });
+    cap_recent_events(map.values_mut(), limits.total_recent_events);
+
+    for agent in map.values_mut() {
+        if agent.repo_path.is_none() {
+            if let Some(path) = repo_bindings.get(&agent.key) {
+                agent.repo_path = Some(path.clone());
+            }
+        }
+        if let Some(root) = agent.repo_path.as_deref().and_then(git::find_repo_root) {
+            agent.repo_path = Some(root);
+        }
+    }
+
+    let now = now_ms();
+    apply_pending_binds(state, &mut map, now)?;
+
+    let mut worktree_branches: HashMap<String, String> = HashMap::new();
+    let mut agent_commits: HashMap<String, Vec<MonitorCommitView>> = HashMap::new();
+    let mut agent_git_status: HashMap<String, GitStatus> = HashMap::new();
+    if settings.enable_git {
+        let mut git_cache = state
+            .git_cache
+            .lock()
+            .map_err(|_| Error::lock("git cache"))?;
+        git_cache.begin_tick(now, settings.git_poll_interval_ms);
+        for agent in map.values_mut() {
+            let Some(cwd) = agent.repo_path.clone() else {
+                continue;
+            };
+            let worktrees = git_cache.worktrees_for(&cwd);
+            if let Some(worktree) = git::find_worktree(&worktrees, &cwd) {
+                agent.repo_path = Some(worktree.path.clone());
+                if let Some(branch) = &worktree.branch {
+                    worktree_branches.insert(agent.key.clone(), branch.clone());
+                }
+            }
+            let Some(repo_path) = agent.repo_path.as_deref() else {
+                continue;
+            };
+            if let Some(status) = git_cache.status_for(repo_path) {
+                agent_git_status.insert(agent.key.clone(), status);
+            }
+            let commits: Vec<MonitorCommitView> = git_cache
+                .commits_for(repo_path)
+                .into_iter()
+                .filter(|commit| commit.ts_ms >= agent.started_ts_ms)
+                .take(MAX_AGENT_COMMITS)
+                .map(|commit| MonitorCommitView {
+                    hash: commit.hash,
+                    subject: commit.subject,
+                    ts_ms: commit.ts_ms,
+                    author: commit.author,
+                })
+                .collect();
+            if !commits.is_empty() {
+                agent_commits.insert(agent.key.clone(), commits);
+            }
+        }
+    }
+
+    let agent_branches: HashMap<String, String> = map
+        .keys()
+        .filter_map(|key| {
+            agent_git_status
+                .get(key)
+                .and_then(|s| s.branch.clone())
+                .or_else(|| worktree_branches.get(key).cloned())
+                .map(|branch| (key.clone(), branch))
+        })
+        .collect();
+
+    let mut agent_prs: HashMap<String, Vec<MonitorPrView>> = HashMap::new();
+    let mut agent_runs: HashMap<String, MonitorWorkflowRunView> = HashMap::new();
+    let mut pr_transitions: Vec<PrTransition> = Vec::new();
+    if settings.enable_pr {
+        let mut pr_cache = state.pr_cache.lock().map_err(|_| Error::lock("pr cache"))?;
+        let mut repo_branches: HashMap<String, Vec<String>> = HashMap::new();
+        let mut unplaced_branches: Vec<String> = Vec::new();
+        for agent in map.values() {
+            let template_branch =
+                render_branch_template(&settings.branch_template, &agent.source, &agent.session_id);
+            let wanted = agent_branches
+                .get(&agent.key)
+                .into_iter()
+                .chain(&template_branch);
+            match agent.repo_path.as_ref() {
+                Some(repo_path) => {
+                    let branches = repo_branches.entry(repo_path.clone()).or_default();
+                    for branch in wanted {
+                        if !branches.contains(branch) {
+                            branches.push(branch.clone());
+                        }
+                    }
+                }
+                None => unplaced_branches.extend(template_branch),
+            }
+        }
+        for branches in repo_branches.values_mut() {
+            for branch in &unplaced_branches {
+                if !branches.contains(branch) {
+                    branches.push(branch.clone());
+                }
+            }
+        }
+        let github_token = read_github_token();
+        pr_cache.refresh(
+            &repo_branches,
+            github_token.as_deref(),
+            now,
+            settings.pr_poll_interval_ms,
+        );
+        for agent in map.values() {
+            let repo_path = agent.repo_path.as_deref();
+            let branch = agent_branches.get(&agent.key);
+            let template_branch =
+                render_branch_template(&settings.branch_template, &agent.source, &agent.session_id);
+            let mut matched: Vec<(pr::PullRequest, &'static str)> = match (repo_path, branch) {
+                (Some(repo_path), Some(branch)) => {
+                    let confidence = if template_branch.as_ref() == Some(branch) {
+                        "high"
+                    } else {
+                        "medium"
+                    };
+                    pr_cache
+                        .prs_for_branch(repo_path, branch)
+                        .into_iter()
+                        .map(|pr| (pr, confidence))
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+            if matched.is_empty() {
+                if let Some(template_branch) = template_branch.as_deref() {
+                    matched = pr_cache
+                        .prs_for_head(repo_path, template_branch)
+                        .into_iter()
+                        .map(|pr| (pr, "low"))
+                        .collect();
+                }
+            }
+            let mut prs: Vec<MonitorPrView> = Vec::new();
+            for (pr, confidence) in matched {
+                let checks = pr.checks_state();
+                if let Some(checks) = checks {
+                    let previous = pr_cache.record_transition(&pr.url, "checks", checks);
+                    if previous.as_deref() == Some("pending") && checks == "failing" {
+                        pr_transitions.push(PrTransition {
+                            key: agent.key.clone(),
+                            title: "PR checks failed",
+                            kind: "checks_failed",
+                            detail: format!("#{} {}", pr.number, pr.title),
+                        });
+                    }
+                }
+                let conflicts = pr.has_conflicts();
+                let comments = pr.comment_count();
+                let previous =
+                    pr_cache.record_transition(&pr.url, "comments", &comments.to_string());
+                let previous_comments = previous.and_then(|p| p.parse::<usize>().ok());
+                if previous_comments.is_some_and(|p| comments > p) {
+                    if let Some(comment) = pr.latest_comment() {
+                        let first_line = comment
+                            .body
+                            .lines()
+                            .map(str::trim)
+                            .find(|line| !line.is_empty())
+                            .unwrap_or_default();
+                        let author = comment
+                            .author
+                            .as_ref()
+                            .map(|a| a.login.as_str())
+                            .unwrap_or("someone");
+                        pr_transitions.push(PrTransition {
+                            key: agent.key.clone(),
+                            title: "New PR comment",
+                            kind: "pr_comment",
+                            detail: truncate_text(format!(
+                                "#{} {}: {}",
+                                pr.number, author, first_line
+                            )),
+                        });
+                    }
+                }
+                let review = pr.review_state();
+                if let Some(review) = review.as_deref() {
+                    let previous = pr_cache.record_transition(&pr.url, "review", review);
+                    if previous.is_some() && review == "changes_requested" {
+                        pr_transitions.push(PrTransition {
+                            key: agent.key.clone(),
+                            title: "Changes requested",
+                            kind: "changes_requested",
+                            detail: format!("#{} {}", pr.number, pr.title),
+                        });
+                    }
+                }
+                prs.push(MonitorPrView {
+                    number: pr.number,
+                    title: pr.title,
+                    url: pr.url,
+                    state: pr.state,
+                    checks: checks.map(|c| c.to_string()),
+                    review,
+                    conflicts,
+                    comments,
+                    draft: pr.is_draft,
+                    match_confidence: confidence.to_string(),
+                });
+            }
+            if !prs.is_empty() {
+                agent_prs.insert(agent.key.clone(), prs);
+            }
+            let (Some(repo_path), Some(branch)) = (repo_path, branch) else {
+                continue;
+            };
+            if let Some(run) = pr_cache.latest_run_for_branch(repo_path, branch) {
+                agent_runs.insert(
+                    agent.key.clone(),
+                    MonitorWorkflowRunView {
+                        name: run.name,
+                        status: run.status.to_lowercase(),
+                        conclusion: Some(run.conclusion.to_lowercase()).filter(|c| !c.is_empty()),
+                        url: run.url,
+                    },
+                );
+            }
+        }
+    }
+
+    let error_classifier = state.error_classifier(&settings)?;
+    // Busy agents about to be downgraded for silence, with their state and text
+    // beforehand, so a live process can keep them busy after attach.
+    let mut silent_busy: HashMap<String, (String, Option<String>)> = HashMap::new();
+    let mut agents: Vec<MonitorAgentView> = map
+        .into_values()
+        .map(|mut a| {
+            let text = a
+                .recent_events
+                .first()
+                .and_then(|e| e.text.as_deref())
+                .or(a.last_text.as_deref())
+                .unwrap_or_default();
+            if let Some(state) =
+                error_classifier.reclassify(&normalize_source_name(&a.source), &a.state, text)
+            {
+                a.state = state.to_string();
+            }
+            let silence = now - a.last_ts_ms;
+            let stuck = settings.stuck_after_ms > 0
+                && (a.state == "running" || a.state == "thinking")
+                && silence > settings.stuck_after_ms;
+            if settings.stuck_after_ms > 0
+                && (a.state == "running" || a.state == "thinking")
+                && silence > IDLE_AFTER_MS
+            {
+                silent_busy.insert(a.key.clone(), (a.state.clone(), a.last_text.clone()));
+            }
+            let awaiting_permission = is_awaiting_permission(&a.state, &a.recent_events);
+            if (a.state == "running" || a.state == "thinking" || a.state == "waiting")
+                && silence > IDLE_AFTER_MS
+                && !awaiting_permission
+            {
+                a.state = "idle".to_string();
+                if a.last_text.as_deref() == Some("Thinking") {
+                    a.last_text = Some("Idle".to_string());
+                }
+            }
+            // Windsurf is idle from file mtimes alone; it never reports finishing.
+            if a.state == "idle" && silence > DONE_AFTER_MS && a.source != "windsurf" {
+                a.state = "done".to_string();
+                if a.last_text.is_none()
+                    || a.last_text.as_deref() == Some("Idle")
+                    || a.last_text.as_deref() == Some("Thinking")
+                {
+                    a.last_text = Some("No recent activity".to_string());
+                }
+            }
+
+            let mut alerts = if a.state == "error" {
+                vec![MonitorAlert {
+                    kind: "error".to_string(),
+                    message: a
+                        .last_text
+                        .clone()
+                        .unwrap_or_else(|| "Error detected".to_string()),
+                    ts_ms: a.last_ts_ms,
+                }]
+            } else if a.state == "limited" {
+                vec![MonitorAlert {
+                    kind: "limited".to_string(),
+                    message: a
+                        .last_text
+                        .clone()
+                        .unwrap_or_else(|| "Rate limit reached".to_string()),
+                    ts_ms: a.last_ts_ms,
+                }]
+            } else {
+                Vec::new()
+            };
+            if a.state == "done" || a.state == "idle" {
+                if let Some(status) = agent_git_status.get(&a.key).filter(|s| s.dirty) {
+                    alerts.push(MonitorAlert {
+                        kind: "uncommitted".to_string(),
+                        message: format!(
+                            "{} uncommitted change(s) in {}",
+                            status.changed_files,
+                            a.repo_path.as_deref().unwrap_or("repo")
+                        ),
+                        ts_ms: a.last_ts_ms,
+                    });
+                }
+            }
+
+            if stuck {
+                alerts.push(MonitorAlert {
+                    kind: "stuck".to_string(),
+                    message: format!(
+                        "No new events for {}+ min while running",
+                        settings.stuck_after_ms / 60_000
+                    ),
+                    ts_ms: now,
+                });
+            }
+
+            let branch = agent_branches.get(&a.key).cloned();
+            let prs = agent_prs.remove(&a.key).unwrap_or_default();
+            for pr in prs
+                .iter()
+                .filter(|pr| pr.review.as_deref() == Some("changes_requested"))
+            {
+                alerts.push(MonitorAlert {
+                    kind: "changes_requested".to_string(),
+                    message: format!("#{} {}: changes requested", pr.number, pr.title),
+                    ts_ms: now,
+                });
+            }
+            let workflow_run = agent_runs.remove(&a.key);
+            if let Some(run) = workflow_run.as_ref().filter(|run| {
+                matches!(
+                    run.conclusion.as_deref(),
+                    Some("failure" | "timed_out" | "startup_failure")
+                )
+            }) {
+                alerts.push(MonitorAlert {
+                    kind: "workflow_failed".to_string(),
+                    message: format!(
+                        "{} failed on {}",
+                        run.name,
+                        branch.as_deref().unwrap_or("branch")
+                    ),
+                    ts_ms: now,
+                });
+            }
+            for pr in prs.iter().filter(|pr| pr.conflicts) {
+                alerts.push(MonitorAlert {
+                    kind: "merge_conflict".to_string(),
+                    message: format!("#{} {}: merge conflicts with base", pr.number, pr.title),
+                    ts_ms: now,
+                });
+            }
+
+            if let Some(status) = agent_git_status.get(&a.key) {
+                let session_stashes = status
+                    .stash_ts_ms
+                    .iter()
+                    .filter(|ts| **ts >= a.started_ts_ms)
+                    .count();
+                if session_stashes > 0 {
+                    alerts.push(MonitorAlert {
+                        kind: "stash".to_string(),
+                        message: format!(
+                            "{} stash(es) created during this session",
+                            session_stashes
+                        ),
+                        ts_ms: status
+                            .stash_ts_ms
+                            .iter()
+                            .copied()
+                            .max()
+                            .unwrap_or(a.last_ts_ms),
+                    });
+                }
+            }
+
+            MonitorAgentView {
+                key: a.key.clone(),
+                source: normalize_source_name(&a.source),
+                session_id: a.session_id.clone(),
+                agent_id: a.session_id.clone(),
+                display_name: format_agent_display_name(
+                    &a.source,
+                    &a.session_id,
+                    prefs.name_for(&a.key, a.repo_path.as_deref()),
+                    a.agent_name.as_deref(),
+                    a.repo_path.as_deref(),
+                    branch.as_deref(),
+                ),
+                pinned: prefs.is_pinned(&a.key),
+                note: prefs.notes.get(&a.key).cloned(),
+                muted: prefs.is_muted(&a.key),
+                state: a.state.clone(),
+                last_ts_ms: a.last_ts_ms,
+                last_text: a.last_text.clone(),
+                repo_path: a.repo_path.clone(),
+                vcs: agent_git_status.get(&a.key).map(|s| s.vcs.to_string()),
+                branch,
+                change_id: agent_git_status
+                    .get(&a.key)
+                    .and_then(|s| s.change_id.clone()),
+                worktree_branch: worktree_branches.get(&a.key).cloned(),
+                ahead: agent_git_status.get(&a.key).and_then(|s| s.ahead),
+                behind: agent_git_status.get(&a.key).and_then(|s| s.behind),
+                stash_count: agent_git_status
+                    .get(&a.key)
+                    .filter(|s| s.vcs == "git")
+                    .map(|s| s.stash_ts_ms.len()),
+                pid: None,
+                cpu_percent: None,
+                rss_bytes: None,
+                files_touched: Vec::new(),
+                alerts,
+                recent_events: a
+                    .recent_events
+                    .iter()
+                    .take(limits.recent_events)
+                    .cloned()
+                    .collect(),
+                commits: agent_commits.remove(&a.key).unwrap_or_default(),
+                prs,
+                workflow_run,
+            }
+        })
+        .collect();
+
+    {
+        let last_agents = state.last_agents.lock().map_err(|_| Error::lock("agent"))?;
+        let missing_pinned: Vec<MonitorAgentView> = last_agents
+            .iter()
+            .filter(|previous| prefs.is_pinned(&previous.key) && !prefs.is_archived(&previous.key))
+            .filter(|previous| !agents.iter().any(|a| a.key == previous.key))
+            .cloned()
+            .collect();
+        for mut previous in missing_pinned {
+            previous.note = prefs.notes.get(&previous.key).cloned();
+            previous.muted = prefs.is_muted(&previous.key);
+            if previous.state != "done"
+                && previous.source != "windsurf"
+                && now - previous.last_ts_ms > DONE_AFTER_MS
+            {
+                previous.state = "done".to_string();
+            }
+            agents.push(previous);
+        }
+    }
+    agents.retain(|agent| agent.pinned || settings.agent_filter.matches(agent, now));
+    state
+        .process_table
+        .lock()
+        .map_err(|_| Error::lock("process table"))?
+        .attach(&mut agents);
+    for agent in agents.iter_mut().filter(|agent| agent.pid.is_none()) {
+        agent.alerts.retain(|alert| alert.kind != "stuck");
+    }
+    hold_live_busy_agents(&mut agents, &silent_busy);
+    agents.sort_by(|a, b| {
+        b.pinned
+            .cmp(&a.pinned)
+            .then_with(|| b.last_ts_ms.cmp(&a.last_ts_ms))
+    });
+    apply_collision_alerts(&mut agents, now);
+    stabilize_alert_timestamps(state, &mut agents, now)?;
+    for agent in &mut agents {
+        let key = agent.key.as_str();
+        agent
+            .alerts
+            .retain(|alert| !prefs.is_alert_dismissed(key, alert.ts_ms));
+    }
+
+    let summary = summarize(&agents);
+
+    let mut lock = state
+        .previous_states
+        .lock()
+        .map_err(|_| Error::lock("state"))?;
+    let mut notifications = agent_notifications(&lock, &agents, &settings, now);
+    let next_states: HashMap<String, String> = agents
+        .iter()
+        .map(|agent| (agent.key.clone(), agent.state.clone()))
+        .collect();
+    *lock = next_states;
+
+    for transition in pr_transitions {
+        let display_name = agents
+            .iter()
+            .find(|a| a.key == transition.key)
+            .map(|a| a.display_name.clone())
+            .unwrap_or_else(|| transition.key.clone());
+        notifications.push(MonitorNotification::new(
+            transition.title,
+            format!("{} - {}", display_name, transition.detail),
+            transition.kind,
+            transition.key,
+        ));
+    }
+
+    drop(lock);
+    let (notifications, disabled): (Vec<_>, Vec<_>) =
+        notifications.into_iter().partition(|notification| {
+            agents
+                .iter()
+                .find(|a| a.key == notification.key)
+                .is_none_or(|a| settings.notifications_enabled(&a.source, a.repo_path.as_deref()))
+        });
+    let (notifications, muted): (Vec<_>, Vec<_>) = notifications
+        .into_iter()
+        .partition(|notification| !prefs.is_muted(&notification.key));
+    let source_status = state.source_status(&settings)?;
+    let quiet = quiet_mode_now();
+    let status = match quiet {
+        _ if read_notifications_snoozed_until() > now => "snoozed",
+        Some(QuietMode::Suppress) => "quiet",
+        Some(QuietMode::Silent) => "silent",
+        None => "delivered",
+    };
+    let (notifications, cooled) = apply_notification_cooldown(
+        state,
+        notifications,
+        now,
+        settings.notification_cooldown_ms,
+        matches!(status, "delivered" | "silent"),
+    )?;
+    let history: Vec<_> = disabled
+        .iter()
+        .map(|n| history_entry(n, "disabled", now))
+        .chain(muted.iter().map(|n| history_entry(n, "muted", now)))
+        .chain(cooled.iter().map(|n| history_entry(n, "cooldown", now)))
+        .chain(notifications.iter().map(|n| history_entry(n, status, now)))
+        .collect();
+    let remote_notifications = apply_quiet_hours(notifications.clone(), quiet);
+    let notifications = group_notifications(notifications, |key| {
+        agents
+            .iter()
+            .find(|a| a.key == key)
+            .and_then(|a| a.repo_path.as_deref())
+            .and_then(|repo| Path::new(repo).file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+    });
+    let notifications = apply_quiet_hours(notifications, quiet);
+    let mut last_agents = state.last_agents.lock().map_err(|_| Error::lock("agent"))?;
+    let payload = if full_snapshot {
+        MonitorTickPayload {
+            snapshot: Some(MonitorSnapshot {
+                summary,
+                agents: agents.clone(),
+                source_status,
+                now_ms: now,
+            }),
+            delta: None,
+            notifications,
+            history,
+            remote_notifications,
+        }
+    } else {
+        MonitorTickPayload {
+            snapshot: None,
+            delta: Some(snapshot_delta(
+                &last_agents,
+                &agents,
+                summary,
+                source_status,
+                now,
+            )),
+            notifications,
+            history,
+            remote_notifications,
+        }
+    };
+    *last_agents = agents;
+    Ok(payload)
+}
+
+fn summarize(agents: &[MonitorAgentView]) -> MonitorSummary {
+    MonitorSummary {
+        total: agents.len(),
+        active: agents
+            .iter()
+            .filter(|a| a.state == "running" || a.state == "thinking")
+            .count(),
+        waiting: agents.iter().filter(|a| a.state == "waiting").count(),
+        done: agents.iter().filter(|a| a.state == "done").count(),
+        error: agents.iter().filter(|a| a.state == "error").count(),
+        limited: agents.iter().filter(|a| a.state == "limited").count(),
+        pr_pending: agents
+            .iter()
+            .flat_map(|a| a.prs.iter().map(|pr| pr.url.as_str()))
+            .collect::<HashSet<_>>()
+            .len(),
+        alerts: agents.iter().map(|a| a.alerts.len()).sum(),
+    }
+}
+
+pub(crate) fn apply_notification_cooldown(
+    state: &Monitor,
+    notifications: Vec<MonitorNotification>,
+    now: i64,
+    cooldown_ms: i64,
+    delivered: bool,
+) -> Result<(Vec<MonitorNotification>, Vec<MonitorNotification>), Error> {
+    let mut history = state
+        .notification_history
+        .lock()
+        .map_err(|_| Error::lock("notification history"))?;
+    let cooldown_ms = cooldown_ms.max(0);
+    history.retain(|_, sent_ms| now - *sent_ms < cooldown_ms);
+    Ok(notifications.into_iter().partition(|notification| {
+        let id = (notification.key.clone(), notification.kind.clone());
+        if history.contains_key(&id) {
+            return false;
+        }
+        // Snoozed or suppressed notifications never reached the user, so they
+        // don't start a cooldown window.
+        if delivered {
+            history.insert(id, now);
+        }
+        true
+    }))
+}
+
+/// Notifications for newly stuck agents and for agents whose state changed
+/// since the previous tick.
+fn agent_notifications(
+    previous_states: &HashMap<String, String>,
+    agents: &[MonitorAgentView],
+    settings: &MonitorSettings,
+    now: i64,
+) -> Vec<MonitorNotification> {
+    let mut notifications = Vec::new();
+    for agent in agents {
+        // Alerts keep their first-seen timestamp, so `now` marks a newly stuck agent.
+        if let Some(alert) = agent
+            .alerts
+            .iter()
+            .find(|alert| alert.kind == "stuck" && alert.ts_ms == now)
+        {
+            notifications.push(MonitorNotification::new(
+                "Agent may be stuck",
+                format!("{} - {}", agent.display_name, alert.message),
+                "stuck",
+                agent.key.clone(),
+            ));
+        }
+    }
+    for agent in agents {
+        if previous_states.get(&agent.key) == Some(&agent.state) || agent.source == "windsurf" {
+            continue;
+        }
+        let was_active = previous_states
+            .get(&agent.key)
+            .map(|previous| previous != "done" && previous != "idle")
+            .unwrap_or(false);
+        if was_active && (agent.state == "done" || agent.state == "idle") {
+            if let Some(alert) = agent.alerts.iter().find(|a| a.kind == "uncommitted") {
+                notifications.push(MonitorNotification::new(
+                    "Uncommitted work",
+                    format!("{} - {}", agent.display_name, alert.message),
+                    "uncommitted",
+                    agent.key.clone(),
+                ));
+            }
+        }
+        let awaiting_permission = is_awaiting_permission(&agent.state, &agent.recent_events);
+        let (kind, title, fallback) = match agent.state.as_str() {
+            "waiting" if !settings.notify_waiting => continue,
+            "error" => ("error", "Agent error", "Error"),
+            "done" => ("done", "Agent done", "Completed"),
+            "limited" => ("limited", "Agent rate limited", "Rate limit reached"),
+            "waiting" if awaiting_permission => {
+                ("approval", "Agent needs approval", "Approval requested")
+            }
+            "waiting" => ("waiting", "Agent waiting for input", "Waiting for input"),
+            _ => continue,
+        };
+        // For waiting agents the newest event carries the question or approval prompt.
+        let prompt = if agent.state == "waiting" {
+            agent.recent_events.first().and_then(|e| e.text.clone())
+        } else {
+            None
+        };
+        notifications.push(MonitorNotification::new(
+            title,
+            format!(
+                "{} - {}",
+                agent.display_name,
+                prompt
+                    .or_else(|| agent.last_text.clone())
+                    .unwrap_or_else(|| fallback.to_string())
+            ),
+            kind,
+            agent.key.clone(),
+        ));
+    }
+    notifications
+}
+
+/// A silent busy agent whose process is still alive has not finished: once
+/// flagged stuck it keeps its busy state, and until then it goes no further
+/// than idle.
+fn hold_live_busy_agents(
+    agents: &mut [MonitorAgentView],
+    silent_busy: &HashMap<String, (String, Option<String>)>,
+) {
+    for agent in agents.iter_mut().filter(|agent| agent.pid.is_some()) {
+        let Some((state, text)) = silent_busy.get(&agent.key) else {
+            continue;
+        };
+        if agent.alerts.iter().any(|alert| alert.kind == "stuck") {
+            agent.state = state.clone();
+            agent.last_text = text.clone();
+        } else if agent.state == "done" {
+            agent.state = "idle".to_string();
+        }
+    }
+}
+
+/// Drops or silences notifications while quiet hours are in effect.
+pub fn apply_quiet_hours(
+    notifications: Vec<MonitorNotification>,
+    mode: Option<QuietMode>,
+) -> Vec<MonitorNotification> {
+    match mode {
+        None => notifications,
+        Some(QuietMode::Suppress) => Vec::new(),
+        Some(QuietMode::Silent) => notifications
+            .into_iter()
+            .map(|notification| MonitorNotification {
+                silent: true,
+                ..notification
+            })
+            .collect(),
+    }
+}
+
+/// Coalesces same-kind agent notifications from one repo into a single
+/// message and orders the result by priority, most urgent first.
+pub(crate) fn group_notifications(
+    notifications: Vec<MonitorNotification>,
+    repo_of: impl Fn(&str) -> Option<String>,
+) -> Vec<MonitorNotification> {
+    let mut buckets: Vec<(Option<String>, Vec<MonitorNotification>)> = Vec::new();
+    let mut index: HashMap<(String, Option<String>), usize> = HashMap::new();
+    for notification in notifications {
+        if grouped_verb(&notification.kind).is_none() {
+            buckets.push((None, vec![notification]));
+            continue;
+        }
+        let repo = repo_of(&notification.key);
+        let slot = *index
+            .entry((notification.kind.clone(), repo.clone()))
+            .or_insert_with(|| {
+                buckets.push((repo, Vec::new()));
+                buckets.len() - 1
+            });
+        buckets[slot].1.push(notification);
+    }
+    let mut grouped: Vec<_> = buckets
+        .into_iter()
+        .filter_map(|(repo, mut group)| {
+            if group.len() < 2 {
+                return group.pop();
+            }
+            let (title, verb) = grouped_verb(&group[0].kind)?;
+            let mut message = format!("{} agents {}", group.len(), verb);
+            if let Some(repo) = repo {
+                message.push_str(&format!(" in {}", repo));
+            }
+            let mut notification =
+                MonitorNotification::new(title, message, group[0].kind.as_str(), &group[0].key);
+            notification.silent = group.iter().all(|n| n.silent);
+            notification.grouped_keys = group.into_iter().map(|n| n.key).collect();
+            Some(notification)
+        })
+        .collect();
+    grouped.sort_by_key(|notification| std::cmp::Reverse(notification.priority));
+    grouped
+}
+
+fn grouped_verb(kind: &str) -> Option<(&'static str, &'static str)> {
+    match kind {
+        "error" => Some(("Agent errors", "hit errors")),
+        "waiting" => Some(("Agents waiting", "are waiting for input")),
+        "stuck" => Some(("Agents may be stuck", "stopped logging while running")),
+        "limited" => Some(("Agents rate limited", "hit rate limits")),
+        "done" => Some(("Agents done", "finished")),
+        _ => None,
+    }
+}
+
+pub(crate) fn snapshot_delta(
+    previous: &[MonitorAgentView],
+    agents: &[MonitorAgentView],
+    summary: MonitorSummary,
+    source_status: Vec<MonitorSourceStatus>,
+    now_ms: i64,
+) -> MonitorSnapshotDelta {
+    let previous_by_key: HashMap<&str, &MonitorAgentView> =
+        previous.iter().map(|a| (a.key.as_str(), a)).collect();
+    let current_keys: HashSet<&str> = agents.iter().map(|a| a.key.as_str()).collect();
+    let mut added = Vec::new();
+    let mut updated = Vec::new();
+    for agent in agents {
+        match previous_by_key.get(agent.key.as_str()) {
+            None => added.push(agent.clone()),
+            Some(prev) if *prev != agent => updated.push(agent.clone()),
+            Some(_) => {}
+        }
+    }
+    let removed = previous
+        .iter()
+        .filter(|a| !current_keys.contains(a.key.as_str()))
+        .map(|a| a.key.clone())
+        .collect();
+    MonitorSnapshotDelta {
+        summary,
+        added,
+        updated,
+        removed,
+        source_status,
+        now_ms,
+    }
+}
+
+pub(crate) type SourceScanner =
+    fn(&Monitor, &ScanLimits, &mut ScanCounts) -> Result<HashMap<String, AgentTemp>, Error>;
+
+fn record_scan_stats(
+    state: &Monitor,
+    name: &'static str,
+    result: &Result<HashMap<String, AgentTemp>, Error>,
+    counts: ScanCounts,
+    elapsed: Duration,
+) {
+    let Ok(mut stats) = state.scan_stats.lock() else {
+        return;
+    };
+    let entry = stats.entry(name).or_default();
+    entry.last_scan_ms = now_ms();
+    entry.duration_ms = elapsed.as_millis() as u64;
+    if counts.rescanned {
+        entry.files = counts.files;
+        entry.parse_errors = counts.parse_errors;
+        entry.warning = counts.warning;
+    }
+    match result {
+        Ok(agents) => {
+            entry.agents = agents.len();
+            entry.error = None;
+        }
+        Err(e) => entry.error = Some(e.to_string()),
+    }
+}
+
+pub(crate) fn source_data_roots(source: &str) -> Vec<PathBuf> {
+    match source {
+        "opencode" => vec![
+            opencode_db_file(),
+            opencode_message_root(),
+            opencode_part_root(),
+        ],
+        "codex" => vec![codex_sessions_root()],
+        "windsurf" => vec![windsurf_cascade_root(), windsurf_workspace_storage_root()],
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) fn render_branch_template(
+    template: &str,
+    source: &str,
+    session_id: &str,
+) -> Option<String> {
+    let template = template.trim();
+    if template.is_empty() {
+        return None;
+    }
+    let short_session: String = session_id
+        .trim_start_matches("ses_")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(8)
+        .collect::<String>()
+        .to_lowercase();
+    Some(
+        template
+            .replace("{source}", source)
+            .replace("{short_session}", &short_session)
+            .replace("{session}", session_id),
+    )
+}
+
+pub(crate) fn apply_pending_binds(
+    state: &Monitor,
+    map: &mut HashMap<String, AgentTemp>,
+    now: i64,
+) -> Result<(), Error> {
+    let mut pending = state
+        .pending_binds
+        .lock()
+        .map_err(|_| Error::lock("pending bind"))?;
+    pending.retain(|bind| now - bind.created_ms < PENDING_BIND_TTL_MS);
+
+    let mut remaining = Vec::new();
+    for bind in pending.drain(..) {
+        let agent = map
+            .values_mut()
+            .filter(|agent| {
+                normalize_source_name(&agent.source) == bind.source
+                    && agent.started_ts_ms >= bind.created_ms
+                    && agent
+                        .repo_path
+                        .as_deref()
+                        .is_some_and(|path| Path::new(path).starts_with(&bind.repo_path))
+            })
+            .min_by_key(|agent| agent.started_ts_ms);
+        match agent {
+            Some(agent) => {
+                agent.repo_path = Some(bind.repo_path.clone());
+                if let Err(e) = write_repo_binding(&agent.key, &bind.repo_path) {
+                    tracing::warn!(agent = %agent.key, error = %e, "failed to persist pending bind");
+                }
+            }
+            None => remaining.push(bind),
+        }
+    }
+    *pending = remaining;
+    Ok(())
+}
+
+pub(crate) fn apply_collision_alerts(agents: &mut [MonitorAgentView], now: i64) {
+    let mut groups: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (idx, agent) in agents.iter().enumerate() {
+        if !matches!(agent.state.as_str(), "running" | "thinking" | "waiting") {
+            continue;
+        }
+        let Some(repo_path) = agent.repo_path.clone() else {
+            continue;
+        };
+        let branch = agent.branch.clone().unwrap_or_default();
+        groups.entry((repo_path, branch)).or_default().push(idx);
+    }
+
+    for ((repo_path, branch), members) in groups {
+        if members.len() < 2 {
+            continue;
+        }
+        let location = if branch.is_empty() {
+            repo_path.clone()
+        } else {
+            format!("{} ({})", repo_path, branch)
+        };
+        for &idx in &members {
+            let others: Vec<String> = members
+                .iter()
+                .filter(|&&other| other != idx)
+                .map(|&other| agents[other].display_name.clone())
+                .collect();
+            agents[idx].alerts.push(MonitorAlert {
+                kind: "collision".to_string(),
+                message: format!("Sharing {} with {}", location, others.join(", ")),
+                ts_ms: now,
+            });
+        }
+    }
+}
+
+fn repo_entry<'a>(
+    repos: &'a mut HashMap<String, MonitorRepoView>,
+    path: &str,
+) -> &'a mut MonitorRepoView {
+    repos
+        .entry(path.to_string())
+        .or_insert_with(|| MonitorRepoView {
+            path: path.to_string(),
+            last_activity_ms: None,
+            session_count: 0,
+            bound: false,
+            branch: None,
+        })
+}
+
+pub(crate) fn stabilize_alert_timestamps(
+    state: &Monitor,
+    agents: &mut [MonitorAgentView],
+    now: i64,
+) -> Result<(), Error> {
+    let mut first_seen = state
+        .alert_first_seen
+        .lock()
+        .map_err(|_| Error::lock("alert"))?;
+    let mut next: HashMap<(String, String, String), i64> = HashMap::new();
+    for agent in agents.iter_mut() {
+        for alert in agent.alerts.iter_mut().filter(|alert| alert.ts_ms == now) {
+            let id = (agent.key.clone(), alert.kind.clone(), alert.message.clone());
+            alert.ts_ms = first_seen.get(&id).copied().unwrap_or(now);
+            next.insert(id, alert.ts_ms);
+        }
+    }
+    *first_seen = next;
+    Ok(())
+}
+
+pub(crate) fn is_awaiting_permission(state: &str, recent_events: &[MonitorEventView]) -> bool {
+    state == "waiting"
+        && recent_events
+            .first()
+            .map(|e| e.event_type == "permission")
+            .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::NotificationPriority;
+
+    #[test]
+    fn live_silent_busy_agent_stays_running_and_only_reports_stuck() {
+        let now = 2_000_000;
+        let settings = MonitorSettings::default();
+        // The agent was running last tick; the silence downgrade made it done.
+        let mut agents = vec![MonitorAgentView {
+            key: "codex:s1".to_string(),
+            source: "codex".to_string(),
+            display_name: "fix-billing".to_string(),
+            state: "done".to_string(),
+            last_ts_ms: now - settings.stuck_after_ms - 1,
+            pid: Some(4242),
+            alerts: vec![MonitorAlert {
+                kind: "stuck".to_string(),
+                message: "No new events for 10+ min while running".to_string(),
+                ts_ms: now,
+            }],
+            ..Default::default()
+        }];
+        let silent_busy = HashMap::from([(
+            "codex:s1".to_string(),
+            ("running".to_string(), Some("cargo test".to_string())),
+        )]);
+        hold_live_busy_agents(&mut agents, &silent_busy);
+        assert_eq!(agents[0].state, "running");
+
+        let previous = HashMap::from([("codex:s1".to_string(), "running".to_string())]);
+        let kinds: Vec<_> = agent_notifications(&previous, &agents, &settings, now)
+            .into_iter()
+            .map(|n| n.kind)
+            .collect();
+        assert_eq!(kinds, vec!["stuck"]);
+
+        // Before the stuck threshold a live process only goes idle, never done.
+        agents[0].state = "done".to_string();
+        agents[0].alerts.clear();
+        hold_live_busy_agents(&mut agents, &silent_busy);
+        assert_eq!(agents[0].state, "idle");
+        assert!(agent_notifications(&previous, &agents, &settings, now).is_empty());
+    }
+
+    #[test]
+    fn only_delivered_notifications_start_a_cooldown() {
+        let monitor = Monitor::default();
+        let done = || {
+            vec![MonitorNotification::new(
+                "Agent done",
+                "a - Completed",
+                "done",
+                "codex:a",
+            )]
+        };
+        let (kept, cooled) =
+            apply_notification_cooldown(&monitor, done(), 1_000, 60_000, false).unwrap();
+        assert_eq!((kept.len(), cooled.len()), (1, 0));
+        let (kept, _) = apply_notification_cooldown(&monitor, done(), 2_000, 60_000, true).unwrap();
+        assert_eq!(kept.len(), 1);
+        let (kept, cooled) =
+            apply_notification_cooldown(&monitor, done(), 3_000, 60_000, true).unwrap();
+        assert_eq!((kept.len(), cooled.len()), (0, 1));
+    }
+
+    #[test]
+    fn windsurf_agents_never_notify() {
+        let now = 2_000_000;
+        let settings = MonitorSettings::default();
+        let mut agents = vec![MonitorAgentView {
+            key: "windsurf:w1".to_string(),
+            source: "windsurf".to_string(),
+            display_name: "cascade".to_string(),
+            state: "idle".to_string(),
+            last_ts_ms: now - DONE_AFTER_MS - 1,
+            ..Default::default()
+        }];
+        for previous in [
+            HashMap::new(),
+            HashMap::from([("windsurf:w1".to_string(), "running".to_string())]),
+        ] {
+            assert!(agent_notifications(&previous, &agents, &settings, now).is_empty());
+        }
+        agents[0].state = "done".to_string();
+        let previous = HashMap::from([("windsurf:w1".to_string(), "idle".to_string())]);
+        assert!(agent_notifications(&previous, &agents, &settings, now).is_empty());
+    }
+
+    #[test]
+    fn groups_same_kind_per_repo_and_orders_by_priority() {
+        let notifications = vec![
+            MonitorNotification::new("Agent done", "a - Completed", "done", "codex:a"),
+            MonitorNotification::new("Agent done", "b - Completed", "done", "codex:b"),
+            MonitorNotification::new("Uncommitted work", "b - 2 files", "uncommitted", "codex:b"),
+            MonitorNotification::new("Agent done", "c - Completed", "done", "claude:c"),
+            MonitorNotification::new("Agent done", "d - Completed", "done", "claude:d"),
+            MonitorNotification::new("Agent error", "e - boom", "error", "claude:e"),
+            MonitorNotification::new("Agent needs approval", "f - rm", "approval", "codex:f"),
+            MonitorNotification::new("Agent needs approval", "g - push", "approval", "codex:g"),
+        ];
+        let repos = HashMap::from([
+            ("codex:a", "api"),
+            ("codex:b", "api"),
+            ("claude:c", "api"),
+            ("claude:d", "web"),
+            ("claude:e", "web"),
+            ("codex:f", "api"),
+            ("codex:g", "api"),
+        ]);
+        let grouped = group_notifications(notifications, |key| {
+            repos.get(key).map(|repo| repo.to_string())
+        });
+        let summary: Vec<_> = grouped
+            .iter()
+            .map(|n| (n.kind.as_str(), n.message.as_str(), n.grouped_keys.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("error", "e - boom", 0),
+                ("approval", "f - rm", 0),
+                ("approval", "g - push", 0),
+                ("done", "3 agents finished in api", 3),
+                ("done", "d - Completed", 0),
+                ("uncommitted", "b - 2 files", 0),
+            ]
+        );
+        assert_eq!(grouped[3].key, "codex:a");
+        assert_eq!(grouped[3].priority, NotificationPriority::Normal);
+    }
+}
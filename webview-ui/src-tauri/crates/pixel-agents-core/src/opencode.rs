@@ -0,0 +1,493 @@
+use crate::error::Error;
+use crate::model::MonitorEventView;
+use crate::monitor::Monitor;
+use crate::opencode_record::{
+    OpencodeActivity, OpencodeMessage, OpencodePart, OpencodePartRecord, OpencodeProject,
+    OpencodeSession,
+};
+use crate::paths::{
+    opencode_data_root, opencode_db_file, opencode_message_root, opencode_part_root,
+    opencode_project_root, opencode_session_root,
+};
+use crate::scan::{upsert_agent, AgentTemp, JsonFileCache, ScanCounts};
+use crate::settings::ScanLimits;
+use crate::util::{collect_files, modified_ms, normalize_epoch_ms, truncate_option_text};
+use crate::watch::WatchedSource;
+use rusqlite::{Connection, OpenFlags};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub(crate) const OPENCODE_DB_BUSY_TIMEOUT_MS: u64 = 250;
+
+#[derive(Default)]
+pub(crate) struct OpencodeScanState {
+    pub(crate) source: WatchedSource<HashMap<String, AgentTemp>>,
+    pub(crate) limits: Option<ScanLimits>,
+    pub(crate) db: Option<OpencodeDb>,
+    pub(crate) json: JsonFileCache,
+    pub(crate) sessions: Option<OpencodeSessionMaps>,
+}
+
+#[derive(Default)]
+pub(crate) struct OpencodeSessionMaps {
+    pub(crate) repo: HashMap<String, String>,
+    pub(crate) name: HashMap<String, String>,
+}
+
+pub(crate) struct OpencodeDb {
+    pub(crate) path: PathBuf,
+    pub(crate) conn: Connection,
+}
+
+pub(crate) fn scan_opencode_source(
+    state: &Monitor,
+    limits: &ScanLimits,
+    counts: &mut ScanCounts,
+) -> Result<HashMap<String, AgentTemp>, Error> {
+    let mut cache = state
+        .opencode_scan
+        .lock()
+        .map_err(|_| Error::lock("opencode scan"))?;
+    let OpencodeScanState {
+        source,
+        limits: last_limits,
+        db,
+        json,
+        sessions,
+    } = &mut *cache;
+    if *last_limits != Some(*limits) {
+        source.reset();
+        *sessions = None;
+        *last_limits = Some(*limits);
+    }
+    Ok(source.scan(&opencode_data_root(), |changes| {
+        let session_roots = [opencode_session_root(), opencode_project_root()];
+        if changes.full_rescan
+            || changes
+                .paths
+                .iter()
+                .any(|path| session_roots.iter().any(|root| path.starts_with(root)))
+        {
+            *sessions = None;
+        }
+        let mut agents = HashMap::new();
+        scan_opencode(&mut agents, db, json, sessions, limits, counts);
+        json.sweep();
+        counts.rescanned = true;
+        counts.parse_errors += json.parse_failures();
+        agents
+    }))
+}
+
+pub(crate) fn scan_opencode_db(
+    map: &mut HashMap<String, AgentTemp>,
+    db: &mut Option<OpencodeDb>,
+    limits: &ScanLimits,
+    counts: &mut ScanCounts,
+) -> bool {
+    let db_path = opencode_db_file();
+    if !db_path.exists() {
+        *db = None;
+        return false;
+    }
+    if db.as_ref().map(|d| d.path != db_path).unwrap_or(true) {
+        *db = match open_opencode_db(&db_path) {
+            Ok(opened) => Some(opened),
+            Err(e) => {
+                tracing::warn!(path = %db_path.display(), error = %e, "opencode db open failed");
+                counts.warning = Some(format!("opencode db open failed: {}", e));
+                None
+            }
+        };
+    }
+    counts.files += 1;
+    let Some(conn) = db.as_ref().map(|d| &d.conn) else {
+        counts.parse_errors += 1;
+        return false;
+    };
+    if let Err(e) = read_opencode_db(map, conn, limits) {
+        tracing::warn!(error = %e, "opencode db read failed");
+        counts.warning = Some(format!("opencode db read failed: {}", e));
+        counts.parse_errors += 1;
+        *db = None;
+        return false;
+    }
+    true
+}
+
+pub(crate) fn open_opencode_db(path: &Path) -> rusqlite::Result<OpencodeDb> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    conn.busy_timeout(Duration::from_millis(OPENCODE_DB_BUSY_TIMEOUT_MS))?;
+    Ok(OpencodeDb {
+        path: path.to_path_buf(),
+        conn,
+    })
+}
+
+pub(crate) fn read_opencode_db(
+    map: &mut HashMap<String, AgentTemp>,
+    conn: &Connection,
+    limits: &ScanLimits,
+) -> rusqlite::Result<()> {
+    let mut session_repo: HashMap<String, String> = HashMap::new();
+    let mut session_name: HashMap<String, String> = HashMap::new();
+
+    {
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, directory, title, time_created, time_updated
+             FROM session
+             WHERE time_archived IS NULL OR time_archived = 0
+             ORDER BY time_updated DESC
+             LIMIT ?1",
+        )?;
+
+        let rows = stmt.query_map([limits.opencode_db_sessions as i64], |row| {
+            let id: String = row.get(0)?;
+            let directory: String = row.get(1)?;
+            let title: Option<String> = row.get(2)?;
+            let time_created: i64 = row.get(3)?;
+            let time_updated: i64 = row.get(4)?;
+            Ok((id, directory, title, time_created, time_updated))
+        });
+
+        if let Ok(rows) = rows {
+            for row in rows.flatten() {
+                let (session_id, directory, title, time_created, time_updated) = row;
+                let started = normalize_epoch_ms(time_created);
+                let ts = normalize_epoch_ms(time_updated);
+                session_repo.insert(session_id.clone(), directory.clone());
+                if let Some(name) = title.clone() {
+                    session_name.insert(session_id.clone(), name);
+                }
+                upsert_agent(
+                    map,
+                    AgentTemp {
+                        key: format!("opencode:{}", session_id),
+                        source: "opencode".to_string(),
+                        session_id,
+                        agent_name: title.clone(),
+                        state: "running".to_string(),
+                        started_ts_ms: started,
+                        last_ts_ms: ts,
+                        last_text: Some("Session activity".to_string()),
+                        repo_path: Some(directory),
+                        recent_events: vec![MonitorEventView {
+                            ts_ms: ts,
+                            event_type: "status".to_string(),
+                            state_hint: "running".to_string(),
+                            text: Some("Session activity".to_string()),
+                            files_touched: Vec::new(),
+                        }],
+                    },
+                );
+            }
+        }
+    }
+
+    {
+        let mut stmt = conn.prepare_cached(
+            "SELECT session_id, time_updated, data
+             FROM part
+             ORDER BY time_updated DESC
+             LIMIT ?1",
+        )?;
+
+        let rows = stmt.query_map([limits.opencode_db_parts as i64], |row| {
+            let session_id: String = row.get(0)?;
+            let time_updated: i64 = row.get(1)?;
+            let data: String = row.get(2)?;
+            Ok((session_id, time_updated, data))
+        });
+
+        if let Ok(rows) = rows {
+            for row in rows.flatten() {
+                let (session_id, time_updated, data) = row;
+                let Ok(part) = serde_json::from_str::<OpencodePart>(&data) else {
+                    continue;
+                };
+                let Some(event) = part_event(&part, normalize_epoch_ms(time_updated)) else {
+                    continue;
+                };
+                upsert_agent(
+                    map,
+                    AgentTemp {
+                        key: format!("opencode:{}", session_id),
+                        source: "opencode".to_string(),
+                        session_id: session_id.clone(),
+                        agent_name: session_name.get(&session_id).cloned(),
+                        state: event.state_hint.clone(),
+                        started_ts_ms: event.ts_ms,
+                        last_ts_ms: event.ts_ms,
+                        last_text: event.text.clone(),
+                        repo_path: session_repo.get(&session_id).cloned(),
+                        recent_events: vec![event],
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn scan_opencode(
+    map: &mut HashMap<String, AgentTemp>,
+    db: &mut Option<OpencodeDb>,
+    json_cache: &mut JsonFileCache,
+    sessions: &mut Option<OpencodeSessionMaps>,
+    limits: &ScanLimits,
+    counts: &mut ScanCounts,
+) {
+    if scan_opencode_db(map, db, limits, counts) {
+        return;
+    }
+
+    let root = opencode_message_root();
+    if !root.exists() {
+        return;
+    }
+
+    let OpencodeSessionMaps {
+        repo: session_repo,
+        name: session_name,
+    } = sessions.get_or_insert_with(|| load_opencode_session_maps(json_cache, limits));
+
+    let files = collect_files(&root, "json", limits.opencode_files);
+    counts.files += files.len();
+    for file in files {
+        let Some(value) = json_cache.read(&file) else {
+            continue;
+        };
+        let Ok(message) = OpencodeMessage::deserialize(value) else {
+            continue;
+        };
+        let session_id = message
+            .session_id
+            .clone()
+            .or_else(|| {
+                file.parent()
+                    .and_then(|p| p.file_name())
+                    .map(|s| s.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        let key = format!("opencode:{}", session_id);
+        let ts = message.time.created.unwrap_or_else(|| modified_ms(&file));
+        let repo_path = message
+            .repo_path()
+            .map(|s| s.to_string())
+            .or_else(|| session_repo.get(&session_id).cloned());
+        let event = message_event(&message, ts);
+
+        upsert_agent(
+            map,
+            AgentTemp {
+                key: key.clone(),
+                source: "opencode".to_string(),
+                session_id: session_id.clone(),
+                agent_name: session_name.get(&session_id).cloned(),
+                state: event.state_hint.clone(),
+                started_ts_ms: ts,
+                last_ts_ms: ts,
+                last_text: event.text.clone(),
+                repo_path,
+                recent_events: vec![event],
+            },
+        );
+    }
+
+    let part_root = opencode_part_root();
+    if !part_root.exists() {
+        return;
+    }
+
+    let part_files = collect_files(&part_root, "json", limits.opencode_part_files);
+    counts.files += part_files.len();
+    for file in part_files {
+        let Some(value) = json_cache.read(&file) else {
+            continue;
+        };
+
+        let Ok(OpencodePartRecord {
+            session_id: Some(session_id),
+            part,
+        }) = OpencodePartRecord::deserialize(value)
+        else {
+            continue;
+        };
+
+        let key = format!("opencode:{}", session_id);
+        let Some(event) = part_event(&part, normalize_epoch_ms(modified_ms(&file))) else {
+            continue;
+        };
+
+        upsert_agent(
+            map,
+            AgentTemp {
+                key: key.clone(),
+                source: "opencode".to_string(),
+                session_id: session_id.clone(),
+                agent_name: session_name.get(&session_id).cloned(),
+                state: event.state_hint.clone(),
+                started_ts_ms: event.ts_ms,
+                last_ts_ms: event.ts_ms,
+                last_text: event.text.clone(),
+                repo_path: session_repo.get(&session_id).cloned(),
+                recent_events: vec![event],
+            },
+        );
+    }
+}
+
+pub(crate) fn read_opencode_session_events(
+    session_id: &str,
+) -> Result<Vec<MonitorEventView>, Error> {
+    let db_path = opencode_db_file();
+    if db_path.exists() {
+        let db = open_opencode_db(&db_path)?;
+        let mut stmt = db
+            .conn
+            .prepare("SELECT time_updated, data FROM part WHERE session_id = ?1")?;
+        let rows = stmt.query_map([session_id], |row| {
+            let time_updated: i64 = row.get(0)?;
+            let data: String = row.get(1)?;
+            Ok((time_updated, data))
+        })?;
+        return Ok(rows
+            .flatten()
+            .filter_map(|(time_updated, data)| {
+                let part = serde_json::from_str::<OpencodePart>(&data).ok()?;
+                part_event(&part, normalize_epoch_ms(time_updated))
+            })
+            .collect());
+    }
+    let message_dir = opencode_message_root().join(session_id);
+    if !message_dir.exists() {
+        return Err(Error::Invalid(format!(
+            "opencode session not found: {}",
+            session_id
+        )));
+    }
+    let mut events = Vec::new();
+    for file in collect_files(&message_dir, "json", usize::MAX) {
+        let Some(message) = fs::read_to_string(&file)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<OpencodeMessage>(&raw).ok())
+        else {
+            continue;
+        };
+        let ts = message.time.created.unwrap_or_else(|| modified_ms(&file));
+        events.push(message_event(&message, ts));
+        let Some(id) = message.id.as_deref() else {
+            continue;
+        };
+        for part_file in collect_files(&opencode_part_root().join(id), "json", usize::MAX) {
+            let Some(part) = fs::read_to_string(&part_file)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<OpencodePart>(&raw).ok())
+            else {
+                continue;
+            };
+            events.extend(part_event(
+                &part,
+                normalize_epoch_ms(modified_ms(&part_file)),
+            ));
+        }
+    }
+    Ok(events)
+}
+
+pub(crate) fn message_event(message: &OpencodeMessage, ts: i64) -> MonitorEventView {
+    let limit_text = message.limit_text();
+    let state = if limit_text.is_some() {
+        "limited"
+    } else if message.time.completed.is_some() {
+        "done"
+    } else {
+        "running"
+    };
+    let text = truncate_option_text(
+        limit_text
+            .map(|s| s.to_string())
+            .or_else(|| message.summary.clone())
+            .or_else(|| message.finish.clone()),
+    );
+    MonitorEventView {
+        ts_ms: ts,
+        event_type: "message".to_string(),
+        state_hint: state.to_string(),
+        text,
+        files_touched: Vec::new(),
+    }
+}
+
+fn part_event(part: &OpencodePart, fallback_ts: i64) -> Option<MonitorEventView> {
+    let OpencodeActivity {
+        state,
+        event_type,
+        text,
+        ts_ms,
+    } = part.activity(fallback_ts)?;
+    Some(MonitorEventView {
+        ts_ms,
+        event_type: event_type.to_string(),
+        state_hint: state.to_string(),
+        text: truncate_option_text(text),
+        files_touched: Vec::new(),
+    })
+}
+
+pub(crate) fn load_opencode_session_maps(
+    json_cache: &mut JsonFileCache,
+    limits: &ScanLimits,
+) -> OpencodeSessionMaps {
+    let mut out = OpencodeSessionMaps::default();
+    let session_root = opencode_session_root();
+    if !session_root.exists() {
+        return out;
+    }
+
+    let project_root = opencode_project_root();
+    let session_files = collect_files(&session_root, "json", limits.opencode_files);
+    for file in session_files {
+        let Some(Ok(session)) = json_cache.read(&file).map(OpencodeSession::deserialize) else {
+            continue;
+        };
+
+        let Some(session_id) = session
+            .id
+            .or_else(|| {
+                file.parent()
+                    .and_then(|p| p.file_name())
+                    .map(|s| s.to_string_lossy().into_owned())
+            })
+            .or_else(|| file.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        else {
+            continue;
+        };
+        if let Some(title) = session.title {
+            out.name.insert(session_id.clone(), title);
+        }
+        let Some(project_id) = session.project_id else {
+            continue;
+        };
+
+        let project_file = project_root.join(format!("{}.json", project_id));
+        let Some(Ok(project)) = json_cache
+            .read(&project_file)
+            .map(OpencodeProject::deserialize)
+        else {
+            continue;
+        };
+        if let Some(repo) = project.worktree {
+            out.repo.insert(session_id, repo);
+        }
+    }
+
+    out
+}
@@ -0,0 +1,425 @@
+use crate::scan::is_rate_limit_text;
+use crate::util::{de_epoch_ms, de_lenient_string};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpencodePartRecord {
+    #[serde(default, rename = "sessionID", alias = "sessionId")]
+    pub(crate) session_id: Option<String>,
+    #[serde(flatten)]
+    pub(crate) part: OpencodePart,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub(crate) enum OpencodePart {
+    Tool {
+        #[serde(default)]
+        tool: Option<String>,
+        #[serde(default)]
+        state: OpencodeToolState,
+    },
+    Text {
+        #[serde(default)]
+        text: Option<String>,
+        #[serde(default)]
+        time: OpencodeTime,
+    },
+    Reasoning {
+        #[serde(default)]
+        text: Option<String>,
+        #[serde(default)]
+        time: OpencodeTime,
+    },
+    Permission(OpencodePrompt),
+    Ask(OpencodePrompt),
+    StepStart {},
+    StepFinish {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OpencodeToolState {
+    #[serde(default)]
+    pub(crate) status: Option<String>,
+    #[serde(default)]
+    pub(crate) time: OpencodeTime,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub(crate) error: Option<String>,
+    #[serde(default)]
+    pub(crate) title: Option<String>,
+    #[serde(default)]
+    pub(crate) input: Option<Value>,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub(crate) output: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OpencodeTime {
+    #[serde(default, deserialize_with = "de_epoch_ms")]
+    pub(crate) start: Option<i64>,
+    #[serde(default, deserialize_with = "de_epoch_ms")]
+    pub(crate) end: Option<i64>,
+    #[serde(default, deserialize_with = "de_epoch_ms")]
+    pub(crate) created: Option<i64>,
+    #[serde(default, deserialize_with = "de_epoch_ms")]
+    pub(crate) completed: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OpencodePrompt {
+    #[serde(default)]
+    pub(crate) title: Option<String>,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+    #[serde(default)]
+    pub(crate) question: Option<String>,
+    #[serde(default)]
+    pub(crate) time: OpencodeTime,
+    #[serde(default)]
+    pub(crate) metadata: OpencodePromptMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OpencodePromptMetadata {
+    #[serde(default)]
+    pub(crate) question: Option<String>,
+    #[serde(default)]
+    pub(crate) command: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpencodeMessage {
+    #[serde(default)]
+    pub(crate) id: Option<String>,
+    #[serde(default, rename = "sessionID", alias = "sessionId")]
+    pub(crate) session_id: Option<String>,
+    #[serde(default)]
+    pub(crate) role: Option<String>,
+    #[serde(default)]
+    pub(crate) time: OpencodeTime,
+    #[serde(default)]
+    pub(crate) error: Option<OpencodeMessageError>,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub(crate) summary: Option<String>,
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub(crate) finish: Option<String>,
+    #[serde(default)]
+    pub(crate) path: Option<OpencodeMessagePath>,
+    #[serde(default, rename = "modelID", alias = "modelId")]
+    pub(crate) model_id: Option<String>,
+    #[serde(default)]
+    pub(crate) cost: Option<f64>,
+    #[serde(default)]
+    pub(crate) tokens: Option<OpencodeTokens>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OpencodeTokens {
+    #[serde(default)]
+    pub(crate) input: f64,
+    #[serde(default)]
+    pub(crate) output: f64,
+    #[serde(default)]
+    pub(crate) reasoning: f64,
+    #[serde(default)]
+    pub(crate) cache: OpencodeCacheTokens,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct OpencodeCacheTokens {
+    #[serde(default)]
+    pub(crate) read: f64,
+    #[serde(default)]
+    pub(crate) write: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpencodeMessageError {
+    #[serde(default)]
+    pub(crate) data: Option<OpencodeMessageErrorData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpencodeMessageErrorData {
+    #[serde(default, deserialize_with = "de_lenient_string")]
+    pub(crate) message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpencodeMessagePath {
+    #[serde(default)]
+    pub(crate) root: Option<String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpencodeSession {
+    #[serde(default)]
+    pub(crate) id: Option<String>,
+    #[serde(default, rename = "projectID", alias = "projectId")]
+    pub(crate) project_id: Option<String>,
+    #[serde(default)]
+    pub(crate) title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpencodeProject {
+    #[serde(default)]
+    pub(crate) worktree: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OpencodeActivity {
+    pub(crate) state: &'static str,
+    pub(crate) event_type: &'static str,
+    pub(crate) text: Option<String>,
+    pub(crate) ts_ms: i64,
+}
+
+impl OpencodePart {
+    pub(crate) fn activity(&self, fallback_ts: i64) -> Option<OpencodeActivity> {
+        Some(match self {
+            Self::Tool { tool, state } => {
+                let status = state.status.as_deref().unwrap_or("running").to_lowercase();
+                let limited =
+                    status == "error" && state.error.as_deref().is_some_and(is_rate_limit_text);
+                let awaiting =
+                    matches!(status.as_str(), "permission" | "ask" | "awaiting_approval");
+                let (hint, event_type, text) = if awaiting {
+                    (
+                        "waiting",
+                        "permission",
+                        Some(permission_text(state.title.as_deref())),
+                    )
+                } else if limited {
+                    ("limited", "limit", state.error.clone())
+                } else {
+                    let tool = tool.as_deref().unwrap_or("tool");
+                    let hint = if status == "error" {
+                        "error"
+                    } else if status == "completed" || state.time.end.is_some() {
+                        "done"
+                    } else {
+                        "running"
+                    };
+                    let event_type = if status == "error" { "error" } else { "tool" };
+                    (hint, event_type, Some(format!("{}: {}", tool, status)))
+                };
+                OpencodeActivity {
+                    state: hint,
+                    event_type,
+                    text,
+                    ts_ms: state.time.end.or(state.time.start).unwrap_or(fallback_ts),
+                }
+            }
+            Self::Reasoning { text, time } => OpencodeActivity {
+                state: "thinking",
+                event_type: "status",
+                text: Some(text.clone().unwrap_or_else(|| "Thinking".to_string())),
+                ts_ms: time.end.or(time.start).unwrap_or(fallback_ts),
+            },
+            Self::Permission(prompt) | Self::Ask(prompt) => OpencodeActivity {
+                state: "waiting",
+                event_type: "permission",
+                text: Some(prompt.text()),
+                ts_ms: prompt.time.created.unwrap_or(fallback_ts),
+            },
+            Self::StepStart {} => OpencodeActivity {
+                state: "running",
+                event_type: "status",
+                text: Some("Step started".to_string()),
+                ts_ms: fallback_ts,
+            },
+            Self::StepFinish { reason } => OpencodeActivity {
+                state: "done",
+                event_type: "status",
+                text: Some(format!(
+                    "Step finished: {}",
+                    reason.as_deref().unwrap_or("stop")
+                )),
+                ts_ms: fallback_ts,
+            },
+            Self::Text { .. } | Self::Other => return None,
+        })
+    }
+}
+
+impl OpencodePrompt {
+    fn text(&self) -> String {
+        permission_text(
+            self.title
+                .as_deref()
+                .or(self.message.as_deref())
+                .or(self.question.as_deref())
+                .or(self.metadata.question.as_deref())
+                .or(self.metadata.command.as_deref()),
+        )
+    }
+}
+
+impl OpencodeMessage {
+    pub(crate) fn limit_text(&self) -> Option<&str> {
+        self.error
+            .as_ref()
+            .and_then(|error| error.data.as_ref())
+            .and_then(|data| data.message.as_deref())
+            .filter(|message| is_rate_limit_text(message))
+    }
+
+    pub(crate) fn repo_path(&self) -> Option<&str> {
+        let path = self.path.as_ref()?;
+        path.root.as_deref().or(path.cwd.as_deref())
+    }
+}
+
+fn permission_text(text: Option<&str>) -> String {
+    text.map(|text| format!("Permission: {}", text.trim()))
+        .unwrap_or_else(|| "Permission requested".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(raw: &str) -> Option<OpencodeActivity> {
+        serde_json::from_str::<OpencodePartRecord>(raw)
+            .unwrap()
+            .part
+            .activity(7)
+    }
+
+    #[test]
+    fn reads_session_id_spellings() {
+        for raw in [
+            r#"{"sessionID":"ses_1","type":"step-start"}"#,
+            r#"{"sessionId":"ses_1","type":"step-start"}"#,
+        ] {
+            let record: OpencodePartRecord = serde_json::from_str(raw).unwrap();
+            assert_eq!(record.session_id.as_deref(), Some("ses_1"));
+        }
+    }
+
+    #[test]
+    fn tool_part_tracks_status() {
+        let running = activity(
+            r#"{"type":"tool","tool":"bash","state":{"status":"running","time":{"start":1735689600}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            (running.state, running.event_type, running.ts_ms),
+            ("running", "tool", 1_735_689_600_000)
+        );
+        assert_eq!(running.text.as_deref(), Some("bash: running"));
+
+        let done = activity(
+            r#"{"type":"tool","tool":"bash","state":{"status":"completed","time":{"start":1,"end":1735689601000}}}"#,
+        )
+        .unwrap();
+        assert_eq!((done.state, done.ts_ms), ("done", 1_735_689_601_000));
+
+        let failed =
+            activity(r#"{"type":"tool","tool":"edit","state":{"status":"error","error":"nope"}}"#)
+                .unwrap();
+        assert_eq!((failed.state, failed.event_type), ("error", "error"));
+    }
+
+    #[test]
+    fn tool_part_detects_rate_limits_and_approvals() {
+        let limited =
+            activity(r#"{"type":"tool","state":{"status":"error","error":"Rate limit exceeded"}}"#)
+                .unwrap();
+        assert_eq!((limited.state, limited.event_type), ("limited", "limit"));
+        assert_eq!(limited.text.as_deref(), Some("Rate limit exceeded"));
+
+        let awaiting = activity(
+            r#"{"type":"tool","state":{"status":"awaiting_approval","title":"rm -rf build"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            (awaiting.state, awaiting.event_type),
+            ("waiting", "permission")
+        );
+        assert_eq!(awaiting.text.as_deref(), Some("Permission: rm -rf build"));
+    }
+
+    #[test]
+    fn reasoning_part_thinks() {
+        let thinking =
+            activity(r#"{"type":"reasoning","text":"Planning","time":{"start":5,"end":6}}"#)
+                .unwrap();
+        assert_eq!(thinking.state, "thinking");
+        assert_eq!(thinking.text.as_deref(), Some("Planning"));
+        assert_eq!(thinking.ts_ms, 6000);
+    }
+
+    #[test]
+    fn permission_and_ask_parts_wait() {
+        let permission = activity(
+            r#"{"type":"permission","metadata":{"command":"git push"},"time":{"created":9}}"#,
+        )
+        .unwrap();
+        assert_eq!(permission.state, "waiting");
+        assert_eq!(permission.text.as_deref(), Some("Permission: git push"));
+        assert_eq!(permission.ts_ms, 9000);
+
+        let ask = activity(r#"{"type":"ask","question":"Continue?"}"#).unwrap();
+        assert_eq!(ask.text.as_deref(), Some("Permission: Continue?"));
+        assert_eq!(ask.ts_ms, 7);
+    }
+
+    #[test]
+    fn step_parts() {
+        let start = activity(r#"{"type":"step-start"}"#).unwrap();
+        assert_eq!(
+            (start.state, start.text.as_deref()),
+            ("running", Some("Step started"))
+        );
+        let finish = activity(r#"{"type":"step-finish","reason":"tool-calls"}"#).unwrap();
+        assert_eq!(
+            (finish.state, finish.text.as_deref()),
+            ("done", Some("Step finished: tool-calls"))
+        );
+    }
+
+    #[test]
+    fn unknown_parts_are_ignored() {
+        assert_eq!(activity(r#"{"type":"text","text":"hello"}"#), None);
+        assert_eq!(
+            activity(r#"{"type":"patch","hash":"abc","files":[]}"#),
+            None
+        );
+        assert_eq!(activity(r#"{"type":"snapshot","snapshot":"abc"}"#), None);
+    }
+
+    #[test]
+    fn message_reports_limits_and_paths() {
+        let message: OpencodeMessage = serde_json::from_str(
+            r#"{"sessionID":"ses_1","time":{"created":1735689600,"completed":1735689601},"error":{"data":{"message":"usage limit reached"}},"summary":{"diffs":[]},"path":{"cwd":"/repo"}}"#,
+        )
+        .unwrap();
+        assert_eq!(message.session_id.as_deref(), Some("ses_1"));
+        assert_eq!(message.time.created, Some(1_735_689_600_000));
+        assert!(message.time.completed.is_some());
+        assert_eq!(message.limit_text(), Some("usage limit reached"));
+        assert_eq!(message.summary, None);
+        assert_eq!(message.repo_path(), Some("/repo"));
+    }
+
+    #[test]
+    fn session_and_project_files() {
+        let session: OpencodeSession =
+            serde_json::from_str(r#"{"id":"ses_1","projectID":"p1","title":"Refactor"}"#).unwrap();
+        assert_eq!(session.project_id.as_deref(), Some("p1"));
+        assert_eq!(session.title.as_deref(), Some("Refactor"));
+        let project: OpencodeProject =
+            serde_json::from_str(r#"{"worktree":"/repo","vcs":"git"}"#).unwrap();
+        assert_eq!(project.worktree.as_deref(), Some("/repo"));
+    }
+}
@@ -0,0 +1,184 @@
+use dirs::home_dir;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const DATA_DIR_ENV: &str = "PIXEL_AGENTS_DIR";
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Resolved once per process: `PIXEL_AGENTS_DIR`, then the configured
+/// directory in [`data_dir_config_file`], then `~/.pixel-agents`.
+pub fn pixel_agents_dir() -> PathBuf {
+    DATA_DIR
+        .get_or_init(|| {
+            data_dir_env()
+                .or_else(configured_data_dir)
+                .unwrap_or_else(default_pixel_agents_dir)
+        })
+        .clone()
+}
+
+pub fn default_pixel_agents_dir() -> PathBuf {
+    home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".pixel-agents")
+}
+
+pub fn data_dir_env() -> Option<PathBuf> {
+    std::env::var_os(DATA_DIR_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Lives outside the data directory it points at, in the machine-local
+/// config dir so it is not carried along by synced or network homes.
+pub fn data_dir_config_file() -> PathBuf {
+    dirs::config_local_dir()
+        .or_else(home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("pixel-agents")
+        .join("data-dir.json")
+}
+
+pub fn configured_data_dir() -> Option<PathBuf> {
+    let raw = fs::read_to_string(data_dir_config_file()).ok()?;
+    let value: Value = serde_json::from_str(&raw).ok()?;
+    value
+        .get("dataDir")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+pub fn layout_file() -> PathBuf {
+    pixel_agents_dir().join("layout.json")
+}
+
+pub fn layouts_dir() -> PathBuf {
+    pixel_agents_dir().join("layouts")
+}
+
+pub fn reset_backups_dir() -> PathBuf {
+    pixel_agents_dir().join("reset-backups")
+}
+
+pub fn agent_seats_file() -> PathBuf {
+    pixel_agents_dir().join("agent-seats.json")
+}
+
+pub fn monitor_settings_file() -> PathBuf {
+    pixel_agents_dir().join("monitor-settings.json")
+}
+
+pub fn sound_settings_file() -> PathBuf {
+    pixel_agents_dir().join("desktop-settings.json")
+}
+
+pub fn notification_history_file() -> PathBuf {
+    pixel_agents_dir().join("notification-history.jsonl")
+}
+
+pub fn repo_bindings_file() -> PathBuf {
+    pixel_agents_dir().join("monitor-repo-bindings.json")
+}
+
+pub fn agent_prefs_file() -> PathBuf {
+    pixel_agents_dir().join("agent-prefs.json")
+}
+
+pub fn opencode_message_root() -> PathBuf {
+    opencode_storage_root().join("message")
+}
+
+pub fn opencode_part_root() -> PathBuf {
+    opencode_storage_root().join("part")
+}
+
+pub fn opencode_session_root() -> PathBuf {
+    opencode_storage_root().join("session")
+}
+
+pub fn opencode_project_root() -> PathBuf {
+    opencode_storage_root().join("project")
+}
+
+pub fn opencode_storage_root() -> PathBuf {
+    opencode_data_root().join("storage")
+}
+
+pub fn opencode_db_file() -> PathBuf {
+    opencode_data_root().join("opencode.db")
+}
+
+pub fn opencode_data_root() -> PathBuf {
+    let configured = std::env::var("OPENCODE_DATA_DIR")
+        .ok()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".local")
+                .join("share")
+                .join("opencode")
+        });
+
+    if configured
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.eq_ignore_ascii_case("storage"))
+        .unwrap_or(false)
+    {
+        return configured
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or(configured);
+    }
+    configured
+}
+
+pub fn claude_data_root() -> PathBuf {
+    std::env::var_os("CLAUDE_CONFIG_DIR")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".claude")
+        })
+}
+
+pub fn codex_sessions_root() -> PathBuf {
+    std::env::var("CODEX_HOME")
+        .ok()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".codex")
+        })
+        .join("sessions")
+}
+
+pub fn windsurf_cascade_root() -> PathBuf {
+    std::env::var("WINDSURF_DATA_DIR")
+        .ok()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".codeium")
+                .join("windsurf")
+        })
+        .join("cascade")
+}
+
+pub fn windsurf_workspace_storage_root() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Windsurf")
+        .join("User")
+        .join("workspaceStorage")
+}
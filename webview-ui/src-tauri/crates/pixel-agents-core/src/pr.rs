@@ -0,0 +1,331 @@
+use crate::error::Error;
+use crate::github::{self, GithubClient};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+const MIN_PR_POLL_INTERVAL_MS: i64 = 15_000;
+const MAX_PR_REFRESHES_PER_TICK: usize = 2;
+const MAX_PRS_PER_REPO: usize = 50;
+const MAX_RUNS_PER_REPO: usize = 50;
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PullRequest {
+    #[serde(default)]
+    pub(crate) id: String,
+    pub(crate) number: i64,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) state: String,
+    #[serde(rename = "headRefName")]
+    pub(crate) head_ref_name: String,
+    #[serde(rename = "isDraft", default)]
+    pub(crate) is_draft: bool,
+    #[serde(rename = "reviewDecision", default)]
+    pub(crate) review_decision: Option<String>,
+    #[serde(default)]
+    pub(crate) mergeable: Option<String>,
+    #[serde(rename = "mergeStateStatus", default)]
+    pub(crate) merge_state_status: Option<String>,
+    #[serde(rename = "statusCheckRollup", default)]
+    pub(crate) status_check_rollup: Vec<CheckRollupItem>,
+    #[serde(default)]
+    pub(crate) comments: Vec<PrComment>,
+    #[serde(default)]
+    pub(crate) reviews: Vec<PrComment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PrComment {
+    #[serde(default)]
+    pub(crate) author: Option<PrAuthor>,
+    #[serde(default)]
+    pub(crate) body: String,
+    #[serde(rename = "createdAt", alias = "submittedAt", default)]
+    pub(crate) created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PrAuthor {
+    pub(crate) login: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct CheckRollupItem {
+    pub(crate) status: Option<String>,
+    pub(crate) conclusion: Option<String>,
+    pub(crate) state: Option<String>,
+}
+
+impl PullRequest {
+    pub(crate) fn review_state(&self) -> Option<String> {
+        self.review_decision
+            .as_deref()
+            .map(|decision| decision.trim().to_lowercase())
+            .filter(|decision| !decision.is_empty())
+    }
+
+    pub(crate) fn comment_count(&self) -> usize {
+        self.all_comments().count()
+    }
+
+    pub(crate) fn latest_comment(&self) -> Option<&PrComment> {
+        self.all_comments()
+            .max_by(|a, b| a.created_at.cmp(&b.created_at))
+    }
+
+    fn all_comments(&self) -> impl Iterator<Item = &PrComment> {
+        self.comments
+            .iter()
+            .chain(self.reviews.iter())
+            .filter(|comment| !comment.body.trim().is_empty())
+    }
+
+    pub(crate) fn has_conflicts(&self) -> bool {
+        self.mergeable
+            .as_deref()
+            .is_some_and(|m| m.eq_ignore_ascii_case("conflicting"))
+            || self
+                .merge_state_status
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case("dirty"))
+    }
+
+    pub(crate) fn checks_state(&self) -> Option<&'static str> {
+        let mut pending = false;
+        for item in &self.status_check_rollup {
+            let outcome = item
+                .conclusion
+                .as_deref()
+                .filter(|c| !c.is_empty())
+                .or(item.state.as_deref())
+                .unwrap_or_default()
+                .to_uppercase();
+            let completed = item
+                .status
+                .as_deref()
+                .map(|status| status.eq_ignore_ascii_case("completed"))
+                .unwrap_or(item.state.is_some());
+            match outcome.as_str() {
+                "FAILURE" | "ERROR" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED"
+                | "STARTUP_FAILURE" => return Some("failing"),
+                "PENDING" | "EXPECTED" => pending = true,
+                _ if !completed => pending = true,
+                _ => {}
+            }
+        }
+        if self.status_check_rollup.is_empty() {
+            None
+        } else if pending {
+            Some("pending")
+        } else {
+            Some("passing")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct WorkflowRun {
+    #[serde(rename = "workflowName", default)]
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) status: String,
+    #[serde(default)]
+    pub(crate) conclusion: String,
+    #[serde(default)]
+    pub(crate) url: String,
+    #[serde(rename = "headBranch", default)]
+    pub(crate) head_branch: String,
+    #[serde(rename = "createdAt", default)]
+    pub(crate) created_at: String,
+}
+
+struct PrCacheEntry {
+    checked_ms: i64,
+    prs: Vec<PullRequest>,
+    runs: Vec<WorkflowRun>,
+}
+
+#[derive(Default)]
+pub(crate) struct PrCache {
+    entries: HashMap<String, PrCacheEntry>,
+    last_seen: HashMap<(String, &'static str), String>,
+}
+
+impl PrCache {
+    pub(crate) fn refresh(
+        &mut self,
+        repo_branches: &HashMap<String, Vec<String>>,
+        github_token: Option<&str>,
+        now_ms: i64,
+        interval_ms: i64,
+    ) {
+        let interval_ms = interval_ms.max(MIN_PR_POLL_INTERVAL_MS);
+        self.entries
+            .retain(|repo_path, _| repo_branches.contains_key(repo_path));
+
+        let client = github_token.map(GithubClient::new);
+        let mut stale: Vec<(&String, i64)> = repo_branches
+            .keys()
+            .map(|path| {
+                let checked = self.entries.get(path).map(|e| e.checked_ms).unwrap_or(0);
+                (path, checked)
+            })
+            .filter(|(_, checked)| now_ms - checked >= interval_ms)
+            .collect();
+        stale.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+
+        for (repo_path, _) in stale.into_iter().take(MAX_PR_REFRESHES_PER_TICK) {
+            let (fetched_prs, fetched_runs) = match &client {
+                Some(client) => match github::repo_for_path(repo_path) {
+                    Some(repo) => {
+                        let branches = repo_branches.get(repo_path).cloned().unwrap_or_default();
+                        (
+                            client.list_open_prs(&repo, &branches, MAX_PRS_PER_REPO),
+                            client.list_workflow_runs(&repo, MAX_RUNS_PER_REPO),
+                        )
+                    }
+                    None => (None, None),
+                },
+                None => (list_open_prs(repo_path), list_workflow_runs(repo_path)),
+            };
+            let previous = self.entries.remove(repo_path);
+            let prs = fetched_prs
+                .or_else(|| previous.as_ref().map(|e| e.prs.clone()))
+                .unwrap_or_default();
+            let runs = fetched_runs
+                .or_else(|| previous.map(|e| e.runs))
+                .unwrap_or_default();
+            self.entries.insert(
+                repo_path.clone(),
+                PrCacheEntry {
+                    checked_ms: now_ms,
+                    prs,
+                    runs,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn record_transition(
+        &mut self,
+        pr_url: &str,
+        field: &'static str,
+        value: &str,
+    ) -> Option<String> {
+        self.last_seen
+            .insert((pr_url.to_string(), field), value.to_string())
+            .filter(|previous| previous != value)
+    }
+
+    pub(crate) fn mark_ready_cached(&mut self, pr_url: &str) {
+        for entry in self.entries.values_mut() {
+            for pr in entry.prs.iter_mut().filter(|pr| pr.url == pr_url) {
+                pr.is_draft = false;
+            }
+        }
+    }
+
+    pub(crate) fn latest_run_for_branch(
+        &self,
+        repo_path: &str,
+        branch: &str,
+    ) -> Option<WorkflowRun> {
+        self.entries
+            .get(repo_path)?
+            .runs
+            .iter()
+            .filter(|run| run.head_branch == branch)
+            .max_by(|a, b| a.created_at.cmp(&b.created_at))
+            .cloned()
+    }
+
+    pub(crate) fn prs_for_head(&self, repo_path: Option<&str>, branch: &str) -> Vec<PullRequest> {
+        self.entries
+            .iter()
+            .filter(|(path, _)| repo_path.is_none_or(|repo_path| repo_path == path.as_str()))
+            .flat_map(|(_, entry)| entry.prs.iter())
+            .filter(|pr| pr.head_ref_name == branch)
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn prs_for_branch(&self, repo_path: &str, branch: &str) -> Vec<PullRequest> {
+        self.entries
+            .get(repo_path)
+            .map(|entry| {
+                entry
+                    .prs
+                    .iter()
+                    .filter(|pr| pr.head_ref_name == branch)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) fn list_open_prs(repo_path: &str) -> Option<Vec<PullRequest>> {
+    let limit = MAX_PRS_PER_REPO.to_string();
+    let output = gh_output(
+        repo_path,
+        &[
+            "pr",
+            "list",
+            "--state",
+            "open",
+            "--limit",
+            &limit,
+            "--json",
+            "id,number,title,url,state,headRefName,isDraft,reviewDecision,mergeable,mergeStateStatus,statusCheckRollup,comments,reviews",
+        ],
+    )?;
+    serde_json::from_str(&output).ok()
+}
+
+pub(crate) fn list_workflow_runs(repo_path: &str) -> Option<Vec<WorkflowRun>> {
+    let limit = MAX_RUNS_PER_REPO.to_string();
+    let output = gh_output(
+        repo_path,
+        &[
+            "run",
+            "list",
+            "--limit",
+            &limit,
+            "--json",
+            "workflowName,status,conclusion,url,headBranch,createdAt",
+        ],
+    )?;
+    serde_json::from_str(&output).ok()
+}
+
+pub(crate) fn mark_ready(
+    repo_path: &str,
+    pr: &PullRequest,
+    github_token: Option<&str>,
+) -> Result<(), Error> {
+    if let Some(token) = github_token {
+        let repo = github::repo_for_path(repo_path)
+            .ok_or_else(|| Error::Github("repo has no GitHub remote".to_string()))?;
+        return GithubClient::new(token).mark_ready(&repo, &pr.id);
+    }
+    gh_output(repo_path, &["pr", "ready", &pr.number.to_string()])
+        .map(|_| ())
+        .ok_or_else(|| Error::Github(format!("gh pr ready failed for #{}", pr.number)))
+}
+
+fn gh_output(cwd: &str, args: &[&str]) -> Option<String> {
+    if !Path::new(cwd).is_dir() {
+        return None;
+    }
+    let output = Command::new("gh")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,141 @@
+use crate::error::Error;
+use crate::paths::agent_prefs_file;
+use crate::settings::{read_versioned_file, write_versioned_file};
+use crate::util::now_ms;
+use crate::{migrate, store};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const DISMISSED_ALERT_TTL_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentPrefs {
+    #[serde(default)]
+    pub archived: Vec<ArchivedAgent>,
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    #[serde(default)]
+    pub names: HashMap<String, String>,
+    #[serde(default)]
+    pub repo_names: HashMap<String, String>,
+    #[serde(default)]
+    pub notes: HashMap<String, String>,
+    #[serde(default)]
+    pub muted: Vec<String>,
+    #[serde(default)]
+    pub dismissed_alerts: Vec<DismissedAlert>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedAgent {
+    pub key: String,
+    pub source: String,
+    pub display_name: Option<String>,
+    pub archived_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DismissedAlert {
+    pub key: String,
+    pub ts_ms: i64,
+    pub dismissed_ms: i64,
+}
+
+impl AgentPrefs {
+    pub fn is_archived(&self, key: &str) -> bool {
+        self.archived.iter().any(|agent| agent.key == key)
+    }
+
+    pub fn is_pinned(&self, key: &str) -> bool {
+        self.pinned.iter().any(|pinned| pinned == key)
+    }
+
+    pub fn is_muted(&self, key: &str) -> bool {
+        self.muted.iter().any(|muted| muted == key)
+    }
+
+    pub fn is_alert_dismissed(&self, key: &str, ts_ms: i64) -> bool {
+        self.dismissed_alerts
+            .iter()
+            .any(|alert| alert.key == key && alert.ts_ms == ts_ms)
+    }
+
+    pub fn dismiss_alert(&mut self, key: &str, ts_ms: i64) {
+        let now = now_ms();
+        self.dismissed_alerts.retain(|alert| {
+            now - alert.dismissed_ms < DISMISSED_ALERT_TTL_MS
+                && !(alert.key == key && alert.ts_ms == ts_ms)
+        });
+        self.dismissed_alerts.push(DismissedAlert {
+            key: key.to_string(),
+            ts_ms,
+            dismissed_ms: now,
+        });
+    }
+
+    pub fn name_for(&self, key: &str, repo_path: Option<&str>) -> Option<&str> {
+        self.names
+            .get(key)
+            .or_else(|| repo_path.and_then(|repo| self.repo_names.get(repo)))
+            .map(String::as_str)
+    }
+
+    pub fn set_name(&mut self, key: &str, repo_path: Option<&str>, name: Option<&str>) {
+        match name.map(str::trim).filter(|name| !name.is_empty()) {
+            Some(name) => {
+                self.names.insert(key.to_string(), name.to_string());
+                if let Some(repo) = repo_path {
+                    self.repo_names.insert(repo.to_string(), name.to_string());
+                }
+            }
+            None => {
+                self.names.remove(key);
+                if let Some(repo) = repo_path {
+                    self.repo_names.remove(repo);
+                }
+            }
+        }
+    }
+
+    pub fn set_note(&mut self, key: &str, note: Option<&str>) {
+        match note.map(str::trim).filter(|note| !note.is_empty()) {
+            Some(note) => {
+                self.notes.insert(key.to_string(), note.to_string());
+            }
+            None => {
+                self.notes.remove(key);
+            }
+        }
+    }
+
+    pub fn set_pinned(&mut self, key: &str, pinned: bool) {
+        self.pinned.retain(|k| k != key);
+        if pinned {
+            self.pinned.push(key.to_string());
+        }
+    }
+
+    pub fn set_muted(&mut self, key: &str, muted: bool) {
+        self.muted.retain(|k| k != key);
+        if muted {
+            self.muted.push(key.to_string());
+        }
+    }
+}
+
+pub fn read_agent_prefs() -> AgentPrefs {
+    match read_versioned_file(&agent_prefs_file(), &migrate::AGENT_PREFS) {
+        Ok(value) => serde_json::from_value(value).unwrap_or_default(),
+        Err(_) => AgentPrefs::default(),
+    }
+}
+
+pub fn update_agent_prefs<T>(update: impl FnOnce(&mut AgentPrefs) -> T) -> Result<T, Error> {
+    let path = agent_prefs_file();
+    store::with_lock(&path, || {
+        let mut prefs = read_agent_prefs();
+        let out = update(&mut prefs);
+        write_versioned_file(&path, &migrate::AGENT_PREFS, serde_json::to_value(&prefs)?)?;
+        Ok(out)
+    })
+}
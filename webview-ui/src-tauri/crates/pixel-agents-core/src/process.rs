@@ -0,0 +1,139 @@
+use crate::model::MonitorAgentView;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind};
+
+const PROCESS_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+pub(crate) struct ProcessTable {
+    system: System,
+    refreshed_at: Option<Instant>,
+}
+
+impl ProcessTable {
+    pub(crate) fn system(&self) -> &System {
+        &self.system
+    }
+
+    pub(crate) fn refresh(&mut self) {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cpu()
+                .with_memory()
+                .with_cmd(UpdateKind::OnlyIfNotSet)
+                .with_cwd(UpdateKind::OnlyIfNotSet),
+        );
+        self.refreshed_at = Some(Instant::now());
+    }
+
+    pub(crate) fn attach(&mut self, agents: &mut [MonitorAgentView]) {
+        if self
+            .refreshed_at
+            .is_none_or(|at| at.elapsed() >= PROCESS_REFRESH_INTERVAL)
+        {
+            self.refresh();
+        }
+        // A cwd match only identifies a session when nothing else is open in that repo.
+        let mut open_sessions: HashMap<String, usize> = HashMap::new();
+        for agent in agents.iter().filter(|agent| agent.state != "done") {
+            if let Some(repo) = &agent.repo_path {
+                *open_sessions.entry(repo.clone()).or_default() += 1;
+            }
+        }
+        for agent in agents.iter_mut() {
+            let lone_session = agent.state != "done"
+                && agent
+                    .repo_path
+                    .as_ref()
+                    .is_some_and(|repo| open_sessions.get(repo) == Some(&1));
+            let found = match agent_pids(&self.system, agent, lone_session)[..] {
+                [pid] => self.system.process(pid),
+                _ => None,
+            };
+            agent.pid = found.map(|process| process.pid().as_u32());
+            agent.cpu_percent = found.map(Process::cpu_usage);
+            agent.rss_bytes = found.map(Process::memory);
+        }
+    }
+}
+
+/// Processes whose argv names the agent's session; with `match_cwd`, processes
+/// running inside its repo count too when none names the session.
+pub(crate) fn agent_pids(system: &System, agent: &MonitorAgentView, match_cwd: bool) -> Vec<Pid> {
+    let Some(program) = agent_program(&agent.source) else {
+        return Vec::new();
+    };
+    let scored: Vec<(Pid, Option<Pid>, u8)> = system
+        .processes()
+        .values()
+        .filter(|process| runs_program(process, program))
+        .filter_map(|process| {
+            let score = if mentions_session(process, &agent.session_id) {
+                2
+            } else if match_cwd && in_repo(process, agent.repo_path.as_deref()) {
+                1
+            } else {
+                return None;
+            };
+            Some((process.pid(), process.parent(), score))
+        })
+        .collect();
+    let Some(best) = scored.iter().map(|(_, _, score)| *score).max() else {
+        return Vec::new();
+    };
+    let candidates: Vec<(Pid, Option<Pid>)> = scored
+        .into_iter()
+        .filter(|(_, _, score)| *score == best)
+        .map(|(pid, parent, _)| (pid, parent))
+        .collect();
+    let mut pids: Vec<Pid> = candidates
+        .iter()
+        .filter(|(pid, _)| !candidates.iter().any(|(_, parent)| *parent == Some(*pid)))
+        .map(|(pid, _)| *pid)
+        .collect();
+    pids.sort();
+    pids
+}
+
+pub(crate) fn kill_process(process: &Process, force: bool) -> bool {
+    let signal = if force { Signal::Kill } else { Signal::Term };
+    process.kill_with(signal).unwrap_or_else(|| process.kill())
+}
+
+fn agent_program(source: &str) -> Option<&'static str> {
+    match source {
+        "claude" => Some("claude"),
+        "codex" => Some("codex"),
+        "opencode" => Some("opencode"),
+        _ => None,
+    }
+}
+
+fn runs_program(process: &Process, program: &str) -> bool {
+    let matches = |text: &OsStr| {
+        Path::new(text)
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .is_some_and(|stem| stem.eq_ignore_ascii_case(program))
+    };
+    matches(process.name()) || process.cmd().iter().take(2).any(|arg| matches(arg))
+}
+
+fn mentions_session(process: &Process, session_id: &str) -> bool {
+    process
+        .cmd()
+        .iter()
+        .any(|arg| arg.to_string_lossy().contains(session_id))
+}
+
+fn in_repo(process: &Process, repo_path: Option<&str>) -> bool {
+    match (process.cwd(), repo_path) {
+        (Some(cwd), Some(repo)) => cwd.starts_with(repo),
+        _ => false,
+    }
+}
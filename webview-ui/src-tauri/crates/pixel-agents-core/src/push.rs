@@ -0,0 +1,235 @@
+use crate::error::Error;
+use crate::github::{API_TIMEOUT_SECS, USER_AGENT};
+use crate::model::MonitorNotification;
+use crate::settings::{read_desktop_settings, write_desktop_setting};
+use crate::webhooks::{subscribes, WEBHOOK_EVENTS};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::thread;
+use std::time::Duration;
+
+const PUSH_KEY: &str = "push";
+const PUSHOVER_URL: &str = "https://api.pushover.net/1/messages.json";
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_BASE_DELAY_MS: u64 = 1_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "lowercase")]
+pub enum PushTarget {
+    Ntfy {
+        #[serde(default = "default_ntfy_server")]
+        server: String,
+        topic: String,
+        /// Access token for protected topics.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+    },
+    Pushover {
+        #[serde(rename = "userKey")]
+        user_key: String,
+        #[serde(rename = "appToken")]
+        app_token: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PushSettings {
+    #[serde(flatten)]
+    pub target: PushTarget,
+    #[serde(default = "default_push_events")]
+    pub events: Vec<String>,
+}
+
+fn default_ntfy_server() -> String {
+    "https://ntfy.sh".to_string()
+}
+
+fn default_push_events() -> Vec<String> {
+    ["done", "error", "waiting", "approval"]
+        .map(str::to_string)
+        .to_vec()
+}
+
+impl PushSettings {
+    fn validate(&self) -> Result<(), Error> {
+        match &self.target {
+            PushTarget::Ntfy { server, topic, .. } => {
+                if !server.starts_with("https://") && !server.starts_with("http://") {
+                    return Err(Error::Invalid(format!(
+                        "ntfy server must start with http:// or https://: {}",
+                        server
+                    )));
+                }
+                if topic.trim().is_empty() || topic.contains('/') {
+                    return Err(Error::Invalid("ntfy topic is empty or invalid".to_string()));
+                }
+            }
+            PushTarget::Pushover {
+                user_key,
+                app_token,
+            } => {
+                if user_key.trim().is_empty() || app_token.trim().is_empty() {
+                    return Err(Error::Invalid(
+                        "pushover needs both a user key and an app token".to_string(),
+                    ));
+                }
+            }
+        }
+        if let Some(event) = self
+            .events
+            .iter()
+            .find(|event| !WEBHOOK_EVENTS.contains(&event.as_str()))
+        {
+            return Err(Error::Invalid(format!("unknown push event: {}", event)));
+        }
+        Ok(())
+    }
+}
+
+pub fn read_push_settings() -> Option<PushSettings> {
+    read_desktop_settings()
+        .ok()
+        .and_then(|mut v| v.get_mut(PUSH_KEY).map(Value::take))
+        .and_then(|push| serde_json::from_value(push).ok())
+}
+
+pub fn write_push_settings(settings: Option<&PushSettings>) -> Result<(), Error> {
+    match settings {
+        Some(settings) => {
+            settings.validate()?;
+            write_desktop_setting(PUSH_KEY, serde_json::to_value(settings)?)
+        }
+        None => write_desktop_setting(PUSH_KEY, Value::Null),
+    }
+}
+
+/// Publishes subscribed notifications to the configured push channel, logging failures.
+pub fn dispatch_push(notifications: &[MonitorNotification]) {
+    let Some(settings) = read_push_settings() else {
+        return;
+    };
+    for notification in notifications
+        .iter()
+        .filter(|n| subscribes(&settings.events, &n.kind))
+    {
+        if let Err(e) = send_push(&settings, notification) {
+            tracing::warn!(kind = %notification.kind, error = %e, "push notification failed");
+        }
+    }
+}
+
+/// Sends one push, retrying transport errors, 429s and 5xx responses with
+/// exponential backoff.
+pub fn send_push(settings: &PushSettings, notification: &MonitorNotification) -> Result<(), Error> {
+    settings.validate()?;
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(API_TIMEOUT_SECS))
+        .user_agent(USER_AGENT)
+        .build();
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match publish(&agent, &settings.target, notification) {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < MAX_ATTEMPTS && is_retryable(&e) => {
+                let delay = RETRY_BASE_DELAY_MS << (attempt - 1);
+                tracing::debug!(attempt, delay_ms = delay, error = %e, "retrying push");
+                thread::sleep(Duration::from_millis(delay));
+            }
+            Err(e) => return Err(Error::State(format!("push failed: {}", e))),
+        }
+    }
+}
+
+fn publish(
+    agent: &ureq::Agent,
+    target: &PushTarget,
+    notification: &MonitorNotification,
+) -> Result<(), Box<ureq::Error>> {
+    let urgent = matches!(
+        notification.kind.as_str(),
+        "error" | "waiting" | "approval" | "stuck"
+    );
+    // Silent quiet hours still deliver, but at the provider's no-alert priority.
+    let (ntfy_priority, pushover_priority) = match (notification.silent, urgent) {
+        (true, _) => ("min", "-2"),
+        (false, true) => ("high", "1"),
+        (false, false) => ("default", "0"),
+    };
+    match target {
+        PushTarget::Ntfy {
+            server,
+            topic,
+            token,
+        } => {
+            let mut request = agent
+                .post(&format!(
+                    "{}/{}",
+                    server.trim_end_matches('/'),
+                    topic.trim()
+                ))
+                .set("Title", &notification.title)
+                .set("Tags", &notification.kind)
+                .set("Priority", ntfy_priority);
+            if let Some(token) = token.as_deref().filter(|t| !t.is_empty()) {
+                request = request.set("Authorization", &format!("Bearer {}", token));
+            }
+            request
+                .send_string(&notification.message)
+                .map(drop)
+                .map_err(Box::new)
+        }
+        PushTarget::Pushover {
+            user_key,
+            app_token,
+        } => agent
+            .post(PUSHOVER_URL)
+            .send_form(&[
+                ("token", app_token.trim()),
+                ("user", user_key.trim()),
+                ("title", &notification.title),
+                ("message", &notification.message),
+                ("priority", pushover_priority),
+            ])
+            .map(drop)
+            .map_err(Box::new),
+    }
+}
+
+fn is_retryable(error: &ureq::Error) -> bool {
+    match error {
+        ureq::Error::Status(code, _) => *code == 429 || *code >= 500,
+        ureq::Error::Transport(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_provider_tagged_settings() {
+        let ntfy: PushSettings =
+            serde_json::from_value(json!({ "provider": "ntfy", "topic": "my-agents" })).unwrap();
+        assert_eq!(
+            ntfy.target,
+            PushTarget::Ntfy {
+                server: "https://ntfy.sh".to_string(),
+                topic: "my-agents".to_string(),
+                token: None,
+            }
+        );
+        assert_eq!(ntfy.events, default_push_events());
+        assert!(ntfy.validate().is_ok());
+
+        let pushover: PushSettings = serde_json::from_value(json!({
+            "provider": "pushover",
+            "userKey": "u",
+            "appToken": "",
+            "events": ["error"],
+        }))
+        .unwrap();
+        assert!(pushover.validate().is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error::Error;
+use crate::model::ResetReport;
+use crate::paths::{
+    agent_prefs_file, agent_seats_file, layout_file, layouts_dir, monitor_settings_file,
+    notification_history_file, pixel_agents_dir, repo_bindings_file, reset_backups_dir,
+    sound_settings_file,
+};
+use crate::settings::write_desktop_setting;
+use crate::store::backups_dir;
+use crate::util::now_ms;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResetScope {
+    Monitor,
+    Bindings,
+    Layouts,
+    All,
+}
+
+pub fn reset_data(scope: ResetScope) -> Result<ResetReport, Error> {
+    let root = pixel_agents_dir();
+    let entries = app_entries(&root);
+    let backup_path = write_backup_zip(&root, &entries)?;
+    let targets = match scope {
+        ResetScope::Monitor => vec![
+            monitor_settings_file(),
+            agent_prefs_file(),
+            agent_seats_file(),
+            notification_history_file(),
+        ],
+        ResetScope::Bindings => vec![repo_bindings_file()],
+        ResetScope::Layouts => vec![layout_file(), layouts_dir()],
+        ResetScope::All => entries,
+    };
+    let mut removed = Vec::new();
+    for path in targets {
+        let result = if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        match result {
+            Ok(()) => removed.push(path.to_string_lossy().into_owned()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    if scope == ResetScope::Layouts {
+        write_desktop_setting("activeLayout", Value::Null)?;
+    }
+    tracing::info!(?scope, backup = %backup_path.display(), removed = removed.len(), "reset data");
+    Ok(ResetReport {
+        backup_path: backup_path.to_string_lossy().into_owned(),
+        removed,
+    })
+}
+
+/// Everything the app writes at the top of the data dir, plus the lock and
+/// temp files beside them. The data dir can be a shared folder, so a full
+/// reset must not touch anything else.
+fn app_entries(root: &Path) -> Vec<PathBuf> {
+    let owned = [
+        layout_file(),
+        layouts_dir(),
+        monitor_settings_file(),
+        sound_settings_file(),
+        notification_history_file(),
+        repo_bindings_file(),
+        agent_prefs_file(),
+        agent_seats_file(),
+        backups_dir(),
+    ];
+    let siblings: Vec<_> = owned
+        .iter()
+        .filter_map(|path| path.file_name())
+        .flat_map(|name| {
+            let name = name.to_string_lossy();
+            [
+                root.join(format!(".{}.lock", name)),
+                root.join(format!(".{}.tmp", name)),
+            ]
+        })
+        .collect();
+    owned.into_iter().chain(siblings).collect()
+}
+
+fn write_backup_zip(root: &Path, entries: &[PathBuf]) -> Result<PathBuf, Error> {
+    let dir = reset_backups_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("pixel-agents-{}.zip", now_ms()));
+    let mut zip = ZipWriter::new(fs::File::create(&path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let files = entries
+        .iter()
+        .flat_map(WalkDir::new)
+        .flatten()
+        .filter(|entry| entry.file_type().is_file());
+    for entry in files {
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let name = relative
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        zip.start_file(name, options)?;
+        io::copy(&mut fs::File::open(entry.path())?, &mut zip)?;
+    }
+    zip.finish()?;
+    Ok(path)
+}
@@ -0,0 +1,243 @@
+use crate::model::MonitorEventView;
+use crate::util::system_time_to_ms;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub(crate) struct ScanCounts {
+    pub(crate) rescanned: bool,
+    pub(crate) files: usize,
+    pub(crate) parse_errors: usize,
+    pub(crate) warning: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceScanStats {
+    pub(crate) last_scan_ms: i64,
+    pub(crate) duration_ms: u64,
+    pub(crate) agents: usize,
+    pub(crate) files: usize,
+    pub(crate) parse_errors: usize,
+    pub(crate) warning: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct JsonFileCache {
+    pub(crate) entries: HashMap<PathBuf, JsonFileEntry>,
+    pub(crate) seen: HashSet<PathBuf>,
+}
+
+pub(crate) struct JsonFileEntry {
+    pub(crate) modified: i64,
+    pub(crate) len: u64,
+    pub(crate) value: Option<Value>,
+}
+
+impl JsonFileCache {
+    pub(crate) fn read(&mut self, path: &Path) -> Option<&Value> {
+        let meta = fs::metadata(path).ok()?;
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(system_time_to_ms)
+            .unwrap_or_default();
+        let len = meta.len();
+        self.seen.insert(path.to_path_buf());
+        let fresh = self
+            .entries
+            .get(path)
+            .is_some_and(|entry| entry.modified == modified && entry.len == len);
+        if !fresh {
+            let value = fs::read_to_string(path)
+                .ok()
+                .and_then(|raw| serde_json::from_str(&raw).ok());
+            self.entries.insert(
+                path.to_path_buf(),
+                JsonFileEntry {
+                    modified,
+                    len,
+                    value,
+                },
+            );
+        }
+        self.entries
+            .get(path)
+            .and_then(|entry| entry.value.as_ref())
+    }
+
+    pub(crate) fn sweep(&mut self) {
+        let seen = std::mem::take(&mut self.seen);
+        self.entries.retain(|path, _| seen.contains(path));
+    }
+
+    pub(crate) fn parse_failures(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|entry| entry.value.is_none())
+            .count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AgentTemp {
+    pub(crate) key: String,
+    pub(crate) source: String,
+    pub(crate) session_id: String,
+    pub(crate) agent_name: Option<String>,
+    pub(crate) state: String,
+    pub(crate) started_ts_ms: i64,
+    pub(crate) last_ts_ms: i64,
+    pub(crate) last_text: Option<String>,
+    pub(crate) repo_path: Option<String>,
+    pub(crate) recent_events: Vec<MonitorEventView>,
+}
+
+pub(crate) fn cap_recent_events<'a>(agents: impl Iterator<Item = &'a mut AgentTemp>, cap: usize) {
+    let mut agents: Vec<&mut AgentTemp> = agents.collect();
+    let total: usize = agents.iter().map(|a| a.recent_events.len()).sum();
+    if total <= cap {
+        return;
+    }
+    agents.sort_by(|a, b| b.last_ts_ms.cmp(&a.last_ts_ms));
+    let mut remaining = cap;
+    for agent in agents {
+        agent.recent_events.truncate(remaining);
+        remaining -= agent.recent_events.len();
+    }
+}
+
+pub fn normalize_source_name(source: &str) -> String {
+    let normalized = source.trim().to_lowercase();
+    if normalized == "claude"
+        || normalized == "claude code"
+        || normalized == "claude-code"
+        || normalized == "claudecode"
+    {
+        return "claude".to_string();
+    }
+    if normalized == "opencode"
+        || normalized == "open"
+        || normalized == "open-code"
+        || normalized == "open_code"
+    {
+        return "opencode".to_string();
+    }
+    if normalized == "codex" {
+        return "codex".to_string();
+    }
+    if normalized == "windsurf" || normalized == "cascade" || normalized == "windsurf-cascade" {
+        return "windsurf".to_string();
+    }
+    source.to_string()
+}
+
+pub(crate) fn is_rate_limit_text(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    lower.contains("rate limit")
+        || lower.contains("rate_limit")
+        || lower.contains("ratelimit")
+        || lower.contains("usage limit")
+        || lower.contains("usage_limit")
+        || lower.contains("quota")
+        || lower.contains("too many requests")
+}
+
+pub(crate) fn upsert_agent(map: &mut HashMap<String, AgentTemp>, incoming: AgentTemp) {
+    match map.get_mut(&incoming.key) {
+        Some(existing) => {
+            let started_ts_ms = existing.started_ts_ms.min(incoming.started_ts_ms);
+            existing.started_ts_ms = started_ts_ms;
+            if incoming.last_ts_ms >= existing.last_ts_ms {
+                let mut merged = incoming;
+                merged.started_ts_ms = started_ts_ms;
+                if merged.repo_path.is_none() {
+                    merged.repo_path = existing.repo_path.clone();
+                }
+                if merged.last_text.is_none() {
+                    merged.last_text = existing.last_text.clone();
+                }
+                if merged.agent_name.is_none() {
+                    merged.agent_name = existing.agent_name.clone();
+                }
+                *existing = merged;
+            }
+        }
+        None => {
+            map.insert(incoming.key.clone(), incoming);
+        }
+    }
+}
+
+pub(crate) fn short_session(session: &str) -> String {
+    session.chars().take(8).collect()
+}
+
+pub(crate) fn normalize_agent_name(name: &str) -> Option<String> {
+    let compact = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim()
+        .to_string();
+    if compact.is_empty() {
+        return None;
+    }
+    let mut out = String::new();
+    for (idx, ch) in compact.chars().enumerate() {
+        if idx >= 56 {
+            break;
+        }
+        out.push(ch);
+    }
+    if compact.chars().count() > 56 {
+        out.push_str("...");
+    }
+    Some(out)
+}
+
+pub(crate) fn repo_label(repo_path: &str) -> Option<String> {
+    let trimmed = repo_path.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let path = Path::new(trimmed);
+    let name = path
+        .file_name()
+        .and_then(|v| v.to_str())
+        .unwrap_or(trimmed)
+        .trim();
+    if name.is_empty() {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+pub(crate) fn format_agent_display_name(
+    source: &str,
+    session_id: &str,
+    name_override: Option<&str>,
+    agent_name: Option<&str>,
+    repo_path: Option<&str>,
+    branch: Option<&str>,
+) -> String {
+    if let Some(name) = name_override.map(str::trim).filter(|name| !name.is_empty()) {
+        return name.to_string();
+    }
+    let normalized_source = normalize_source_name(source);
+    if let Some(name) = agent_name.and_then(normalize_agent_name) {
+        return format!("{}: {}", normalized_source, name);
+    }
+    if let Some(repo) = repo_path
+        .and_then(repo_label)
+        .and_then(|name| normalize_agent_name(&name))
+    {
+        if let Some(branch) = branch.map(str::trim).filter(|b| !b.is_empty()) {
+            return format!("{}: {} ({})", normalized_source, repo, branch);
+        }
+        return format!("{}: {}", normalized_source, repo);
+    }
+    format!("{}: {}", normalized_source, short_session(session_id))
+}
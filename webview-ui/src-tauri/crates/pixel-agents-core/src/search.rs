@@ -0,0 +1,229 @@
+use crate::codex_record::CodexLine;
+use crate::error::Error;
+use crate::model::{SessionSearchHit, TranscriptEntry};
+use crate::opencode::open_opencode_db;
+use crate::opencode_record::OpencodeSession;
+use crate::paths::{codex_sessions_root, opencode_db_file, opencode_session_root};
+use crate::prefs::read_agent_prefs;
+use crate::transcript::{codex_transcript, read_opencode_db_transcript, read_transcript};
+use crate::util::{collect_files, modified_ms, normalize_epoch_ms, parse_session_from_filename};
+use std::fs;
+
+const MAX_SEARCH_HITS: usize = 50;
+const MAX_SNIPPETS_PER_HIT: usize = 3;
+const SNIPPET_CONTEXT_BYTES: usize = 60;
+
+struct SearchCandidate {
+    source: &'static str,
+    session_id: String,
+    title: Option<String>,
+    repo_path: Option<String>,
+    last_ts_ms: Option<i64>,
+    entries: Vec<TranscriptEntry>,
+}
+
+pub fn search_sessions(query: &str) -> Result<Vec<SessionSearchHit>, Error> {
+    let needle = query.trim().to_ascii_lowercase();
+    if needle.is_empty() {
+        return Err(Error::Invalid("search query is empty".to_string()));
+    }
+    let prefs = read_agent_prefs();
+    let mut hits = Vec::new();
+    let mut visit = |mut candidate: SearchCandidate| {
+        let key = format!("{}:{}", candidate.source, candidate.session_id);
+        if let Some(name) = prefs.names.get(&key) {
+            candidate.title = Some(name.clone());
+        }
+        hits.extend(match_candidate(key, candidate, &needle));
+    };
+    visit_codex_sessions(&mut visit);
+    visit_opencode_sessions(&mut visit)?;
+    hits.sort_by(|a, b| b.last_ts_ms.cmp(&a.last_ts_ms));
+    hits.truncate(MAX_SEARCH_HITS);
+    Ok(hits)
+}
+
+fn visit_codex_sessions(visit: &mut dyn FnMut(SearchCandidate)) {
+    for file in collect_files(&codex_sessions_root(), "jsonl", usize::MAX) {
+        let Ok(raw) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let mut session_id = None;
+        let mut title = None;
+        let mut repo_path = None;
+        for line in raw
+            .lines()
+            .filter_map(|line| serde_json::from_str::<CodexLine>(line.trim()).ok())
+        {
+            session_id = session_id.or_else(|| line.session_id().map(str::to_string));
+            repo_path = repo_path.or_else(|| line.cwd().map(str::to_string));
+            title = title.or_else(|| line.agent_name());
+            if session_id.is_some() && repo_path.is_some() && title.is_some() {
+                break;
+            }
+        }
+        let Some(session_id) = session_id.or_else(|| parse_session_from_filename(&file)) else {
+            continue;
+        };
+        visit(SearchCandidate {
+            source: "codex",
+            session_id,
+            title,
+            repo_path,
+            last_ts_ms: Some(modified_ms(&file)),
+            entries: codex_transcript(&raw),
+        });
+    }
+}
+
+fn visit_opencode_sessions(visit: &mut dyn FnMut(SearchCandidate)) -> Result<(), Error> {
+    let db_path = opencode_db_file();
+    if db_path.exists() {
+        let db = open_opencode_db(&db_path)?;
+        let sessions: Vec<(String, Option<String>, Option<String>, i64)> = {
+            let mut stmt = db
+                .conn
+                .prepare("SELECT id, title, directory, time_updated FROM session")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?;
+            rows.flatten().collect()
+        };
+        for (session_id, title, repo_path, time_updated) in sessions {
+            let entries = read_opencode_db_transcript(&db.conn, &session_id)?;
+            visit(SearchCandidate {
+                source: "opencode",
+                session_id,
+                title,
+                repo_path,
+                last_ts_ms: Some(normalize_epoch_ms(time_updated)),
+                entries,
+            });
+        }
+        return Ok(());
+    }
+
+    for file in collect_files(&opencode_session_root(), "json", usize::MAX) {
+        let Some(session) = fs::read_to_string(&file)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<OpencodeSession>(&raw).ok())
+        else {
+            continue;
+        };
+        let Some(session_id) = session.id.or_else(|| {
+            file.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        }) else {
+            continue;
+        };
+        let entries = read_transcript("opencode", &session_id).unwrap_or_default();
+        visit(SearchCandidate {
+            source: "opencode",
+            session_id,
+            title: session.title,
+            repo_path: None,
+            last_ts_ms: Some(modified_ms(&file)),
+            entries,
+        });
+    }
+    Ok(())
+}
+
+fn match_candidate(
+    key: String,
+    candidate: SearchCandidate,
+    needle: &str,
+) -> Option<SessionSearchHit> {
+    let mut matches = 0;
+    let mut snippets = Vec::new();
+    let texts = candidate
+        .title
+        .iter()
+        .map(String::as_str)
+        .chain(candidate.entries.iter().map(|entry| entry.text.as_str()));
+    for text in texts {
+        let lower = text.to_ascii_lowercase();
+        for (index, _) in lower.match_indices(needle) {
+            matches += 1;
+            if snippets.len() < MAX_SNIPPETS_PER_HIT {
+                snippets.push(snippet(text, index, needle.len()));
+            }
+        }
+    }
+    (matches > 0).then(|| SessionSearchHit {
+        key,
+        source: candidate.source.to_string(),
+        session_id: candidate.session_id,
+        title: candidate.title,
+        repo_path: candidate.repo_path,
+        last_ts_ms: candidate.last_ts_ms,
+        matches,
+        snippets,
+    })
+}
+
+fn snippet(text: &str, start: usize, len: usize) -> String {
+    let mut from = start.saturating_sub(SNIPPET_CONTEXT_BYTES);
+    while !text.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (start + len + SNIPPET_CONTEXT_BYTES).min(text.len());
+    while !text.is_char_boundary(to) {
+        to += 1;
+    }
+    let body = text[from..to]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "{}{}{}",
+        if from > 0 { "..." } else { "" },
+        body,
+        if to < text.len() { "..." } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str) -> TranscriptEntry {
+        TranscriptEntry {
+            ts_ms: None,
+            role: "assistant".to_string(),
+            text: text.to_string(),
+            tool: None,
+        }
+    }
+
+    #[test]
+    fn matches_titles_and_entries_case_insensitively() {
+        let candidate = SearchCandidate {
+            source: "codex",
+            session_id: "s1".to_string(),
+            title: Some("Billing fixes".to_string()),
+            repo_path: None,
+            last_ts_ms: Some(1),
+            entries: vec![
+                entry("Edited services/billing/mod.rs"),
+                entry("nothing here"),
+            ],
+        };
+        let hit = match_candidate("codex:s1".to_string(), candidate, "billing").unwrap();
+        assert_eq!(hit.matches, 2);
+        assert_eq!(
+            hit.snippets,
+            vec!["Billing fixes", "Edited services/billing/mod.rs"]
+        );
+    }
+
+    #[test]
+    fn snippet_trims_long_text_on_char_boundaries() {
+        let text = format!("{}needle{}", "é".repeat(80), "\nx".repeat(80));
+        let start = text.find("needle").unwrap();
+        let out = snippet(&text, start, "needle".len());
+        assert!(out.starts_with("...é"));
+        assert!(out.ends_with("..."));
+        assert!(out.contains("needle x x"));
+    }
+}
@@ -0,0 +1,943 @@
+use crate::error::Error;
+use crate::model::{DataDirView, MonitorAgentView, RepoBindingView};
+use crate::paths::{
+    configured_data_dir, data_dir_config_file, data_dir_env, default_pixel_agents_dir,
+    monitor_settings_file, pixel_agents_dir, repo_bindings_file, sound_settings_file,
+};
+use crate::util::now_ms;
+use crate::{migrate, store};
+use chrono::{Local, TimeZone};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub(crate) const CODEX_TAIL_BYTES: usize = 65_536;
+pub(crate) const MAX_CODEX_FILES: usize = 120;
+pub(crate) const MAX_OPENCODE_FILES: usize = 800;
+pub(crate) const MAX_OPENCODE_PART_FILES: usize = 900;
+pub(crate) const MAX_OPENCODE_DB_SESSIONS: usize = 800;
+pub(crate) const MAX_OPENCODE_DB_PARTS: usize = 1500;
+pub(crate) const MAX_RECENT_EVENTS: usize = 20;
+pub(crate) const MAX_TOTAL_RECENT_EVENTS: usize = 2_000;
+pub(crate) const MIN_CODEX_TAIL_BYTES: usize = 4_096;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorSettings {
+    pub enabled: bool,
+    #[serde(rename = "enableClaude", default = "default_enable_claude")]
+    pub enable_claude: bool,
+    #[serde(rename = "enableOpencode")]
+    pub enable_opencode: bool,
+    #[serde(rename = "enableCodex")]
+    pub enable_codex: bool,
+    #[serde(rename = "enableWindsurf", default = "default_enable_windsurf")]
+    pub enable_windsurf: bool,
+    #[serde(rename = "enableGit")]
+    pub enable_git: bool,
+    #[serde(rename = "enablePr")]
+    pub enable_pr: bool,
+    #[serde(rename = "flushIntervalMs")]
+    pub flush_interval_ms: i64,
+    #[serde(rename = "sourcePollIntervalMs")]
+    pub source_poll_interval_ms: i64,
+    #[serde(rename = "gitPollIntervalMs")]
+    pub git_poll_interval_ms: i64,
+    #[serde(rename = "prPollIntervalMs")]
+    pub pr_poll_interval_ms: i64,
+    #[serde(rename = "agentLabelFontPx", default = "default_agent_label_font_px")]
+    pub agent_label_font_px: i64,
+    #[serde(rename = "maxIdleAgents", default = "default_max_idle_agents")]
+    pub max_idle_agents: i64,
+    #[serde(rename = "branchTemplate", default = "default_branch_template")]
+    pub branch_template: String,
+    #[serde(
+        rename = "notificationCooldownMs",
+        default = "default_notification_cooldown_ms"
+    )]
+    pub notification_cooldown_ms: i64,
+    #[serde(rename = "notifyWaiting", default = "default_notify_waiting")]
+    pub notify_waiting: bool,
+    /// Busy agents silent this long with a live process get a "stuck" alert; 0 disables.
+    #[serde(rename = "stuckAfterMs", default = "default_stuck_after_ms")]
+    pub stuck_after_ms: i64,
+    #[serde(
+        rename = "maxCodexFiles",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_codex_files: Option<usize>,
+    #[serde(
+        rename = "codexTailBytes",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub codex_tail_bytes: Option<usize>,
+    #[serde(
+        rename = "maxOpencodeFiles",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_opencode_files: Option<usize>,
+    #[serde(
+        rename = "maxOpencodePartFiles",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_opencode_part_files: Option<usize>,
+    #[serde(
+        rename = "maxOpencodeDbSessions",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_opencode_db_sessions: Option<usize>,
+    #[serde(
+        rename = "maxOpencodeDbParts",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_opencode_db_parts: Option<usize>,
+    #[serde(
+        rename = "maxRecentEvents",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_recent_events: Option<usize>,
+    #[serde(
+        rename = "maxTotalRecentEvents",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_total_recent_events: Option<usize>,
+    #[serde(
+        rename = "agentFilter",
+        default,
+        skip_serializing_if = "AgentFilter::is_empty"
+    )]
+    pub agent_filter: AgentFilter,
+    #[serde(
+        rename = "errorPatterns",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub error_patterns: HashMap<String, ErrorPatterns>,
+    /// Notification switches per source; unlisted sources notify.
+    #[serde(
+        rename = "notifySources",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub notify_sources: HashMap<String, bool>,
+    /// Notification switches per repo path prefix; the longest match wins and
+    /// overrides the source switch.
+    #[serde(
+        rename = "notifyRepos",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub notify_repos: HashMap<String, bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentFilter {
+    #[serde(
+        rename = "includeSources",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub include_sources: Vec<String>,
+    #[serde(
+        rename = "excludeSources",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub exclude_sources: Vec<String>,
+    #[serde(
+        rename = "includeRepoPrefixes",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub include_repo_prefixes: Vec<String>,
+    #[serde(
+        rename = "excludeRepoPrefixes",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub exclude_repo_prefixes: Vec<String>,
+    #[serde(
+        rename = "includeStates",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub include_states: Vec<String>,
+    #[serde(
+        rename = "excludeStates",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub exclude_states: Vec<String>,
+    #[serde(rename = "maxAgeMs", default, skip_serializing_if = "Option::is_none")]
+    pub max_age_ms: Option<i64>,
+}
+
+impl AgentFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn matches(&self, agent: &MonitorAgentView, now: i64) -> bool {
+        let listed =
+            |list: &[String], value: &str| list.iter().any(|item| item.eq_ignore_ascii_case(value));
+        let under = |prefixes: &[String]| {
+            agent.repo_path.as_deref().is_some_and(|repo| {
+                prefixes
+                    .iter()
+                    .any(|prefix| Path::new(repo).starts_with(prefix))
+            })
+        };
+        (self.include_sources.is_empty() || listed(&self.include_sources, &agent.source))
+            && !listed(&self.exclude_sources, &agent.source)
+            && (self.include_states.is_empty() || listed(&self.include_states, &agent.state))
+            && !listed(&self.exclude_states, &agent.state)
+            && (self.include_repo_prefixes.is_empty() || under(&self.include_repo_prefixes))
+            && !under(&self.exclude_repo_prefixes)
+            && self
+                .max_age_ms
+                .is_none_or(|max_age| now - agent.last_ts_ms <= max_age)
+    }
+}
+
+/// Regexes for one source that override the scanners' error classification;
+/// `exclude` clears an error whose text matches, `include` raises one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ErrorPatterns {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ErrorClassifier {
+    rules: HashMap<String, (Vec<Regex>, Vec<Regex>)>,
+}
+
+impl ErrorClassifier {
+    /// The state `text` should have instead of `state`, if a pattern applies.
+    pub(crate) fn reclassify(&self, source: &str, state: &str, text: &str) -> Option<&'static str> {
+        let (include, exclude) = self.rules.get(source)?;
+        let patterns = match state {
+            "error" => exclude,
+            // Finished or waiting agents keep their state even if an old message matches.
+            "running" | "thinking" => include,
+            _ => return None,
+        };
+        patterns
+            .iter()
+            .any(|re| re.is_match(text))
+            .then_some(if state == "error" { "running" } else { "error" })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Hotkeys {
+    pub toggle_window: String,
+    pub toggle_pip: String,
+    pub snooze_notifications: String,
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Self {
+            toggle_window: "CommandOrControl+Shift+Alt+P".to_string(),
+            toggle_pip: "CommandOrControl+Shift+Alt+I".to_string(),
+            snooze_notifications: "CommandOrControl+Shift+Alt+S".to_string(),
+        }
+    }
+}
+
+impl Hotkeys {
+    /// `(action, shortcut)` pairs; an empty shortcut leaves the action unbound.
+    pub fn bindings(&self) -> [(&'static str, &str); 3] {
+        [
+            ("toggleWindow", self.toggle_window.trim()),
+            ("togglePip", self.toggle_pip.trim()),
+            ("snoozeNotifications", self.snooze_notifications.trim()),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PipCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl PipCorner {
+    /// Top-left position for a `width` x `height` window in this corner of
+    /// `area` (`[x, y, width, height]`), inset by `margin`.
+    pub fn place(self, area: [f64; 4], width: f64, height: f64, margin: f64) -> (f64, f64) {
+        let [x, y, area_width, area_height] = area;
+        let left = x + margin;
+        let top = y + margin;
+        let right = (x + area_width - width - margin).max(left);
+        let bottom = (y + area_height - height - margin).max(top);
+        match self {
+            PipCorner::TopLeft => (left, top),
+            PipCorner::TopRight => (right, top),
+            PipCorner::BottomLeft => (left, bottom),
+            PipCorner::BottomRight => (right, bottom),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuietMode {
+    /// Notifications still show but play no sound.
+    #[default]
+    Silent,
+    /// Notifications are dropped entirely.
+    Suppress,
+}
+
+/// Do-not-disturb schedule; `start`/`end` are local `HH:MM` times and the
+/// window wraps past midnight when `end` is earlier than `start`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct QuietHours {
+    pub enabled: bool,
+    pub start: String,
+    pub end: String,
+    pub weekends: bool,
+    pub mode: QuietMode,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "08:00".to_string(),
+            weekends: false,
+            mode: QuietMode::Silent,
+        }
+    }
+}
+
+impl QuietHours {
+    /// Whether `local_ms` (epoch milliseconds shifted to local time) is quiet.
+    pub fn active_at(&self, local_ms: i64) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let minutes = local_ms.div_euclid(60_000);
+        let day = minutes.div_euclid(1440);
+        let minute = minutes.rem_euclid(1440);
+        // 1970-01-01 was a Thursday, so Monday is day index 0 after this shift.
+        if self.weekends && (day + 3).rem_euclid(7) >= 5 {
+            return true;
+        }
+        let (Some(start), Some(end)) = (parse_clock(&self.start), parse_clock(&self.end)) else {
+            return false;
+        };
+        if start <= end {
+            start <= minute && minute < end
+        } else {
+            minute >= start || minute < end
+        }
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        for time in [&self.start, &self.end] {
+            if parse_clock(time).is_none() {
+                return Err(Error::Invalid(format!(
+                    "invalid time {:?}, expected HH:MM",
+                    time
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_clock(text: &str) -> Option<i64> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    ((0..24).contains(&hours) && (0..60).contains(&minutes)).then_some(hours * 60 + minutes)
+}
+
+/// Last picture-in-picture window position and size, in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PipGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanLimits {
+    pub codex_files: usize,
+    pub codex_tail_bytes: usize,
+    pub opencode_files: usize,
+    pub opencode_part_files: usize,
+    pub opencode_db_sessions: usize,
+    pub opencode_db_parts: usize,
+    pub recent_events: usize,
+    pub total_recent_events: usize,
+}
+
+impl MonitorSettings {
+    pub fn source_enabled(&self, source: &str) -> bool {
+        match source {
+            "claude" => self.enable_claude,
+            "opencode" => self.enable_opencode,
+            "codex" => self.enable_codex,
+            "windsurf" => self.enable_windsurf,
+            _ => false,
+        }
+    }
+
+    pub fn notifications_enabled(&self, source: &str, repo_path: Option<&str>) -> bool {
+        let repo = repo_path.and_then(|repo| {
+            self.notify_repos
+                .iter()
+                .filter(|(prefix, _)| Path::new(repo).starts_with(prefix))
+                .max_by_key(|(prefix, _)| prefix.len())
+                .map(|(_, enabled)| *enabled)
+        });
+        repo.or_else(|| {
+            self.notify_sources
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(source))
+                .map(|(_, enabled)| *enabled)
+        })
+        .unwrap_or(true)
+    }
+
+    pub(crate) fn error_classifier(&self) -> Result<ErrorClassifier, Error> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    Regex::new(pattern).map_err(|e| {
+                        Error::Invalid(format!("invalid error pattern {:?}: {}", pattern, e))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let mut rules = HashMap::new();
+        for (source, patterns) in &self.error_patterns {
+            rules.insert(
+                source.to_lowercase(),
+                (compile(&patterns.include)?, compile(&patterns.exclude)?),
+            );
+        }
+        Ok(ErrorClassifier { rules })
+    }
+
+    pub fn scan_limits(&self) -> ScanLimits {
+        ScanLimits {
+            codex_files: self.max_codex_files.unwrap_or(MAX_CODEX_FILES).max(1),
+            codex_tail_bytes: self
+                .codex_tail_bytes
+                .unwrap_or(CODEX_TAIL_BYTES)
+                .max(MIN_CODEX_TAIL_BYTES),
+            opencode_files: self.max_opencode_files.unwrap_or(MAX_OPENCODE_FILES).max(1),
+            opencode_part_files: self
+                .max_opencode_part_files
+                .unwrap_or(MAX_OPENCODE_PART_FILES)
+                .max(1),
+            opencode_db_sessions: self
+                .max_opencode_db_sessions
+                .unwrap_or(MAX_OPENCODE_DB_SESSIONS)
+                .max(1),
+            opencode_db_parts: self
+                .max_opencode_db_parts
+                .unwrap_or(MAX_OPENCODE_DB_PARTS)
+                .max(1),
+            recent_events: self.max_recent_events.unwrap_or(MAX_RECENT_EVENTS).max(1),
+            total_recent_events: self
+                .max_total_recent_events
+                .unwrap_or(MAX_TOTAL_RECENT_EVENTS),
+        }
+    }
+}
+
+impl Default for MonitorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            enable_claude: default_enable_claude(),
+            enable_opencode: true,
+            enable_codex: true,
+            enable_windsurf: default_enable_windsurf(),
+            enable_git: true,
+            enable_pr: true,
+            flush_interval_ms: 1000,
+            source_poll_interval_ms: 2000,
+            git_poll_interval_ms: 20000,
+            pr_poll_interval_ms: 90000,
+            agent_label_font_px: default_agent_label_font_px(),
+            max_idle_agents: default_max_idle_agents(),
+            branch_template: default_branch_template(),
+            notification_cooldown_ms: default_notification_cooldown_ms(),
+            notify_waiting: default_notify_waiting(),
+            stuck_after_ms: default_stuck_after_ms(),
+            max_codex_files: None,
+            codex_tail_bytes: None,
+            max_opencode_files: None,
+            max_opencode_part_files: None,
+            max_opencode_db_sessions: None,
+            max_opencode_db_parts: None,
+            max_recent_events: None,
+            max_total_recent_events: None,
+            agent_filter: AgentFilter::default(),
+            error_patterns: HashMap::new(),
+            notify_sources: HashMap::new(),
+            notify_repos: HashMap::new(),
+        }
+    }
+}
+
+pub(crate) fn default_enable_claude() -> bool {
+    true
+}
+
+pub(crate) fn default_enable_windsurf() -> bool {
+    true
+}
+
+pub(crate) fn default_agent_label_font_px() -> i64 {
+    24
+}
+
+pub(crate) fn default_max_idle_agents() -> i64 {
+    3
+}
+
+pub(crate) fn default_branch_template() -> String {
+    "agent/{source}/{short_session}".to_string()
+}
+
+pub(crate) fn default_notification_cooldown_ms() -> i64 {
+    120_000
+}
+
+pub(crate) fn default_notify_waiting() -> bool {
+    true
+}
+
+pub(crate) fn default_stuck_after_ms() -> i64 {
+    600_000
+}
+
+pub fn read_monitor_settings() -> MonitorSettings {
+    match read_versioned_file(&monitor_settings_file(), &migrate::MONITOR_SETTINGS) {
+        Ok(value) => serde_json::from_value(value).unwrap_or_default(),
+        Err(_) => MonitorSettings::default(),
+    }
+}
+
+pub async fn read_monitor_settings_async() -> MonitorSettings {
+    match read_versioned_file_async(&monitor_settings_file(), &migrate::MONITOR_SETTINGS).await {
+        Ok(value) => serde_json::from_value(value).unwrap_or_default(),
+        Err(_) => MonitorSettings::default(),
+    }
+}
+
+pub fn read_sound_enabled() -> bool {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| v.get("soundEnabled").and_then(Value::as_bool))
+        .unwrap_or(true)
+}
+
+pub fn read_demo_mode() -> bool {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| v.get("demoMode").and_then(Value::as_bool))
+        .unwrap_or(false)
+}
+
+pub fn read_notifications_snoozed_until() -> i64 {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| v.get("notificationsSnoozedUntil").and_then(Value::as_i64))
+        .unwrap_or(0)
+}
+
+pub fn read_editor_command() -> String {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| {
+            v.get("editorCommand")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|command| !command.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "code".to_string())
+}
+
+pub fn read_auto_check_updates() -> bool {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| v.get("autoCheckUpdates").and_then(Value::as_bool))
+        .unwrap_or(false)
+}
+
+pub fn read_close_to_tray() -> bool {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| v.get("closeToTray").and_then(Value::as_bool))
+        .unwrap_or(false)
+}
+
+pub fn read_last_update_check_ms() -> i64 {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| v.get("lastUpdateCheckMs").and_then(Value::as_i64))
+        .unwrap_or(0)
+}
+
+pub fn read_quiet_hours() -> QuietHours {
+    read_desktop_settings()
+        .ok()
+        .and_then(|mut v| v.get_mut("quietHours").map(Value::take))
+        .and_then(|quiet| serde_json::from_value(quiet).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_quiet_hours(quiet: &QuietHours) -> Result<(), Error> {
+    quiet.validate()?;
+    write_desktop_setting("quietHours", serde_json::to_value(quiet)?)
+}
+
+/// The quiet-hours mode in effect right now, if any. The local offset is
+/// looked up on every call so DST changes apply without a restart.
+pub fn quiet_mode_now() -> Option<QuietMode> {
+    let quiet = read_quiet_hours();
+    let now = now_ms();
+    let offset_ms = Local.timestamp_millis_opt(now).single().map_or(0, |local| {
+        i64::from(local.offset().local_minus_utc()) * 1_000
+    });
+    quiet.active_at(now + offset_ms).then_some(quiet.mode)
+}
+
+pub fn read_hotkeys() -> Hotkeys {
+    read_desktop_settings()
+        .ok()
+        .and_then(|mut v| v.get_mut("hotkeys").map(Value::take))
+        .and_then(|hotkeys| serde_json::from_value(hotkeys).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_hotkeys(hotkeys: &Hotkeys) -> Result<(), Error> {
+    write_desktop_setting("hotkeys", serde_json::to_value(hotkeys)?)
+}
+
+pub fn read_pip_geometry() -> Option<PipGeometry> {
+    read_desktop_settings()
+        .ok()
+        .and_then(|mut v| v.get_mut("pipGeometry").map(Value::take))
+        .and_then(|geometry| serde_json::from_value(geometry).ok())
+}
+
+pub fn write_pip_geometry(geometry: &PipGeometry) -> Result<(), Error> {
+    write_desktop_setting("pipGeometry", serde_json::to_value(geometry)?)
+}
+
+pub fn read_data_dir() -> DataDirView {
+    let display = |path: PathBuf| path.to_string_lossy().into_owned();
+    DataDirView {
+        current: display(pixel_agents_dir()),
+        configured: configured_data_dir().map(display),
+        env_override: data_dir_env().map(display),
+        default_dir: display(default_pixel_agents_dir()),
+    }
+}
+
+/// Points the data directory at `dir`, or back to the default with `None`.
+/// Takes effect on the next launch; existing files are not moved.
+pub fn write_data_dir(dir: Option<&str>) -> Result<(), Error> {
+    let config = data_dir_config_file();
+    let Some(dir) = dir.map(str::trim).filter(|dir| !dir.is_empty()) else {
+        return match std::fs::remove_file(&config) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    };
+    let path = Path::new(dir);
+    if !path.is_absolute() {
+        return Err(Error::Invalid(format!(
+            "data directory must be an absolute path: {}",
+            dir
+        )));
+    }
+    std::fs::create_dir_all(path)?;
+    let text = serde_json::to_string_pretty(&json!({ "dataDir": dir }))?;
+    store::write_atomic(&config, text.as_bytes())
+}
+
+pub fn read_github_token() -> Option<String> {
+    read_desktop_settings()
+        .ok()
+        .and_then(|v| {
+            v.get("githubToken")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+pub fn read_desktop_settings() -> Result<Value, Error> {
+    read_versioned_file(&sound_settings_file(), &migrate::DESKTOP_SETTINGS)
+}
+
+pub fn write_desktop_setting_bool(key: &str, value: bool) -> Result<(), Error> {
+    write_desktop_setting(key, Value::Bool(value))
+}
+
+pub fn write_desktop_setting(key: &str, value: Value) -> Result<(), Error> {
+    let path = sound_settings_file();
+    store::with_lock(&path, || {
+        let mut settings = match read_desktop_settings() {
+            Ok(existing) => existing,
+            Err(_) => json!({}),
+        };
+        if !settings.is_object() {
+            settings = json!({});
+        }
+        if let Some(map) = settings.as_object_mut() {
+            if value.is_null() {
+                map.remove(key);
+            } else {
+                map.insert(key.to_string(), value);
+            }
+        }
+        write_versioned_file(&path, &migrate::DESKTOP_SETTINGS, settings)
+    })
+}
+
+pub fn write_repo_binding(key: &str, repo_path: &str) -> Result<(), Error> {
+    if !Path::new(repo_path).is_dir() {
+        return Err(Error::Invalid(format!("{} is not a directory", repo_path)));
+    }
+    update_repo_bindings(|bindings| {
+        bindings.insert(key.to_string(), repo_path.to_string());
+    })
+}
+
+pub fn remove_repo_binding(key: &str) -> Result<bool, Error> {
+    let mut removed = false;
+    update_repo_bindings(|bindings| removed = bindings.remove(key).is_some())?;
+    Ok(removed)
+}
+
+fn update_repo_bindings(update: impl FnOnce(&mut HashMap<String, String>)) -> Result<(), Error> {
+    let path = repo_bindings_file();
+    store::with_lock(&path, || {
+        let mut bindings = read_repo_bindings();
+        update(&mut bindings);
+        write_versioned_file(
+            &path,
+            &migrate::REPO_BINDINGS,
+            json!({ "bindings": bindings }),
+        )
+    })
+}
+
+pub fn list_repo_bindings() -> Vec<RepoBindingView> {
+    let mut bindings: Vec<RepoBindingView> = read_repo_bindings()
+        .into_iter()
+        .map(|(key, repo_path)| {
+            let (source, session_id) = key.split_once(':').unwrap_or(("", key.as_str()));
+            RepoBindingView {
+                source: source.to_string(),
+                session_id: session_id.to_string(),
+                exists: Path::new(&repo_path).is_dir(),
+                key: key.clone(),
+                repo_path,
+            }
+        })
+        .collect();
+    bindings.sort_by(|a, b| a.key.cmp(&b.key));
+    bindings
+}
+
+pub async fn read_repo_bindings_async() -> HashMap<String, String> {
+    match read_versioned_file_async(&repo_bindings_file(), &migrate::REPO_BINDINGS).await {
+        Ok(mut value) => value
+            .get_mut("bindings")
+            .map(|bindings| serde_json::from_value(bindings.take()).unwrap_or_default())
+            .unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+pub fn read_repo_bindings() -> HashMap<String, String> {
+    match read_versioned_file(&repo_bindings_file(), &migrate::REPO_BINDINGS) {
+        Ok(mut value) => value
+            .get_mut("bindings")
+            .map(|bindings| serde_json::from_value(bindings.take()).unwrap_or_default())
+            .unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+pub(crate) fn read_versioned_file(path: &Path, schema: &migrate::Schema) -> Result<Value, Error> {
+    let (value, migrated) = schema.migrate(read_json_file(path)?);
+    if migrated {
+        if let Err(e) = write_json_file(path, &value) {
+            tracing::warn!(path = %path.display(), error = %e, "failed to persist migrated file");
+        }
+    }
+    Ok(value)
+}
+
+pub(crate) async fn read_versioned_file_async(
+    path: &Path,
+    schema: &migrate::Schema,
+) -> Result<Value, Error> {
+    Ok(schema.migrate(read_json_file_async(path).await?).0)
+}
+
+pub fn write_monitor_settings(settings: &MonitorSettings) -> Result<(), Error> {
+    settings.error_classifier()?;
+    write_versioned_file(
+        &monitor_settings_file(),
+        &migrate::MONITOR_SETTINGS,
+        serde_json::to_value(settings)?,
+    )
+}
+
+pub(crate) fn write_versioned_file(
+    path: &Path,
+    schema: &migrate::Schema,
+    mut value: Value,
+) -> Result<(), Error> {
+    schema.stamp(&mut value);
+    write_json_file(path, &value)
+}
+
+pub async fn read_json_file_async(path: &Path) -> Result<Value, Error> {
+    let raw = tokio::fs::read_to_string(path).await?;
+    match serde_json::from_str(&raw) {
+        Ok(value) => Ok(value),
+        Err(_) => store::read_json(path),
+    }
+}
+
+pub fn read_json_file(path: &Path) -> Result<Value, Error> {
+    store::read_json(path)
+}
+
+pub fn write_json_file(path: &Path, value: &Value) -> Result<(), Error> {
+    store::write_json(path, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_patterns_override_classification_per_source() {
+        let mut settings = MonitorSettings::default();
+        settings.error_patterns.insert(
+            "opencode".to_string(),
+            ErrorPatterns {
+                include: vec!["(?i)panicked at".to_string()],
+                exclude: vec!["^bash: ".to_string()],
+            },
+        );
+        let classifier = settings.error_classifier().unwrap();
+        assert_eq!(
+            classifier.reclassify("opencode", "error", "bash: error"),
+            Some("running")
+        );
+        assert_eq!(
+            classifier.reclassify("opencode", "error", "edit: error"),
+            None
+        );
+        assert_eq!(
+            classifier.reclassify("opencode", "running", "thread Panicked at main.rs"),
+            Some("error")
+        );
+        assert_eq!(
+            classifier.reclassify("opencode", "done", "thread panicked at main.rs"),
+            None
+        );
+        assert_eq!(classifier.reclassify("codex", "error", "bash: error"), None);
+        settings.error_patterns.get_mut("opencode").unwrap().include = vec!["(".to_string()];
+        assert!(settings.error_classifier().is_err());
+    }
+
+    #[test]
+    fn repo_notification_toggles_override_sources() {
+        let mut settings = MonitorSettings::default();
+        assert!(settings.notifications_enabled("codex", None));
+        settings.notify_sources.insert("codex".to_string(), false);
+        settings
+            .notify_repos
+            .insert("/work/experiments".to_string(), false);
+        settings
+            .notify_repos
+            .insert("/work/experiments/prod-api".to_string(), true);
+        assert!(!settings.notifications_enabled("codex", Some("/work/app")));
+        assert!(settings.notifications_enabled("claude", Some("/work/app")));
+        assert!(!settings.notifications_enabled("claude", Some("/work/experiments/toy")));
+        assert!(settings.notifications_enabled("codex", Some("/work/experiments/prod-api")));
+        assert!(settings.notifications_enabled("claude", Some("/work/experiments-old")));
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight_and_cover_weekends() {
+        const HOUR: i64 = 3_600_000;
+        // 1970-01-05 was a Monday.
+        let monday = 4 * 24 * HOUR;
+        let mut quiet = QuietHours {
+            enabled: true,
+            ..QuietHours::default()
+        };
+        assert!(quiet.active_at(monday + 23 * HOUR));
+        assert!(quiet.active_at(monday + 7 * HOUR));
+        assert!(!quiet.active_at(monday + 12 * HOUR));
+        let saturday_noon = monday + 5 * 24 * HOUR + 12 * HOUR;
+        assert!(!quiet.active_at(saturday_noon));
+        quiet.weekends = true;
+        assert!(quiet.active_at(saturday_noon));
+        quiet.start = "9:00".to_string();
+        quiet.end = "17:30".to_string();
+        assert!(quiet.active_at(monday + 12 * HOUR));
+        assert!(!quiet.active_at(monday + 18 * HOUR));
+        quiet.end = "25:00".to_string();
+        assert!(quiet.validate().is_err());
+    }
+
+    #[test]
+    fn pip_corner_places_inside_area() {
+        let area = [100.0, 0.0, 1000.0, 800.0];
+        assert_eq!(
+            PipCorner::TopLeft.place(area, 300.0, 200.0, 10.0),
+            (110.0, 10.0)
+        );
+        assert_eq!(
+            PipCorner::BottomRight.place(area, 300.0, 200.0, 10.0),
+            (790.0, 590.0)
+        );
+        assert_eq!(
+            PipCorner::BottomRight.place(area, 2000.0, 200.0, 10.0),
+            (110.0, 590.0)
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::Error;
+use crate::model::{MonitorAgentView, MonitorSnapshot, MonitorSummary};
+use serde::Deserialize;
+use std::path::Path;
+
+const MAX_SNAPSHOT_TEXT_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotFormat {
+    #[serde(alias = "md")]
+    Markdown,
+    Json,
+}
+
+pub fn render_snapshot(
+    snapshot: &MonitorSnapshot,
+    format: SnapshotFormat,
+) -> Result<String, Error> {
+    match format {
+        SnapshotFormat::Json => Ok(serde_json::to_string_pretty(snapshot)?),
+        SnapshotFormat::Markdown => Ok(render_markdown(snapshot)),
+    }
+}
+
+/// One-line standup summary, e.g. "3 agents running, 2 waiting, 1 error".
+pub(crate) fn summary_line(summary: &MonitorSummary) -> String {
+    let parts: Vec<String> = [
+        (summary.active, "running"),
+        (summary.waiting, "waiting"),
+        (summary.error, "error"),
+        (summary.limited, "rate limited"),
+        (summary.done, "done"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .enumerate()
+    .map(|(index, (count, label))| match (index, count) {
+        (0, 1) => format!("1 agent {}", label),
+        (0, _) => format!("{} agents {}", count, label),
+        _ => format!("{} {}", count, label),
+    })
+    .collect();
+    if parts.is_empty() {
+        return "No agents".to_string();
+    }
+    parts.join(", ")
+}
+
+fn render_markdown(snapshot: &MonitorSnapshot) -> String {
+    let summary = &snapshot.summary;
+    let mut out = format!("**{}**", summary_line(summary));
+    if summary.pr_pending > 0 || summary.alerts > 0 {
+        out.push_str(&format!(
+            " ({} PRs pending, {} alerts)",
+            summary.pr_pending, summary.alerts
+        ));
+    }
+    out.push('\n');
+    if !snapshot.agents.is_empty() {
+        out.push('\n');
+    }
+    for agent in &snapshot.agents {
+        out.push_str(&agent_line(agent));
+        out.push('\n');
+    }
+    out
+}
+
+fn agent_line(agent: &MonitorAgentView) -> String {
+    let mut line = format!(
+        "- **{}** `{}` ({})",
+        agent.display_name, agent.state, agent.source
+    );
+    if let Some(repo) = agent.repo_path.as_deref() {
+        let name = Path::new(repo)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| repo.to_string());
+        line.push_str(&format!(" - {}", name));
+        if let Some(branch) = agent.branch.as_deref() {
+            line.push_str(&format!(" @ {}", branch));
+        }
+    }
+    if let Some(text) = agent.last_text.as_deref().map(str::trim) {
+        if !text.is_empty() {
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let short: String = text.chars().take(MAX_SNAPSHOT_TEXT_CHARS).collect();
+            let ellipsis = if short.len() < text.len() { "..." } else { "" };
+            line.push_str(&format!(": {}{}", short, ellipsis));
+        }
+    }
+    for alert in &agent.alerts {
+        line.push_str(&format!(" [{}]", alert.kind));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(active: usize, waiting: usize, error: usize) -> MonitorSummary {
+        MonitorSummary {
+            total: active + waiting + error,
+            active,
+            waiting,
+            done: 0,
+            error,
+            limited: 0,
+            pr_pending: 0,
+            alerts: 0,
+        }
+    }
+
+    #[test]
+    fn summary_line_reads_like_a_standup() {
+        assert_eq!(
+            summary_line(&summary(3, 2, 1)),
+            "3 agents running, 2 waiting, 1 error"
+        );
+        assert_eq!(summary_line(&summary(0, 1, 0)), "1 agent waiting");
+        assert_eq!(summary_line(&summary(0, 0, 0)), "No agents");
+    }
+}
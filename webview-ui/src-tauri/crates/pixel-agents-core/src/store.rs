@@ -0,0 +1,194 @@
+use crate::error::Error;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+const MAX_BACKUPS_PER_FILE: usize = 5;
+const BACKUP_SUFFIX: &str = ".bak";
+
+thread_local! {
+    static HELD_LOCKS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+struct FileLock {
+    path: PathBuf,
+    file: fs::File,
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+        HELD_LOCKS.with(|held| held.borrow_mut().retain(|p| p != &self.path));
+    }
+}
+
+pub(crate) fn backups_dir() -> PathBuf {
+    crate::paths::pixel_agents_dir().join("backups")
+}
+
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)?;
+    let file_name = file_name(path)?;
+    let tmp_path = parent.join(format!(".{}.tmp", file_name));
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    Ok(result?)
+}
+
+pub(crate) fn with_lock<T>(path: &Path, f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    if HELD_LOCKS.with(|held| held.borrow().iter().any(|p| p == path)) {
+        return f();
+    }
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)?;
+    let lock_path = parent.join(format!(".{}.lock", file_name(path)?));
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)?;
+    file.lock()?;
+    HELD_LOCKS.with(|held| held.borrow_mut().push(path.to_path_buf()));
+    let _lock = FileLock {
+        path: path.to_path_buf(),
+        file,
+    };
+    f()
+}
+
+pub(crate) fn write_json(path: &Path, value: &Value) -> Result<(), Error> {
+    write_json_keeping(path, value, MAX_BACKUPS_PER_FILE)
+}
+
+pub(crate) fn write_json_keeping(path: &Path, value: &Value, keep: usize) -> Result<(), Error> {
+    let text = serde_json::to_string_pretty(value)?;
+    with_lock(path, || {
+        backup(path, keep);
+        write_atomic(path, text.as_bytes())
+    })
+}
+
+/// Backups of `path`, newest first, as `(timestamp ms, backup path)`.
+pub(crate) fn list_backups(path: &Path) -> Vec<(i64, PathBuf)> {
+    let Ok(name) = backup_name(path) else {
+        return Vec::new();
+    };
+    let mut backups = backups_for(&name);
+    backups.reverse();
+    backups
+}
+
+pub(crate) fn read_json(path: &Path) -> Result<Value, Error> {
+    let error = match fs::read_to_string(path) {
+        Ok(raw) => match serde_json::from_str(&raw) {
+            Ok(value) => return Ok(value),
+            Err(e) => Error::from(e),
+        },
+        Err(e) if e.kind() == ErrorKind::NotFound => return Err(e.into()),
+        Err(e) => Error::from(e),
+    };
+    match recover(path) {
+        Some(value) => Ok(value),
+        None => Err(error),
+    }
+}
+
+fn recover(path: &Path) -> Option<Value> {
+    let name = backup_name(path).ok()?;
+    for (_, backup) in backups_for(&name).iter().rev() {
+        let Some(value) = fs::read_to_string(backup)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+        else {
+            continue;
+        };
+        tracing::warn!(
+            path = %path.display(),
+            backup = %backup.display(),
+            "recovered corrupt file from backup"
+        );
+        if let Ok(text) = serde_json::to_string_pretty(&value) {
+            let _ = with_lock(path, || write_atomic(path, text.as_bytes()));
+        }
+        return Some(value);
+    }
+    None
+}
+
+fn backup(path: &Path, keep: usize) {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return;
+    };
+    if serde_json::from_str::<Value>(&raw).is_err() {
+        return;
+    }
+    let Ok(name) = backup_name(path) else {
+        return;
+    };
+    let dir = backups_dir();
+    let backup_path = dir.join(format!(
+        "{}.{}{}",
+        name,
+        crate::util::now_ms(),
+        BACKUP_SUFFIX
+    ));
+    if let Err(e) = write_atomic(&backup_path, raw.as_bytes()) {
+        tracing::warn!(path = %path.display(), error = %e, "backup failed");
+        return;
+    }
+    let backups = backups_for(&name);
+    let excess = backups.len().saturating_sub(keep);
+    for (_, old) in &backups[..excess] {
+        let _ = fs::remove_file(old);
+    }
+}
+
+fn backups_for(name: &str) -> Vec<(i64, PathBuf)> {
+    let Ok(entries) = fs::read_dir(backups_dir()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", name);
+    let mut backups: Vec<(i64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            let ts = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix(&prefix)?
+                .strip_suffix(BACKUP_SUFFIX)?
+                .parse()
+                .ok()?;
+            Some((ts, path))
+        })
+        .collect();
+    backups.sort();
+    backups
+}
+
+fn backup_name(path: &Path) -> Result<String, Error> {
+    let name = file_name(path)?;
+    let parent = path.parent().unwrap_or(Path::new("."));
+    if parent == crate::paths::pixel_agents_dir() {
+        return Ok(name);
+    }
+    match parent.file_name() {
+        Some(dir) => Ok(format!("{}-{}", dir.to_string_lossy(), name)),
+        None => Ok(name),
+    }
+}
+
+fn file_name(path: &Path) -> Result<String, Error> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| Error::Invalid(format!("invalid path {}", path.display())))
+}
@@ -0,0 +1,436 @@
+use crate::codex_record::{CodexEvent, CodexLine, CodexRecord, CodexResponseItem};
+use crate::error::Error;
+use crate::model::TranscriptEntry;
+use crate::opencode::open_opencode_db;
+use crate::opencode_record::{OpencodeMessage, OpencodePart};
+use crate::paths::{
+    codex_sessions_root, opencode_db_file, opencode_message_root, opencode_part_root,
+    opencode_session_root, windsurf_cascade_root,
+};
+use crate::scan::normalize_source_name;
+use crate::util::{collect_files, format_utc, parse_session_from_filename};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+pub fn read_transcript(source: &str, session_id: &str) -> Result<Vec<TranscriptEntry>, Error> {
+    match normalize_source_name(source).as_str() {
+        "codex" => read_codex_transcript(session_id),
+        "opencode" => read_opencode_transcript(session_id),
+        other => Err(Error::Invalid(format!(
+            "transcripts are not available for {} sessions",
+            other
+        ))),
+    }
+}
+
+pub fn session_location(source: &str, session_id: &str) -> Result<PathBuf, Error> {
+    let source = normalize_source_name(source);
+    let found = match source.as_str() {
+        "codex" => find_codex_session_file(session_id),
+        "opencode" => find_opencode_session_location(session_id),
+        "windsurf" => collect_files(&windsurf_cascade_root(), "pb", usize::MAX)
+            .into_iter()
+            .find(|file| file.file_stem().is_some_and(|stem| stem == session_id)),
+        other => {
+            return Err(Error::Invalid(format!(
+                "session files are not available for {} sessions",
+                other
+            )))
+        }
+    };
+    found.ok_or_else(|| Error::Invalid(format!("{} session not found: {}", source, session_id)))
+}
+
+fn find_opencode_session_location(session_id: &str) -> Option<PathBuf> {
+    let file_name = format!("{}.json", session_id);
+    if let Some(file) = collect_files(&opencode_session_root(), "json", usize::MAX)
+        .into_iter()
+        .find(|file| {
+            file.file_name()
+                .is_some_and(|name| name == file_name.as_str())
+        })
+    {
+        return Some(file);
+    }
+    let message_dir = opencode_message_root().join(session_id);
+    if message_dir.is_dir() {
+        return Some(message_dir);
+    }
+    Some(opencode_db_file()).filter(|db| db.exists())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    #[serde(alias = "md")]
+    Markdown,
+    Html,
+}
+
+impl TranscriptFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+pub fn render_transcript(
+    source: &str,
+    session_id: &str,
+    entries: &[TranscriptEntry],
+    format: TranscriptFormat,
+) -> String {
+    let title = format!("{} session {}", normalize_source_name(source), session_id);
+    match format {
+        TranscriptFormat::Markdown => render_markdown(&title, entries),
+        TranscriptFormat::Html => render_html(&title, entries),
+    }
+}
+
+fn entry_heading(entry: &TranscriptEntry) -> String {
+    let role = match entry.role.as_str() {
+        "user" => "User",
+        "assistant" => "Assistant",
+        "tool_call" => "Tool call",
+        "tool_output" => "Tool output",
+        other => other,
+    };
+    let mut heading = match &entry.tool {
+        Some(tool) => format!("{}: {}", role, tool),
+        None => role.to_string(),
+    };
+    if let Some(ts) = entry.ts_ms {
+        heading.push_str(&format!(" ({})", format_utc(ts)));
+    }
+    heading
+}
+
+fn is_tool_entry(entry: &TranscriptEntry) -> bool {
+    entry.role == "tool_call" || entry.role == "tool_output"
+}
+
+fn render_markdown(title: &str, entries: &[TranscriptEntry]) -> String {
+    let mut out = format!("# {}\n", title);
+    for entry in entries {
+        out.push_str(&format!("\n### {}\n\n", entry_heading(entry)));
+        if is_tool_entry(entry) {
+            let mut fence = "```".to_string();
+            while entry.text.contains(&fence) {
+                fence.push('`');
+            }
+            out.push_str(&format!(
+                "{}\n{}\n{}\n",
+                fence,
+                entry.text.trim_end(),
+                fence
+            ));
+        } else {
+            out.push_str(entry.text.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn render_html(title: &str, entries: &[TranscriptEntry]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ font-family: system-ui, sans-serif; max-width: 860px; margin: 2em auto; padding: 0 1em; color: #1d1d1f; }}\n\
+         section {{ border-left: 4px solid #c8c8d0; margin: 1em 0; padding: 0.25em 1em; }}\n\
+         section.user {{ border-color: #e0a100; }}\n\
+         section.assistant {{ border-color: #2f8f46; }}\n\
+         h3 {{ font-size: 0.9em; color: #555; margin: 0.5em 0; }}\n\
+         pre {{ background: #f4f4f6; padding: 0.75em; overflow-x: auto; }}\n\
+         p {{ white-space: pre-wrap; }}\n\
+         </style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        html_escape(title),
+        html_escape(title)
+    );
+    for entry in entries {
+        let body = if is_tool_entry(entry) {
+            format!("<pre>{}</pre>", html_escape(entry.text.trim_end()))
+        } else {
+            format!("<p>{}</p>", html_escape(entry.text.trim_end()))
+        };
+        out.push_str(&format!(
+            "<section class=\"{}\">\n<h3>{}</h3>\n{}\n</section>\n",
+            html_escape(&entry.role),
+            html_escape(&entry_heading(entry)),
+            body
+        ));
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn entry(ts_ms: Option<i64>, role: &str, text: String, tool: Option<&str>) -> TranscriptEntry {
+    TranscriptEntry {
+        ts_ms,
+        role: role.to_string(),
+        text,
+        tool: tool.map(str::to_string),
+    }
+}
+
+fn read_codex_transcript(session_id: &str) -> Result<Vec<TranscriptEntry>, Error> {
+    let file = find_codex_session_file(session_id)
+        .ok_or_else(|| Error::Invalid(format!("codex session not found: {}", session_id)))?;
+    let raw = fs::read_to_string(&file)?;
+    Ok(codex_transcript(&raw))
+}
+
+pub(crate) fn find_codex_session_file(session_id: &str) -> Option<PathBuf> {
+    let files = collect_files(&codex_sessions_root(), "jsonl", usize::MAX);
+    if let Some(file) = files
+        .iter()
+        .find(|file| parse_session_from_filename(file).as_deref() == Some(session_id))
+    {
+        return Some(file.clone());
+    }
+    files.into_iter().find(|file| {
+        file.file_stem().is_some_and(|stem| stem == session_id)
+            || first_codex_session_id(file).as_deref() == Some(session_id)
+    })
+}
+
+fn first_codex_session_id(file: &Path) -> Option<String> {
+    let mut first = String::new();
+    BufReader::new(fs::File::open(file).ok()?)
+        .read_line(&mut first)
+        .ok()?;
+    let line: CodexLine = serde_json::from_str(first.trim()).ok()?;
+    line.session_id().map(str::to_string)
+}
+
+pub(crate) fn codex_transcript(raw: &str) -> Vec<TranscriptEntry> {
+    let mut out = Vec::new();
+    for line in raw.lines() {
+        let Ok(line) = serde_json::from_str::<CodexLine>(line.trim()) else {
+            continue;
+        };
+        let ts = line.ts_ms();
+        match &line.record {
+            CodexRecord::EventMsg {
+                payload:
+                    CodexEvent::UserMessage {
+                        message: Some(message),
+                    },
+            } => out.push(entry(ts, "user", message.clone(), None)),
+            CodexRecord::EventMsg {
+                payload:
+                    CodexEvent::AgentMessage {
+                        message: Some(message),
+                    },
+            } => out.push(entry(ts, "assistant", message.clone(), None)),
+            CodexRecord::ResponseItem { payload } => match payload {
+                CodexResponseItem::FunctionCall { arguments, .. } => out.push(entry(
+                    ts,
+                    "tool_call",
+                    arguments.clone().unwrap_or_default(),
+                    payload.tool_name(),
+                )),
+                CodexResponseItem::FunctionCallOutput { output } => out.push(entry(
+                    ts,
+                    "tool_output",
+                    output.as_ref().map(value_text).unwrap_or_default(),
+                    None,
+                )),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    out
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Object(map) => ["output", "content", "text"]
+            .iter()
+            .find_map(|key| map.get(*key).and_then(Value::as_str))
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string()),
+        _ => value.to_string(),
+    }
+}
+
+fn read_opencode_transcript(session_id: &str) -> Result<Vec<TranscriptEntry>, Error> {
+    let db_path = opencode_db_file();
+    if db_path.exists() {
+        let db = open_opencode_db(&db_path)?;
+        return Ok(read_opencode_db_transcript(&db.conn, session_id)?);
+    }
+    let message_dir = opencode_message_root().join(session_id);
+    if !message_dir.exists() {
+        return Err(Error::Invalid(format!(
+            "opencode session not found: {}",
+            session_id
+        )));
+    }
+    let mut messages: Vec<OpencodeMessage> = collect_files(&message_dir, "json", usize::MAX)
+        .into_iter()
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .filter_map(|raw| serde_json::from_str(&raw).ok())
+        .collect();
+    messages.sort_by_key(|message| (message.time.created, message.id.clone()));
+
+    let mut out = Vec::new();
+    for message in &messages {
+        let Some(id) = message.id.as_deref() else {
+            continue;
+        };
+        let mut parts: Vec<PathBuf> =
+            collect_files(&opencode_part_root().join(id), "json", usize::MAX);
+        parts.sort();
+        for file in parts {
+            let Some(part) = fs::read_to_string(file)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<OpencodePart>(&raw).ok())
+            else {
+                continue;
+            };
+            push_opencode_part(&mut out, message, &part);
+        }
+    }
+    Ok(out)
+}
+
+pub(crate) fn read_opencode_db_transcript(
+    conn: &rusqlite::Connection,
+    session_id: &str,
+) -> rusqlite::Result<Vec<TranscriptEntry>> {
+    let mut messages: HashMap<String, OpencodeMessage> = HashMap::new();
+    {
+        let mut stmt = conn.prepare("SELECT id, data FROM message WHERE session_id = ?1")?;
+        let rows = stmt.query_map([session_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for (id, data) in rows.flatten() {
+            if let Ok(message) = serde_json::from_str(&data) {
+                messages.insert(id, message);
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut stmt = conn.prepare(
+        "SELECT message_id, data FROM part
+         WHERE session_id = ?1
+         ORDER BY time_created, id",
+    )?;
+    let rows = stmt.query_map([session_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for (message_id, data) in rows.flatten() {
+        let (Some(message), Ok(part)) = (
+            messages.get(&message_id),
+            serde_json::from_str::<OpencodePart>(&data),
+        ) else {
+            continue;
+        };
+        push_opencode_part(&mut out, message, &part);
+    }
+    Ok(out)
+}
+
+fn push_opencode_part(
+    out: &mut Vec<TranscriptEntry>,
+    message: &OpencodeMessage,
+    part: &OpencodePart,
+) {
+    match part {
+        OpencodePart::Text {
+            text: Some(text),
+            time,
+        } => {
+            let role = match message.role.as_deref() {
+                Some("user") => "user",
+                _ => "assistant",
+            };
+            out.push(entry(
+                time.start.or(message.time.created),
+                role,
+                text.clone(),
+                None,
+            ));
+        }
+        OpencodePart::Tool { tool, state } => {
+            out.push(entry(
+                state.time.start.or(message.time.created),
+                "tool_call",
+                state
+                    .input
+                    .as_ref()
+                    .map(Value::to_string)
+                    .unwrap_or_default(),
+                tool.as_deref(),
+            ));
+            if let Some(output) = state.output.as_ref().or(state.error.as_ref()) {
+                out.push(entry(
+                    state.time.end.or(message.time.completed),
+                    "tool_output",
+                    output.clone(),
+                    tool.as_deref(),
+                ));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codex_transcript_orders_messages_and_tools() {
+        let entries = codex_transcript(include_str!("../tests/fixtures/codex-rollout.jsonl"));
+        let roles: Vec<&str> = entries.iter().map(|e| e.role.as_str()).collect();
+        assert_eq!(roles, ["user", "tool_call"]);
+        assert_eq!(entries[0].text, "Fix the flaky test\nthen run the suite");
+        assert_eq!(entries[1].tool.as_deref(), Some("shell"));
+    }
+
+    #[test]
+    fn renders_markdown_and_html() {
+        let entries = vec![
+            entry(
+                Some(1_735_689_600_000),
+                "user",
+                "Fix <it>".to_string(),
+                None,
+            ),
+            entry(None, "tool_call", "echo ```".to_string(), Some("shell")),
+        ];
+        let markdown = render_transcript("codex", "abc", &entries, TranscriptFormat::Markdown);
+        assert!(markdown.starts_with("# codex session abc\n"));
+        assert!(markdown.contains("### User (2025-01-01 00:00:00 UTC)\n\nFix <it>\n"));
+        assert!(markdown.contains("### Tool call: shell\n\n````\necho ```\n````\n"));
+
+        let html = render_transcript("codex", "abc", &entries, TranscriptFormat::Html);
+        assert!(html.contains("<p>Fix &lt;it&gt;</p>"));
+        assert!(html.contains("<section class=\"tool_call\">"));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::error::Error;
+use crate::github::{API_TIMEOUT_SECS, USER_AGENT};
+use crate::model::UpdateInfo;
+use serde::Deserialize;
+use std::time::Duration;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/benben4/pixel-agents/releases/latest";
+
+#[derive(Deserialize)]
+struct RestRelease {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    assets: Vec<RestAsset>,
+}
+
+#[derive(Deserialize)]
+struct RestAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+pub fn check_for_updates(current_version: &str) -> Result<UpdateInfo, Error> {
+    let body = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(API_TIMEOUT_SECS))
+        .user_agent(USER_AGENT)
+        .build()
+        .get(RELEASES_URL)
+        .set("Accept", "application/vnd.github+json")
+        .set("X-GitHub-Api-Version", "2022-11-28")
+        .call()?
+        .into_string()?;
+    let release: RestRelease = serde_json::from_str(&body)?;
+    Ok(update_info(current_version, release))
+}
+
+fn update_info(current_version: &str, release: RestRelease) -> UpdateInfo {
+    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+    let download_url = platform_asset(&release.assets)
+        .map(|asset| asset.browser_download_url.clone())
+        .unwrap_or(release.html_url);
+    UpdateInfo {
+        update_available: is_newer(&latest_version, current_version),
+        current_version: current_version.to_string(),
+        latest_version,
+        release_notes: release.body.filter(|body| !body.trim().is_empty()),
+        download_url,
+        published_at: release.published_at,
+    }
+}
+
+fn platform_asset(assets: &[RestAsset]) -> Option<&RestAsset> {
+    let extensions: &[&str] = if cfg!(target_os = "macos") {
+        &[".dmg"]
+    } else if cfg!(target_os = "windows") {
+        &[".msi", ".exe"]
+    } else {
+        &[".AppImage", ".deb", ".rpm"]
+    };
+    extensions
+        .iter()
+        .find_map(|extension| assets.iter().find(|asset| asset.name.ends_with(extension)))
+}
+
+fn is_newer(latest: &str, current: &str) -> bool {
+    version_parts(latest) > version_parts(current)
+}
+
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or("")
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("v1.0.0", "0.1.0"));
+        assert!(is_newer("0.1.1", "0.1"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.1.0-beta.2", "0.1.0"));
+    }
+
+    #[test]
+    fn falls_back_to_release_page_without_platform_asset() {
+        let release = RestRelease {
+            tag_name: "v0.2.0".to_string(),
+            html_url: "https://github.com/benben4/pixel-agents/releases/tag/v0.2.0".to_string(),
+            body: Some("  ".to_string()),
+            published_at: None,
+            assets: vec![RestAsset {
+                name: "checksums.txt".to_string(),
+                browser_download_url: "https://example.com/checksums.txt".to_string(),
+            }],
+        };
+        let info = update_info("0.1.0", release);
+        assert!(info.update_available);
+        assert_eq!(info.latest_version, "0.2.0");
+        assert_eq!(info.release_notes, None);
+        assert!(info.download_url.ends_with("/tag/v0.2.0"));
+    }
+}
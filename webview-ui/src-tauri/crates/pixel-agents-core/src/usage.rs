@@ -0,0 +1,514 @@
+use crate::codex_record::{CodexLine, CodexTokenUsage};
+use crate::error::Error;
+use crate::model::{SessionUsage, UsageBucket, UsageStatsPayload, UsageTotals};
+use crate::opencode::{message_event, open_opencode_db};
+use crate::opencode_record::OpencodeMessage;
+use crate::paths::{codex_sessions_root, opencode_db_file, opencode_message_root};
+use crate::util::{
+    collect_files, format_utc, modified_ms, normalize_epoch_ms, parse_session_from_filename,
+};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+const ACTIVE_GAP_MS: i64 = 5 * 60_000;
+const NO_REPO_KEY: &str = "(no repo)";
+
+// USD per million tokens: input, cached input, output. First prefix match wins.
+const CODEX_PRICES: [(&str, f64, f64, f64); 9] = [
+    ("gpt-5-nano", 0.05, 0.005, 0.40),
+    ("gpt-5-mini", 0.25, 0.025, 2.00),
+    ("gpt-5", 1.25, 0.125, 10.00),
+    ("gpt-4.1-nano", 0.10, 0.025, 0.40),
+    ("gpt-4.1-mini", 0.40, 0.10, 1.60),
+    ("gpt-4.1", 2.00, 0.50, 8.00),
+    ("o4-mini", 1.10, 0.275, 4.40),
+    ("o3", 2.00, 0.50, 8.00),
+    ("codex-mini", 1.50, 0.375, 6.00),
+];
+
+#[derive(Debug, Default)]
+struct UsageSample {
+    ts_ms: i64,
+    input_tokens: u64,
+    cached_input_tokens: u64,
+    output_tokens: u64,
+    reasoning_tokens: u64,
+    cost_usd: f64,
+    turn: bool,
+}
+
+struct SessionSamples {
+    source: &'static str,
+    session_id: String,
+    repo_path: Option<String>,
+    model: Option<String>,
+    state: String,
+    state_ts_ms: i64,
+    samples: Vec<UsageSample>,
+}
+
+impl SessionSamples {
+    fn new(source: &'static str, session_id: String) -> Self {
+        Self {
+            source,
+            session_id,
+            repo_path: None,
+            model: None,
+            state: "idle".to_string(),
+            state_ts_ms: i64::MIN,
+            samples: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRange {
+    #[serde(default)]
+    pub since_ms: Option<i64>,
+    #[serde(default)]
+    pub until_ms: Option<i64>,
+}
+
+impl UsageRange {
+    fn contains(self, ts_ms: i64) -> bool {
+        self.since_ms.is_none_or(|since| ts_ms >= since)
+            && self.until_ms.is_none_or(|until| ts_ms < until)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsFormat {
+    Csv,
+    Json,
+}
+
+impl StatsFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+pub fn read_usage_stats(range: UsageRange) -> Result<UsageStatsPayload, Error> {
+    let mut sessions = read_codex_usage(range.since_ms);
+    sessions.extend(read_opencode_usage(range.since_ms)?);
+    Ok(aggregate_usage(sessions, range))
+}
+
+pub fn render_session_usage(
+    sessions: &[SessionUsage],
+    format: StatsFormat,
+) -> Result<String, Error> {
+    match format {
+        StatsFormat::Json => Ok(serde_json::to_string_pretty(sessions)?),
+        StatsFormat::Csv => Ok(render_usage_csv(sessions)),
+    }
+}
+
+fn render_usage_csv(sessions: &[SessionUsage]) -> String {
+    let mut out = String::from(
+        "source,session_id,repo,model,start_utc,end_utc,duration_min,active_min,\
+         input_tokens,cached_input_tokens,output_tokens,reasoning_tokens,turns,cost_usd,final_state\n",
+    );
+    for session in sessions {
+        let row = [
+            session.source.clone(),
+            session.session_id.clone(),
+            session.repo_path.clone().unwrap_or_default(),
+            session.model.clone().unwrap_or_default(),
+            format_utc(session.started_ts_ms),
+            format_utc(session.ended_ts_ms),
+            format!(
+                "{:.1}",
+                (session.ended_ts_ms - session.started_ts_ms) as f64 / 60_000.0
+            ),
+            format!("{:.1}", session.totals.active_ms as f64 / 60_000.0),
+            session.totals.input_tokens.to_string(),
+            session.totals.cached_input_tokens.to_string(),
+            session.totals.output_tokens.to_string(),
+            session.totals.reasoning_tokens.to_string(),
+            session.totals.turns.to_string(),
+            format!("{:.4}", session.totals.cost_usd),
+            session.state.clone(),
+        ];
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn aggregate_usage(sessions: Vec<SessionSamples>, range: UsageRange) -> UsageStatsPayload {
+    let mut payload = UsageStatsPayload {
+        since_ms: range.since_ms,
+        until_ms: range.until_ms,
+        ..UsageStatsPayload::default()
+    };
+    let mut by_source: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    let mut by_repo: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    let mut by_day: BTreeMap<String, UsageTotals> = BTreeMap::new();
+    for mut session in sessions {
+        session
+            .samples
+            .retain(|sample| range.contains(sample.ts_ms));
+        session.samples.sort_by_key(|sample| sample.ts_ms);
+        let (Some(first), Some(last)) = (session.samples.first(), session.samples.last()) else {
+            continue;
+        };
+        let (started_ts_ms, ended_ts_ms) = (first.ts_ms, last.ts_ms);
+
+        let mut totals = UsageTotals {
+            sessions: 1,
+            ..UsageTotals::default()
+        };
+        let mut prev_ts: Option<i64> = None;
+        let mut prev_day: Option<String> = None;
+        for sample in &session.samples {
+            let active_ms = prev_ts
+                .map(|prev| sample.ts_ms - prev)
+                .filter(|gap| *gap <= ACTIVE_GAP_MS)
+                .unwrap_or(0);
+            prev_ts = Some(sample.ts_ms);
+            add_sample(&mut totals, sample, active_ms);
+
+            let day = usage_day(sample.ts_ms);
+            let day_totals = by_day.entry(day.clone()).or_default();
+            if prev_day.as_ref() != Some(&day) {
+                day_totals.sessions += 1;
+                prev_day = Some(day);
+            }
+            add_sample(day_totals, sample, active_ms);
+        }
+
+        merge_totals(
+            by_source.entry(session.source.to_string()).or_default(),
+            &totals,
+        );
+        let repo_key = session
+            .repo_path
+            .clone()
+            .unwrap_or_else(|| NO_REPO_KEY.to_string());
+        merge_totals(by_repo.entry(repo_key).or_default(), &totals);
+        merge_totals(&mut payload.total, &totals);
+        payload.sessions.push(SessionUsage {
+            key: format!("{}:{}", session.source, session.session_id),
+            source: session.source.to_string(),
+            session_id: session.session_id,
+            repo_path: session.repo_path,
+            model: session.model,
+            started_ts_ms,
+            ended_ts_ms,
+            state: session.state,
+            totals,
+        });
+    }
+    payload
+        .sessions
+        .sort_by(|a, b| b.ended_ts_ms.cmp(&a.ended_ts_ms));
+    payload.by_source = into_buckets(by_source);
+    payload.by_repo = into_buckets(by_repo);
+    payload.by_day = into_buckets(by_day);
+    payload
+}
+
+fn usage_day(ts_ms: i64) -> String {
+    format_utc(ts_ms).chars().take(10).collect()
+}
+
+fn add_sample(totals: &mut UsageTotals, sample: &UsageSample, active_ms: i64) {
+    totals.input_tokens += sample.input_tokens;
+    totals.cached_input_tokens += sample.cached_input_tokens;
+    totals.output_tokens += sample.output_tokens;
+    totals.reasoning_tokens += sample.reasoning_tokens;
+    totals.cost_usd += sample.cost_usd;
+    totals.turns += u64::from(sample.turn);
+    totals.active_ms += active_ms;
+}
+
+fn merge_totals(into: &mut UsageTotals, from: &UsageTotals) {
+    into.input_tokens += from.input_tokens;
+    into.cached_input_tokens += from.cached_input_tokens;
+    into.output_tokens += from.output_tokens;
+    into.reasoning_tokens += from.reasoning_tokens;
+    into.cost_usd += from.cost_usd;
+    into.turns += from.turns;
+    into.active_ms += from.active_ms;
+    into.sessions += from.sessions;
+}
+
+fn into_buckets(map: BTreeMap<String, UsageTotals>) -> Vec<UsageBucket> {
+    map.into_iter()
+        .map(|(key, totals)| UsageBucket { key, totals })
+        .collect()
+}
+
+fn read_codex_usage(since_ms: Option<i64>) -> Vec<SessionSamples> {
+    collect_files(&codex_sessions_root(), "jsonl", usize::MAX)
+        .into_iter()
+        .filter(|file| since_ms.is_none_or(|since| modified_ms(file) >= since))
+        .filter_map(|file| codex_file_usage(&file))
+        .collect()
+}
+
+fn codex_file_usage(file: &Path) -> Option<SessionSamples> {
+    let raw = fs::read_to_string(file).ok()?;
+    let mut session = SessionSamples::new("codex", String::new());
+    let mut session_id: Option<String> = None;
+    let mut last_total = CodexTokenUsage::default();
+    for line in raw.lines() {
+        let Ok(line) = serde_json::from_str::<CodexLine>(line.trim()) else {
+            continue;
+        };
+        if session_id.is_none() {
+            session_id = line.session_id().map(str::to_string);
+        }
+        if session.repo_path.is_none() {
+            session.repo_path = line.cwd().map(str::to_string);
+        }
+        if let Some(model) = line.model() {
+            session.model = Some(model.to_string());
+        }
+        if let Some(activity) = line.activity() {
+            session.state = activity.state.to_string();
+        }
+        let Some(ts_ms) = line.ts_ms() else {
+            continue;
+        };
+        let mut sample = UsageSample {
+            ts_ms,
+            turn: line.is_user_message(),
+            ..UsageSample::default()
+        };
+        if let Some(total) = line.total_token_usage() {
+            let delta = CodexTokenUsage {
+                input_tokens: total.input_tokens.saturating_sub(last_total.input_tokens),
+                cached_input_tokens: total
+                    .cached_input_tokens
+                    .saturating_sub(last_total.cached_input_tokens),
+                output_tokens: total.output_tokens.saturating_sub(last_total.output_tokens),
+                reasoning_output_tokens: total
+                    .reasoning_output_tokens
+                    .saturating_sub(last_total.reasoning_output_tokens),
+            };
+            last_total = total;
+            sample.input_tokens = delta.input_tokens;
+            sample.cached_input_tokens = delta.cached_input_tokens;
+            sample.output_tokens = delta.output_tokens;
+            sample.reasoning_tokens = delta.reasoning_output_tokens;
+            sample.cost_usd = codex_cost(session.model.as_deref(), &delta);
+        }
+        session.samples.push(sample);
+    }
+    session.session_id = session_id.or_else(|| parse_session_from_filename(file))?;
+    Some(session)
+}
+
+fn codex_cost(model: Option<&str>, usage: &CodexTokenUsage) -> f64 {
+    let Some((_, input, cached, output)) = model.and_then(|model| {
+        CODEX_PRICES
+            .iter()
+            .find(|(prefix, ..)| model.starts_with(prefix))
+    }) else {
+        return 0.0;
+    };
+    let uncached = usage.input_tokens.saturating_sub(usage.cached_input_tokens);
+    (uncached as f64 * input
+        + usage.cached_input_tokens as f64 * cached
+        + usage.output_tokens as f64 * output)
+        / 1_000_000.0
+}
+
+fn read_opencode_usage(since_ms: Option<i64>) -> Result<Vec<SessionSamples>, Error> {
+    let mut sessions: HashMap<String, SessionSamples> = HashMap::new();
+    let db_path = opencode_db_file();
+    if db_path.exists() {
+        let db = open_opencode_db(&db_path)?;
+        let mut stmt = db.conn.prepare(
+            "SELECT m.session_id, m.time_created, m.data, s.directory
+             FROM message m LEFT JOIN session s ON s.id = m.session_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let session_id: String = row.get(0)?;
+            let time_created: i64 = row.get(1)?;
+            let data: String = row.get(2)?;
+            let directory: Option<String> = row.get(3)?;
+            Ok((session_id, time_created, data, directory))
+        })?;
+        for (session_id, time_created, data, directory) in rows.flatten() {
+            let Ok(message) = serde_json::from_str::<OpencodeMessage>(&data) else {
+                continue;
+            };
+            let ts_ms = message
+                .time
+                .created
+                .unwrap_or_else(|| normalize_epoch_ms(time_created));
+            push_opencode_message(&mut sessions, session_id, directory, &message, ts_ms);
+        }
+        return Ok(sessions.into_values().collect());
+    }
+
+    for file in collect_files(&opencode_message_root(), "json", usize::MAX) {
+        if since_ms.is_some_and(|since| modified_ms(&file) < since) {
+            continue;
+        }
+        let Some(message) = fs::read_to_string(&file)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<OpencodeMessage>(&raw).ok())
+        else {
+            continue;
+        };
+        let Some(session_id) = message.session_id.clone().or_else(|| {
+            file.parent()
+                .and_then(|p| p.file_name())
+                .map(|s| s.to_string_lossy().into_owned())
+        }) else {
+            continue;
+        };
+        let ts_ms = message.time.created.unwrap_or_else(|| modified_ms(&file));
+        push_opencode_message(&mut sessions, session_id, None, &message, ts_ms);
+    }
+    Ok(sessions.into_values().collect())
+}
+
+fn push_opencode_message(
+    sessions: &mut HashMap<String, SessionSamples>,
+    session_id: String,
+    repo_path: Option<String>,
+    message: &OpencodeMessage,
+    ts_ms: i64,
+) {
+    let session = sessions
+        .entry(session_id.clone())
+        .or_insert_with(|| SessionSamples::new("opencode", session_id));
+    if session.repo_path.is_none() {
+        session.repo_path = repo_path.or_else(|| message.repo_path().map(str::to_string));
+    }
+    if ts_ms >= session.state_ts_ms {
+        session.state = message_event(message, ts_ms).state_hint;
+        session.state_ts_ms = ts_ms;
+        if let Some(model) = &message.model_id {
+            session.model = Some(model.clone());
+        }
+    }
+    let mut sample = UsageSample {
+        ts_ms,
+        cost_usd: message.cost.unwrap_or(0.0),
+        turn: message.role.as_deref() == Some("user"),
+        ..UsageSample::default()
+    };
+    if let Some(tokens) = &message.tokens {
+        sample.input_tokens = (tokens.input + tokens.cache.read + tokens.cache.write) as u64;
+        sample.cached_input_tokens = tokens.cache.read as u64;
+        sample.output_tokens = (tokens.output + tokens.reasoning) as u64;
+        sample.reasoning_tokens = tokens.reasoning as u64;
+    }
+    session.samples.push(sample);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: i64 = 86_400_000;
+
+    fn sample(ts_ms: i64, output_tokens: u64, turn: bool) -> UsageSample {
+        UsageSample {
+            ts_ms,
+            output_tokens,
+            cost_usd: output_tokens as f64 / 1000.0,
+            turn,
+            ..UsageSample::default()
+        }
+    }
+
+    #[test]
+    fn aggregates_by_source_repo_and_day() {
+        let mut codex = SessionSamples::new("codex", "c1".to_string());
+        codex.repo_path = Some("/repo".to_string());
+        codex.samples = vec![
+            sample(DAY_MS - 60_000, 100, true),
+            sample(DAY_MS + 60_000, 200, false),
+            sample(DAY_MS + 60_000 + ACTIVE_GAP_MS + 1, 300, true),
+        ];
+        let mut opencode = SessionSamples::new("opencode", "o1".to_string());
+        opencode.samples = vec![sample(DAY_MS + 1000, 50, true)];
+
+        let stats = aggregate_usage(vec![codex, opencode], UsageRange::default());
+        assert_eq!(stats.total.output_tokens, 650);
+        assert_eq!(stats.total.turns, 3);
+        assert_eq!(stats.total.sessions, 2);
+        assert_eq!(stats.total.active_ms, 120_000);
+        assert_eq!(stats.sessions[0].key, "codex:c1");
+        assert_eq!(stats.sessions[0].started_ts_ms, DAY_MS - 60_000);
+
+        let days: Vec<(&str, u64, usize)> = stats
+            .by_day
+            .iter()
+            .map(|b| (b.key.as_str(), b.totals.output_tokens, b.totals.sessions))
+            .collect();
+        assert_eq!(days, vec![("1970-01-01", 100, 1), ("1970-01-02", 550, 2)]);
+        let repos: Vec<&str> = stats.by_repo.iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(repos, vec![NO_REPO_KEY, "/repo"]);
+        assert_eq!(stats.by_source.len(), 2);
+    }
+
+    #[test]
+    fn range_drops_samples_outside() {
+        let mut codex = SessionSamples::new("codex", "c1".to_string());
+        codex.samples = vec![
+            sample(1000, 100, true),
+            sample(DAY_MS, 200, true),
+            sample(2 * DAY_MS, 400, true),
+        ];
+        let range = UsageRange {
+            since_ms: Some(DAY_MS),
+            until_ms: Some(2 * DAY_MS),
+        };
+        let stats = aggregate_usage(vec![codex], range);
+        assert_eq!(stats.total.output_tokens, 200);
+        assert_eq!(stats.sessions[0].started_ts_ms, DAY_MS);
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_lists_sessions() {
+        let mut codex = SessionSamples::new("codex", "c1".to_string());
+        codex.repo_path = Some("/work/a,b".to_string());
+        codex.samples = vec![sample(0, 1000, true), sample(120_000, 0, false)];
+        let stats = aggregate_usage(vec![codex], UsageRange::default());
+        let csv = render_session_usage(&stats.sessions, StatsFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("source,session_id,repo,"));
+        assert_eq!(
+            lines[1],
+            "codex,c1,\"/work/a,b\",,1970-01-01 00:00:00 UTC,1970-01-01 00:02:00 UTC,2.0,2.0,0,0,1000,0,1,1.0000,idle"
+        );
+    }
+
+    #[test]
+    fn codex_cost_uses_cached_rate() {
+        let usage = CodexTokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 500_000,
+            output_tokens: 100_000,
+            reasoning_output_tokens: 0,
+        };
+        let cost = codex_cost(Some("gpt-5-codex"), &usage);
+        assert!((cost - (0.625 + 0.0625 + 1.0)).abs() < 1e-9);
+        assert_eq!(codex_cost(Some("unknown-model"), &usage), 0.0);
+    }
+}
@@ -0,0 +1,279 @@
+use crate::error::Error;
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+pub(crate) const MAX_TAIL_RECORD_BYTES: u64 = 4 * 1024 * 1024;
+pub(crate) const TAIL_SEEK_STEP_BYTES: u64 = 8_192;
+pub(crate) const MAX_MONITOR_TEXT_CHARS: usize = 180;
+
+pub(crate) fn file_uri_to_path(uri: &str) -> Option<String> {
+    let rest = uri.strip_prefix("file://")?;
+    let decoded = percent_decode(rest);
+    let bytes = decoded.as_bytes();
+    if bytes.len() > 2 && bytes[0] == b'/' && bytes[2] == b':' {
+        return Some(decoded[1..].to_string());
+    }
+    Some(decoded)
+}
+
+/// Agent key from a `pixel-agents://agent/<source>:<session>` link.
+pub fn agent_key_from_link(link: &str) -> Option<String> {
+    let (scheme, rest) = link.trim().split_once("://")?;
+    if !scheme.eq_ignore_ascii_case("pixel-agents") {
+        return None;
+    }
+    let path = rest.split(['?', '#']).next()?.trim_end_matches('/');
+    let key = percent_decode(path.strip_prefix("agent/")?);
+    let (source, session_id) = key.split_once(':')?;
+    (!source.is_empty() && !session_id.is_empty() && !session_id.contains('/')).then_some(key)
+}
+
+pub(crate) fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' && idx + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[idx + 1..idx + 3]).unwrap_or_default();
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                out.push(byte);
+                idx += 3;
+                continue;
+            }
+        }
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+pub(crate) fn collect_files(root: &Path, ext: &str, max_files: usize) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let path = entry.path().to_path_buf();
+            let matches = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.eq_ignore_ascii_case(ext))
+                .unwrap_or(false);
+            if matches {
+                Some(path)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    files.sort_by_cached_key(|path| std::cmp::Reverse(modified_ms(path)));
+    if files.len() > max_files {
+        files.truncate(max_files);
+    }
+    files
+}
+
+pub(crate) fn truncate_option_text(text: Option<String>) -> Option<String> {
+    text.map(truncate_text)
+}
+
+pub(crate) fn truncate_text(text: String) -> String {
+    if text.chars().count() <= MAX_MONITOR_TEXT_CHARS {
+        return text;
+    }
+    let mut out = String::new();
+    for (idx, ch) in text.chars().enumerate() {
+        if idx >= MAX_MONITOR_TEXT_CHARS {
+            break;
+        }
+        out.push(ch);
+    }
+    out.push_str("...");
+    out
+}
+
+pub(crate) fn modified_ms(path: &Path) -> i64 {
+    fs::metadata(path)
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(system_time_to_ms)
+        .unwrap_or_else(now_ms)
+}
+
+pub(crate) fn normalize_epoch_ms(value: i64) -> i64 {
+    if value <= 0 {
+        return value;
+    }
+    if value < 10_000_000_000 {
+        return value.saturating_mul(1000);
+    }
+    if value > 10_000_000_000_000_000 {
+        return value / 1_000_000;
+    }
+    if value > 10_000_000_000_000 {
+        return value / 1000;
+    }
+    value
+}
+
+pub(crate) fn system_time_to_ms(time: SystemTime) -> Option<i64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as i64)
+}
+
+pub(crate) fn read_range(path: &Path, start: u64, end: u64) -> Result<Vec<u8>, Error> {
+    if end <= start {
+        return Ok(Vec::new());
+    }
+    let mut file = fs::File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut buf = vec![0_u8; (end - start) as usize];
+    file.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+pub(crate) fn read_tail_lines(
+    path: &Path,
+    len: u64,
+    tail_bytes: usize,
+) -> Result<(u64, Vec<u8>), Error> {
+    let start = len.saturating_sub(tail_bytes as u64);
+    if start == 0 {
+        return Ok((0, read_range(path, 0, len)?));
+    }
+    if let Some(line_start) = line_start_before(path, start)? {
+        return Ok((line_start, read_range(path, line_start, len)?));
+    }
+    let chunk = read_range(path, start, len)?;
+    let skip = chunk
+        .iter()
+        .position(|b| *b == b'\n')
+        .map(|idx| idx + 1)
+        .unwrap_or(chunk.len());
+    Ok((start + skip as u64, chunk[skip..].to_vec()))
+}
+
+pub(crate) fn line_start_before(path: &Path, offset: u64) -> Result<Option<u64>, Error> {
+    let mut end = offset;
+    while end > 0 && offset - end < MAX_TAIL_RECORD_BYTES {
+        let begin = end.saturating_sub(TAIL_SEEK_STEP_BYTES);
+        let block = read_range(path, begin, end)?;
+        if let Some(idx) = block.iter().rposition(|b| *b == b'\n') {
+            return Ok(Some(begin + idx as u64 + 1));
+        }
+        end = begin;
+    }
+    Ok((end == 0).then_some(0))
+}
+
+pub(crate) fn complete_lines_len(buf: &[u8]) -> usize {
+    buf.iter()
+        .rposition(|b| *b == b'\n')
+        .map(|idx| idx + 1)
+        .unwrap_or(0)
+}
+
+pub(crate) fn parse_session_from_filename(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_string_lossy().to_string();
+    let re = Regex::new(
+        r"([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})$",
+    )
+    .ok()?;
+    re.captures(&stem)
+        .and_then(|c| c.get(1).map(|m| m.as_str().to_string()))
+}
+
+pub(crate) fn string_at(value: &Value, path: &[&str]) -> Option<String> {
+    let mut current = value;
+    for key in path {
+        current = current.get(*key)?;
+    }
+    current.as_str().map(|s| s.to_string())
+}
+
+pub(crate) fn de_epoch_ms<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(value.as_ref().and_then(to_i64).map(normalize_epoch_ms))
+}
+
+pub(crate) fn de_lenient_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(Value::String(text)) => Some(text),
+        _ => None,
+    })
+}
+
+pub(crate) fn to_i64(value: &Value) -> Option<i64> {
+    if let Some(v) = value.as_i64() {
+        return Some(v);
+    }
+    if let Some(v) = value.as_u64() {
+        return Some(v as i64);
+    }
+    if let Some(v) = value.as_f64() {
+        return Some(v as i64);
+    }
+    None
+}
+
+pub(crate) fn format_utc(ms: i64) -> String {
+    let secs = ms.div_euclid(1000);
+    let days = secs.div_euclid(86_400);
+    let rem = secs.rem_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+pub fn now_ms() -> i64 {
+    system_time_to_ms(SystemTime::now()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_links_yield_agent_keys() {
+        assert_eq!(
+            agent_key_from_link("pixel-agents://agent/codex:0199-abc"),
+            Some("codex:0199-abc".to_string())
+        );
+        assert_eq!(
+            agent_key_from_link("pixel-agents://agent/opencode%3Ases_1/?from=slack"),
+            Some("opencode:ses_1".to_string())
+        );
+        assert_eq!(agent_key_from_link("pixel-agents://agent/codex"), None);
+        assert_eq!(agent_key_from_link("https://agent/codex:1"), None);
+    }
+}
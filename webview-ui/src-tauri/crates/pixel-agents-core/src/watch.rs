@@ -0,0 +1,114 @@
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default)]
+pub struct WatchChanges {
+    pub full_rescan: bool,
+    pub paths: HashSet<PathBuf>,
+}
+
+impl WatchChanges {
+    pub(crate) fn full() -> Self {
+        Self {
+            full_rescan: true,
+            paths: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.full_rescan && self.paths.is_empty()
+    }
+}
+
+pub struct DirWatcher {
+    root: PathBuf,
+    changes: Arc<Mutex<WatchChanges>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl DirWatcher {
+    pub(crate) fn new(root: &Path) -> Option<Self> {
+        Self::with_mode(root, RecursiveMode::Recursive)
+    }
+
+    pub fn shallow(root: &Path) -> Option<Self> {
+        Self::with_mode(root, RecursiveMode::NonRecursive)
+    }
+
+    fn with_mode(root: &Path, mode: RecursiveMode) -> Option<Self> {
+        if !root.is_dir() {
+            return None;
+        }
+        let changes = Arc::new(Mutex::new(WatchChanges::full()));
+        let sink = Arc::clone(&changes);
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            let Ok(mut changes) = sink.lock() else {
+                return;
+            };
+            match res {
+                Ok(event) if event.need_rescan() => changes.full_rescan = true,
+                Ok(event) => changes.paths.extend(event.paths),
+                Err(_) => changes.full_rescan = true,
+            }
+        })
+        .ok()?;
+        watcher.watch(root, mode).ok()?;
+        Some(Self {
+            root: root.to_path_buf(),
+            changes,
+            _watcher: watcher,
+        })
+    }
+
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn take_changes(&self) -> WatchChanges {
+        match self.changes.lock() {
+            Ok(mut changes) => std::mem::take(&mut *changes),
+            Err(_) => WatchChanges::full(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct WatchedSource<T> {
+    watcher: Option<DirWatcher>,
+    cached: Option<T>,
+}
+
+impl<T: Clone> WatchedSource<T> {
+    pub(crate) fn reset(&mut self) {
+        self.watcher = None;
+        self.cached = None;
+    }
+
+    pub(crate) fn scan(&mut self, root: &Path, scan: impl FnOnce(&WatchChanges) -> T) -> T {
+        if self
+            .watcher
+            .as_ref()
+            .map(|w| w.root() != root)
+            .unwrap_or(true)
+        {
+            self.watcher = DirWatcher::new(root);
+            self.cached = None;
+        }
+        let changes = match &self.watcher {
+            Some(watcher) => watcher.take_changes(),
+            None => WatchChanges::full(),
+        };
+        if changes.is_empty() {
+            if let Some(cached) = &self.cached {
+                return cached.clone();
+            }
+        }
+        let value = scan(&changes);
+        if self.watcher.is_some() {
+            self.cached = Some(value.clone());
+        }
+        value
+    }
+}
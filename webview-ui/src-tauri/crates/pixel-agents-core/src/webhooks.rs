@@ -0,0 +1,223 @@
+use crate::error::Error;
+use crate::github::{API_TIMEOUT_SECS, USER_AGENT};
+use crate::model::MonitorNotification;
+use crate::settings::{read_desktop_settings, write_desktop_setting};
+use crate::util::now_ms;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::time::Duration;
+
+pub const WEBHOOK_EVENTS: [&str; 6] = ["done", "error", "waiting", "approval", "limited", "stuck"];
+const WEBHOOKS_KEY: &str = "webhooks";
+const SIGNATURE_HEADER: &str = "X-Pixel-Agents-Signature";
+const EVENT_HEADER: &str = "X-Pixel-Agents-Event";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    #[default]
+    Json,
+    Slack,
+    Discord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(default = "default_webhook_events")]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+fn default_webhook_events() -> Vec<String> {
+    ["done", "error", "waiting", "approval"]
+        .map(str::to_string)
+        .to_vec()
+}
+
+/// Whether `events` subscribes to `kind`. Approval requests used to be sent as
+/// `waiting`, so subscriptions saved before they had their own event still get them.
+pub(crate) fn subscribes(events: &[String], kind: &str) -> bool {
+    events
+        .iter()
+        .any(|event| event == kind || (kind == "approval" && event == "waiting"))
+}
+
+impl Webhook {
+    pub fn accepts(&self, kind: &str) -> bool {
+        subscribes(&self.events, kind)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let url = self.url.trim();
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(Error::Invalid(format!(
+                "webhook url must start with http:// or https://: {}",
+                url
+            )));
+        }
+        if let Some(event) = self
+            .events
+            .iter()
+            .find(|event| !WEBHOOK_EVENTS.contains(&event.as_str()))
+        {
+            return Err(Error::Invalid(format!("unknown webhook event: {}", event)));
+        }
+        Ok(())
+    }
+}
+
+pub fn read_webhooks() -> Vec<Webhook> {
+    read_desktop_settings()
+        .ok()
+        .and_then(|mut v| v.get_mut(WEBHOOKS_KEY).map(Value::take))
+        .and_then(|webhooks| serde_json::from_value(webhooks).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_webhooks(webhooks: &[Webhook]) -> Result<(), Error> {
+    let mut cleaned = Vec::with_capacity(webhooks.len());
+    for webhook in webhooks {
+        webhook.validate()?;
+        cleaned.push(Webhook {
+            url: webhook.url.trim().to_string(),
+            secret: webhook
+                .secret
+                .as_deref()
+                .map(str::trim)
+                .filter(|secret| !secret.is_empty())
+                .map(str::to_string),
+            events: webhook.events.clone(),
+            format: webhook.format,
+        });
+    }
+    if cleaned.is_empty() {
+        return write_desktop_setting(WEBHOOKS_KEY, Value::Null);
+    }
+    write_desktop_setting(WEBHOOKS_KEY, serde_json::to_value(cleaned)?)
+}
+
+/// Posts every notification to the webhooks subscribed to its kind, logging failures.
+pub fn dispatch_webhooks(notifications: &[MonitorNotification]) {
+    let webhooks = read_webhooks();
+    for notification in notifications {
+        for webhook in webhooks.iter().filter(|w| w.accepts(&notification.kind)) {
+            if let Err(e) = send_webhook(webhook, notification) {
+                tracing::warn!(kind = %notification.kind, error = %e, "webhook failed");
+            }
+        }
+    }
+}
+
+pub fn send_webhook(webhook: &Webhook, notification: &MonitorNotification) -> Result<(), Error> {
+    webhook.validate()?;
+    let body = webhook_payload(webhook.format, notification, now_ms()).to_string();
+    let mut request = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(API_TIMEOUT_SECS))
+        .user_agent(USER_AGENT)
+        .build()
+        .post(webhook.url.trim())
+        .set("Content-Type", "application/json")
+        .set(EVENT_HEADER, &notification.kind);
+    if let Some(secret) = &webhook.secret {
+        request = request.set(
+            SIGNATURE_HEADER,
+            &format!("sha256={}", sign(secret, body.as_bytes())),
+        );
+    }
+    request
+        .send_string(&body)
+        .map_err(|e| Error::State(format!("webhook request failed: {}", e)))?;
+    Ok(())
+}
+
+pub(crate) fn webhook_payload(
+    format: WebhookFormat,
+    notification: &MonitorNotification,
+    ts_ms: i64,
+) -> Value {
+    match format {
+        WebhookFormat::Json => json!({
+            "event": notification.kind,
+            "title": notification.title,
+            "message": notification.message,
+            "agentKey": notification.key,
+            "priority": notification.priority,
+            "tsMs": ts_ms,
+        }),
+        WebhookFormat::Slack => json!({
+            "text": format!("*{}*\n{}", notification.title, notification.message),
+        }),
+        WebhookFormat::Discord => json!({
+            "username": "Pixel Agents",
+            "content": format!("**{}**\n{}", notification.title, notification.message),
+        }),
+    }
+}
+
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts keys of any length");
+    mac.update(body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification() -> MonitorNotification {
+        MonitorNotification::new("Agent done", "fix-billing - Completed", "done", "codex:s1")
+    }
+
+    #[test]
+    fn formats_payloads_for_each_target() {
+        let json = webhook_payload(WebhookFormat::Json, &notification(), 42);
+        assert_eq!(json["event"], "done");
+        assert_eq!(json["agentKey"], "codex:s1");
+        assert_eq!(json["tsMs"], 42);
+        assert_eq!(json["priority"], "normal");
+        let slack = webhook_payload(WebhookFormat::Slack, &notification(), 42);
+        assert_eq!(slack["text"], "*Agent done*\nfix-billing - Completed");
+        let discord = webhook_payload(WebhookFormat::Discord, &notification(), 42);
+        assert_eq!(
+            discord["content"],
+            "**Agent done**\nfix-billing - Completed"
+        );
+    }
+
+    #[test]
+    fn signs_bodies_with_hmac_sha256() {
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_events_and_schemes() {
+        let mut webhook: Webhook =
+            serde_json::from_value(json!({ "url": "https://hooks.example.com/x" })).unwrap();
+        assert!(webhook.accepts("waiting"));
+        webhook.events = vec!["waiting".to_string()];
+        assert!(webhook.accepts("approval"));
+        webhook.events = default_webhook_events();
+        assert!(!webhook.accepts("limited"));
+        assert!(webhook.validate().is_ok());
+        webhook.events.push("pr_comment".to_string());
+        assert!(webhook.validate().is_err());
+        webhook.events.pop();
+        webhook.url = "ftp://example.com".to_string();
+        assert!(webhook.validate().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::error::Error;
+use crate::monitor::Monitor;
+use crate::paths::{windsurf_cascade_root, windsurf_workspace_storage_root};
+use crate::scan::{upsert_agent, AgentTemp, ScanCounts};
+use crate::settings::{read_json_file, ScanLimits};
+use crate::util::{collect_files, file_uri_to_path, modified_ms, string_at};
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+pub(crate) const MAX_WINDSURF_FILES: usize = 200;
+
+pub(crate) fn scan_windsurf_source(
+    state: &Monitor,
+    _limits: &ScanLimits,
+    counts: &mut ScanCounts,
+) -> Result<HashMap<String, AgentTemp>, Error> {
+    let mut cache = state
+        .windsurf_scan
+        .lock()
+        .map_err(|_| Error::lock("windsurf scan"))?;
+    Ok(cache.scan(&windsurf_cascade_root(), |_| {
+        let mut agents = HashMap::new();
+        scan_windsurf(&mut agents, counts);
+        counts.rescanned = true;
+        agents
+    }))
+}
+
+pub(crate) fn scan_windsurf(map: &mut HashMap<String, AgentTemp>, counts: &mut ScanCounts) {
+    let root = windsurf_cascade_root();
+    if !root.exists() {
+        return;
+    }
+
+    let files = collect_files(&root, "pb", MAX_WINDSURF_FILES);
+    counts.files = files.len();
+    let session_ids: Vec<String> = files
+        .iter()
+        .filter_map(|file| file.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+    let session_repo = load_windsurf_session_repo_map(&session_ids);
+
+    for file in files {
+        let Some(session_id) = file.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+            continue;
+        };
+        // Cascade conversations are stored encrypted, so neither the state nor
+        // the last message can be read; only the file's mtime is reliable.
+        let ts = modified_ms(&file);
+        upsert_agent(
+            map,
+            AgentTemp {
+                key: format!("windsurf:{}", session_id),
+                source: "windsurf".to_string(),
+                session_id: session_id.clone(),
+                agent_name: None,
+                state: "idle".to_string(),
+                started_ts_ms: ts,
+                last_ts_ms: ts,
+                last_text: None,
+                repo_path: session_repo.get(&session_id).cloned(),
+                recent_events: Vec::new(),
+            },
+        );
+    }
+}
+
+pub(crate) fn load_windsurf_session_repo_map(session_ids: &[String]) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    let storage_root = windsurf_workspace_storage_root();
+    if session_ids.is_empty() || !storage_root.exists() {
+        return out;
+    }
+
+    let entries = match fs::read_dir(&storage_root) {
+        Ok(v) => v,
+        Err(_) => return out,
+    };
+    let mut workspaces: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    workspaces.sort_by(|a, b| modified_ms(b).cmp(&modified_ms(a)));
+
+    for workspace in workspaces {
+        let folder = match read_json_file(&workspace.join("workspace.json")) {
+            Ok(value) => string_at(&value, &["folder"]).and_then(|uri| file_uri_to_path(&uri)),
+            Err(_) => None,
+        };
+        let Some(folder) = folder else {
+            continue;
+        };
+
+        let conn = match Connection::open_with_flags(
+            workspace.join("state.vscdb"),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        ) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let mut stmt = match conn.prepare(
+            "SELECT CAST(value AS TEXT)
+             FROM ItemTable
+             WHERE key LIKE '%cascade%' OR key LIKE '%windsurf%'",
+        ) {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        let rows = stmt.query_map([], |row| row.get::<_, Option<String>>(0));
+        let Ok(rows) = rows else {
+            continue;
+        };
+        let values: Vec<String> = rows.flatten().flatten().collect();
+        for session_id in session_ids {
+            if out.contains_key(session_id) {
+                continue;
+            }
+            if values
+                .iter()
+                .any(|value| value.contains(session_id.as_str()))
+            {
+                out.insert(session_id.clone(), folder.clone());
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,74 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum PixelAgentsError {
+    #[error(transparent)]
+    Core(#[from] pixel_agents_core::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Parse(#[from] serde_json::Error),
+    #[error("{0}")]
+    Window(String),
+    #[error("{0}")]
+    Launch(String),
+    #[error("{0}")]
+    Clipboard(String),
+    #[error("{0}")]
+    Invalid(String),
+    #[error("{0}")]
+    State(String),
+}
+
+impl PixelAgentsError {
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            Self::Core(error) => error.code(),
+            Self::Io(_) => "io",
+            Self::Parse(_) => "parse",
+            Self::Window(_) => "window",
+            Self::Launch(_) => "launch",
+            Self::Clipboard(_) => "clipboard",
+            Self::Invalid(_) => "invalid",
+            Self::State(_) => "state",
+        }
+    }
+
+    pub(crate) fn lock(name: &str) -> Self {
+        Self::State(format!("{} lock failed", name))
+    }
+}
+
+impl From<tauri::Error> for PixelAgentsError {
+    fn from(error: tauri::Error) -> Self {
+        Self::Window(error.to_string())
+    }
+}
+
+impl From<opener::OpenError> for PixelAgentsError {
+    fn from(error: opener::OpenError) -> Self {
+        Self::Launch(error.to_string())
+    }
+}
+
+impl From<tauri_plugin_autostart::Error> for PixelAgentsError {
+    fn from(error: tauri_plugin_autostart::Error) -> Self {
+        Self::State(error.to_string())
+    }
+}
+
+impl From<arboard::Error> for PixelAgentsError {
+    fn from(error: arboard::Error) -> Self {
+        Self::Clipboard(error.to_string())
+    }
+}
+
+impl Serialize for PixelAgentsError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("PixelAgentsError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
@@ -1,33 +1,83 @@
+mod error;
+mod logging;
+
 use arboard::Clipboard;
-use dirs::home_dir;
-use regex::Regex;
-use rusqlite::{Connection, OpenFlags};
-use serde::{Deserialize, Serialize};
-use serde_json::{json, Map, Value};
+use error::PixelAgentsError;
+use pixel_agents_core::layouts::{
+    list_layout_backups, list_layouts, read_active_layout_name, read_layout_backup,
+    read_named_layout, write_active_layout_name, write_layout, write_named_layout,
+};
+use pixel_agents_core::paths::{
+    agent_seats_file, claude_data_root, codex_sessions_root, layout_file, monitor_settings_file,
+    opencode_data_root, opencode_message_root, pixel_agents_dir, sound_settings_file,
+};
+use pixel_agents_core::prefs::{read_agent_prefs, update_agent_prefs, ArchivedAgent};
+use pixel_agents_core::settings::{
+    list_repo_bindings, quiet_mode_now, read_auto_check_updates, read_close_to_tray, read_data_dir,
+    read_demo_mode, read_editor_command, read_github_token, read_hotkeys, read_json_file,
+    read_last_update_check_ms, read_monitor_settings, read_monitor_settings_async,
+    read_notifications_snoozed_until, read_pip_geometry, read_quiet_hours,
+    read_repo_bindings_async, read_sound_enabled, remove_repo_binding, write_data_dir,
+    write_desktop_setting, write_desktop_setting_bool, write_hotkeys, write_json_file,
+    write_monitor_settings, write_pip_geometry, write_quiet_hours, write_repo_binding,
+};
+use pixel_agents_core::watch::DirWatcher;
+use pixel_agents_core::{
+    agent_key_from_link, append_notification_history, apply_quiet_hours, check_for_updates,
+    disabled_tick, dispatch_push, dispatch_webhooks, export_bundle, git, import_bundle,
+    normalize_source_name, now_ms, read_agent_events, read_notification_history,
+    read_push_settings, read_transcript, read_usage_stats, read_webhooks, render_session_usage,
+    render_snapshot, render_transcript, reset_data, search_sessions, send_push, send_webhook,
+    session_location, test_notification, write_push_settings, write_webhooks, AgentTemp,
+    DataDirView, Hotkeys, LayoutBackupView, LayoutListView, Monitor, MonitorEventView,
+    MonitorNotification, MonitorRepoView, MonitorSettings, MonitorSourceDiagnostics,
+    MonitorTickPayload, NotificationHistoryEntry, PipCorner, PipGeometry, PushSettings, QuietHours,
+    RepoBindingView, ResetReport, ResetScope, SessionSearchHit, SnapshotFormat, StatsFormat,
+    TranscriptEntry, TranscriptFormat, UpdateInfo, UsageRange, UsageStatsPayload, Webhook,
+};
+use serde::Serialize;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::{LogicalSize, Size, State, WebviewWindow};
-use walkdir::WalkDir;
-
-const IDLE_AFTER_MS: i64 = 20_000;
-const DONE_AFTER_MS: i64 = 90_000;
-const CODEX_TAIL_BYTES: usize = 65_536;
-const MAX_CODEX_FILES: usize = 120;
-const MAX_OPENCODE_FILES: usize = 800;
-const MAX_OPENCODE_PART_FILES: usize = 900;
-const MAX_OPENCODE_DB_SESSIONS: usize = 800;
-const MAX_OPENCODE_DB_PARTS: usize = 1500;
-const MAX_MONITOR_TEXT_CHARS: usize = 180;
+use std::time::Duration;
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{
+    AppHandle, Emitter, LogicalPosition, LogicalSize, Manager, Position, Size, State, WebviewUrl,
+    WebviewWindow, WebviewWindowBuilder, Window, WindowEvent,
+};
+use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+const CLI_PROBE_TTL_MS: i64 = 300_000;
+const AGENT_CLIS: [&str; 3] = ["claude", "opencode", "codex"];
+const MIN_MONITOR_LOOP_INTERVAL_MS: i64 = 500;
+const FULL_SNAPSHOT_EVERY_TICKS: u64 = 15;
 const PIP_WINDOW_WIDTH_PX: f64 = 560.0;
 const PIP_WINDOW_HEIGHT_PX: f64 = 360.0;
+const PIP_CORNER_MARGIN_PX: f64 = 16.0;
+const NOTIFICATION_HISTORY_PAGE: usize = 100;
+const AGENT_WINDOW_WIDTH_PX: f64 = 420.0;
+const AGENT_WINDOW_HEIGHT_PX: f64 = 520.0;
+const DASHBOARD_WINDOW_LABEL: &str = "dashboard";
+const AGENT_EVENTS_PAGE: usize = 100;
+const UPDATE_CHECK_INTERVAL_MS: i64 = 24 * 60 * 60 * 1000;
+const UPDATE_CHECK_POLL_SECS: u64 = 15 * 60;
+const AUTOSTART_ARG: &str = "--minimized";
+const HOTKEY_SNOOZE_MINUTES: i64 = 60;
+const TRAY_SHOW_ID: &str = "show";
+const TRAY_QUIT_ID: &str = "quit";
 
 #[derive(Clone, Copy)]
 struct PipWindowState {
+    logical_x: f64,
+    logical_y: f64,
     logical_width: f64,
     logical_height: f64,
     always_on_top: bool,
@@ -35,232 +85,310 @@ struct PipWindowState {
 
 #[derive(Default)]
 struct AppState {
-    previous_states: Mutex<HashMap<String, String>>,
+    monitor: Monitor,
     pip_window_state: Mutex<Option<PipWindowState>>,
+    monitor_tick_lock: tokio::sync::Mutex<()>,
+    cli_probes: Mutex<HashMap<String, CliProbe>>,
+    notifications_snoozed_until: Mutex<i64>,
+    pending_deep_link: Mutex<Option<String>>,
+    /// Set after the first tick, whose notifications only restate existing agent states.
+    remote_dispatch_primed: AtomicBool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct MonitorSettings {
-    enabled: bool,
-    #[serde(rename = "enableClaude", default = "default_enable_claude")]
-    enable_claude: bool,
-    #[serde(rename = "enableOpencode")]
-    enable_opencode: bool,
-    #[serde(rename = "enableCodex")]
-    enable_codex: bool,
-    #[serde(rename = "enableGit")]
-    enable_git: bool,
-    #[serde(rename = "enablePr")]
-    enable_pr: bool,
-    #[serde(rename = "flushIntervalMs")]
-    flush_interval_ms: i64,
-    #[serde(rename = "sourcePollIntervalMs")]
-    source_poll_interval_ms: i64,
-    #[serde(rename = "gitPollIntervalMs")]
-    git_poll_interval_ms: i64,
-    #[serde(rename = "prPollIntervalMs")]
-    pr_poll_interval_ms: i64,
-    #[serde(rename = "agentLabelFontPx", default = "default_agent_label_font_px")]
-    agent_label_font_px: i64,
-    #[serde(rename = "maxIdleAgents", default = "default_max_idle_agents")]
-    max_idle_agents: i64,
-}
-
-impl Default for MonitorSettings {
-    fn default() -> Self {
-        Self {
-            enabled: true,
-            enable_claude: default_enable_claude(),
-            enable_opencode: true,
-            enable_codex: true,
-            enable_git: true,
-            enable_pr: true,
-            flush_interval_ms: 1000,
-            source_poll_interval_ms: 2000,
-            git_poll_interval_ms: 20000,
-            pr_poll_interval_ms: 90000,
-            agent_label_font_px: default_agent_label_font_px(),
-            max_idle_agents: default_max_idle_agents(),
-        }
-    }
+#[derive(Debug, Clone)]
+struct CliProbe {
+    available: bool,
+    version: Option<String>,
+    checked_ms: i64,
 }
 
-fn default_enable_claude() -> bool {
-    true
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DetectedAgent {
+    name: String,
+    available: bool,
+    version: Option<String>,
+    data_dir: String,
+    data_dir_exists: bool,
+    monitored: bool,
 }
 
-fn default_agent_label_font_px() -> i64 {
-    24
+#[derive(Debug, Clone, Serialize)]
+struct BootstrapPayload {
+    layout: Value,
+    #[serde(rename = "soundEnabled")]
+    sound_enabled: bool,
+    #[serde(rename = "demoMode")]
+    demo_mode: bool,
+    #[serde(rename = "monitorSettings")]
+    monitor_settings: MonitorSettings,
+    #[serde(rename = "detectedAgents")]
+    detected_agents: Vec<DetectedAgent>,
+    #[serde(rename = "githubTokenConfigured")]
+    github_token_configured: bool,
+    #[serde(rename = "autostartEnabled")]
+    autostart_enabled: bool,
 }
 
-fn default_max_idle_agents() -> i64 {
-    3
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct SettingsChangedPayload {
+    #[serde(rename = "soundEnabled")]
+    sound_enabled: bool,
+    #[serde(rename = "demoMode")]
+    demo_mode: bool,
+    #[serde(rename = "monitorSettings")]
+    monitor_settings: MonitorSettings,
+    #[serde(rename = "githubTokenConfigured")]
+    github_token_configured: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
-struct MonitorAlert {
-    kind: String,
-    message: String,
-    ts_ms: i64,
+struct SecondInstancePayload {
+    args: Vec<String>,
+    cwd: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct MonitorEventView {
-    ts_ms: i64,
-    #[serde(rename = "type")]
-    event_type: String,
-    state_hint: String,
-    text: Option<String>,
-    files_touched: Vec<String>,
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_bootstrap(
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<BootstrapPayload, PixelAgentsError> {
+    Ok(BootstrapPayload {
+        layout: read_layout_or_default()?,
+        sound_enabled: read_sound_enabled(),
+        demo_mode: read_demo_mode(),
+        monitor_settings: read_monitor_settings(),
+        detected_agents: detected_agents(&state),
+        github_token_configured: read_github_token().is_some(),
+        autostart_enabled: app.autolaunch().is_enabled().unwrap_or(false),
+    })
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct MonitorAgentView {
-    key: String,
-    source: String,
-    session_id: String,
-    agent_id: String,
-    display_name: String,
-    state: String,
-    last_ts_ms: i64,
-    last_text: Option<String>,
-    repo_path: Option<String>,
-    files_touched: Vec<String>,
-    alerts: Vec<MonitorAlert>,
-    recent_events: Vec<MonitorEventView>,
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_save_layout(layout: Value) -> Result<(), PixelAgentsError> {
+    write_active_layout(&layout)
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct MonitorSummary {
-    total: usize,
-    active: usize,
-    waiting: usize,
-    done: usize,
-    error: usize,
-    pr_pending: usize,
-    alerts: usize,
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_read_layout() -> Result<Value, PixelAgentsError> {
+    read_layout_or_default()
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct MonitorSnapshot {
-    summary: MonitorSummary,
-    agents: Vec<MonitorAgentView>,
-    now_ms: i64,
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_list_layouts() -> Result<LayoutListView, PixelAgentsError> {
+    Ok(list_layouts())
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct MonitorNotification {
-    title: String,
-    message: String,
-    kind: String,
-    key: String,
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_save_layout_as(name: String) -> Result<LayoutListView, PixelAgentsError> {
+    let name = name.trim();
+    write_named_layout(name, &read_layout_or_default()?)?;
+    write_active_layout_name(name)?;
+    Ok(list_layouts())
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct MonitorTickPayload {
-    snapshot: MonitorSnapshot,
-    notifications: Vec<MonitorNotification>,
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_switch_layout(name: String) -> Result<Value, PixelAgentsError> {
+    let layout = read_named_layout(&name)?;
+    write_layout(&layout)?;
+    write_active_layout_name(&name)?;
+    Ok(layout)
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct BootstrapPayload {
-    layout: Value,
-    #[serde(rename = "soundEnabled")]
-    sound_enabled: bool,
-    #[serde(rename = "demoMode")]
-    demo_mode: bool,
-    #[serde(rename = "monitorSettings")]
-    monitor_settings: MonitorSettings,
-    #[serde(rename = "claudeAvailable")]
-    claude_available: bool,
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_list_layout_backups() -> Result<Vec<LayoutBackupView>, PixelAgentsError> {
+    Ok(list_layout_backups())
 }
 
-#[derive(Debug, Clone)]
-struct AgentTemp {
-    key: String,
-    source: String,
-    session_id: String,
-    agent_name: Option<String>,
-    state: String,
-    last_ts_ms: i64,
-    last_text: Option<String>,
-    repo_path: Option<String>,
-    recent_events: Vec<MonitorEventView>,
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_restore_layout_backup(index: usize) -> Result<Value, PixelAgentsError> {
+    let layout = read_layout_backup(index)?;
+    write_active_layout(&layout)?;
+    Ok(layout)
 }
 
 #[tauri::command]
-fn desktop_bootstrap() -> Result<BootstrapPayload, String> {
-    Ok(BootstrapPayload {
-        layout: read_layout_or_default()?,
-        sound_enabled: read_sound_enabled(),
-        demo_mode: read_demo_mode(),
-        monitor_settings: read_monitor_settings(),
-        claude_available: claude_available(),
-    })
+#[tracing::instrument(skip_all, err)]
+fn desktop_save_agent_seats(seats: Value) -> Result<(), PixelAgentsError> {
+    Ok(write_json_file(&agent_seats_file(), &seats)?)
 }
 
 #[tauri::command]
-fn desktop_save_layout(layout: Value) -> Result<(), String> {
-    write_json_file(&layout_file(), &layout)
+#[tracing::instrument(skip_all, err)]
+fn desktop_set_monitor_settings(settings: MonitorSettings) -> Result<(), PixelAgentsError> {
+    Ok(write_monitor_settings(&settings)?)
 }
 
 #[tauri::command]
-fn desktop_read_layout() -> Result<Value, String> {
-    read_layout_or_default()
+#[tracing::instrument(err)]
+fn desktop_set_sound_enabled(enabled: bool) -> Result<(), PixelAgentsError> {
+    Ok(write_desktop_setting_bool("soundEnabled", enabled)?)
 }
 
 #[tauri::command]
-fn desktop_save_agent_seats(seats: Value) -> Result<(), String> {
-    write_json_file(&agent_seats_file(), &seats)
+#[tracing::instrument(err)]
+fn desktop_set_demo_mode(enabled: bool) -> Result<(), PixelAgentsError> {
+    Ok(write_desktop_setting_bool("demoMode", enabled)?)
 }
 
 #[tauri::command]
-fn desktop_set_monitor_settings(settings: MonitorSettings) -> Result<(), String> {
-    write_json_file(
-        &monitor_settings_file(),
-        &serde_json::to_value(settings).map_err(|e| e.to_string())?,
-    )
+#[tracing::instrument(skip(app), err)]
+fn desktop_set_autostart(app: AppHandle, enabled: bool) -> Result<bool, PixelAgentsError> {
+    let autolaunch = app.autolaunch();
+    if enabled {
+        autolaunch.enable()?;
+    } else {
+        autolaunch.disable()?;
+    }
+    Ok(autolaunch.is_enabled()?)
+}
+
+#[tauri::command]
+fn desktop_get_hotkeys() -> Hotkeys {
+    read_hotkeys()
 }
 
 #[tauri::command]
-fn desktop_set_sound_enabled(enabled: bool) -> Result<(), String> {
-    write_desktop_setting_bool("soundEnabled", enabled)
+#[tracing::instrument(skip(app), err)]
+fn desktop_set_hotkeys(app: AppHandle, hotkeys: Hotkeys) -> Result<Hotkeys, PixelAgentsError> {
+    let mut seen = Vec::new();
+    for (action, shortcut) in hotkeys.bindings() {
+        if shortcut.is_empty() {
+            continue;
+        }
+        let parsed = Shortcut::from_str(shortcut).map_err(|e| {
+            PixelAgentsError::Invalid(format!("invalid shortcut for {}: {}", action, e))
+        })?;
+        if seen.contains(&parsed) {
+            return Err(PixelAgentsError::Invalid(format!(
+                "{} is bound to more than one action",
+                shortcut
+            )));
+        }
+        seen.push(parsed);
+    }
+    write_hotkeys(&hotkeys)?;
+    register_hotkeys(&app, &hotkeys);
+    Ok(hotkeys)
+}
+
+fn register_hotkeys(app: &AppHandle, hotkeys: &Hotkeys) {
+    let global_shortcut = app.global_shortcut();
+    if let Err(e) = global_shortcut.unregister_all() {
+        tracing::warn!(error = %e, "failed to clear hotkeys");
+    }
+    for (action, shortcut) in hotkeys.bindings() {
+        if shortcut.is_empty() {
+            continue;
+        }
+        let result = Shortcut::from_str(shortcut)
+            .map_err(|e| e.to_string())
+            .and_then(|parsed| global_shortcut.register(parsed).map_err(|e| e.to_string()));
+        if let Err(error) = result {
+            tracing::warn!(action, shortcut, %error, "failed to register hotkey");
+        }
+    }
+}
+
+fn handle_hotkey(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let hotkeys = read_hotkeys();
+    let Some((action, _)) = hotkeys
+        .bindings()
+        .into_iter()
+        .find(|(_, bound)| Shortcut::from_str(bound).ok().as_ref() == Some(shortcut))
+    else {
+        return;
+    };
+    match action {
+        "toggleWindow" => toggle_main_window(app),
+        "snoozeNotifications" => {
+            let state = app.state::<AppState>();
+            let snoozed = notifications_snoozed_until(&state)
+                .map(|until| until > now_ms())
+                .unwrap_or(false);
+            let minutes = if snoozed { 0 } else { HOTKEY_SNOOZE_MINUTES };
+            if let Err(e) = set_notification_snooze(&state, minutes) {
+                tracing::warn!(error = %e, "hotkey snooze failed");
+            }
+        }
+        _ => {}
+    }
+    let _ = app.emit("app://hotkey", action);
 }
 
 #[tauri::command]
-fn desktop_set_demo_mode(enabled: bool) -> Result<(), String> {
-    write_desktop_setting_bool("demoMode", enabled)
+#[tracing::instrument(skip_all, err)]
+fn desktop_set_github_token(token: Option<String>) -> Result<(), PixelAgentsError> {
+    let value = token
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .map(Value::String)
+        .unwrap_or(Value::Null);
+    Ok(write_desktop_setting("githubToken", value)?)
 }
 
 #[tauri::command]
+#[tracing::instrument(skip(state, window), err)]
 fn desktop_set_picture_in_picture(
     state: State<AppState>,
     window: WebviewWindow,
     enabled: bool,
-) -> Result<(), String> {
+    corner: Option<PipCorner>,
+) -> Result<(), PixelAgentsError> {
     if enabled {
         let mut lock = state
             .pip_window_state
             .lock()
-            .map_err(|_| "pip window state lock failed".to_string())?;
-        if lock.is_none() {
-            let size = window.inner_size().map_err(|e| e.to_string())?;
-            let scale_factor = window.scale_factor().map_err(|e| e.to_string())?;
-            let always_on_top = window.is_always_on_top().map_err(|e| e.to_string())?;
+            .map_err(|_| PixelAgentsError::lock("pip window state"))?;
+        if lock.is_some() {
+            save_pip_geometry(&window)?;
+        } else {
+            let size = window.inner_size()?;
+            let position = window.outer_position()?;
+            let scale_factor = window.scale_factor()?;
+            let always_on_top = window.is_always_on_top()?;
             *lock = Some(PipWindowState {
+                logical_x: f64::from(position.x) / scale_factor,
+                logical_y: f64::from(position.y) / scale_factor,
                 logical_width: f64::from(size.width) / scale_factor,
                 logical_height: f64::from(size.height) / scale_factor,
                 always_on_top,
             });
         }
         drop(lock);
-        window.set_always_on_top(true).map_err(|e| e.to_string())?;
-        window
-            .set_size(Size::Logical(LogicalSize::new(
-                PIP_WINDOW_WIDTH_PX,
-                PIP_WINDOW_HEIGHT_PX,
-            )))
-            .map_err(|e| e.to_string())?;
+        let saved = read_pip_geometry();
+        let (width, height) = saved
+            .map(|geometry| (geometry.width, geometry.height))
+            .unwrap_or((PIP_WINDOW_WIDTH_PX, PIP_WINDOW_HEIGHT_PX));
+        window.set_always_on_top(true)?;
+        window.set_size(Size::Logical(LogicalSize::new(width, height)))?;
+        let position = match corner {
+            Some(corner) => window.current_monitor()?.map(|monitor| {
+                let scale_factor = monitor.scale_factor();
+                let area = [
+                    f64::from(monitor.position().x) / scale_factor,
+                    f64::from(monitor.position().y) / scale_factor,
+                    f64::from(monitor.size().width) / scale_factor,
+                    f64::from(monitor.size().height) / scale_factor,
+                ];
+                corner.place(area, width, height, PIP_CORNER_MARGIN_PX)
+            }),
+            None => saved.map(|geometry| (geometry.x, geometry.y)),
+        };
+        if let Some((x, y)) = position {
+            window.set_position(Position::Logical(LogicalPosition::new(x, y)))?;
+        }
         return Ok(());
     }
 
@@ -268,34 +396,143 @@ fn desktop_set_picture_in_picture(
         let mut lock = state
             .pip_window_state
             .lock()
-            .map_err(|_| "pip window state lock failed".to_string())?;
+            .map_err(|_| PixelAgentsError::lock("pip window state"))?;
         lock.take()
     };
 
     if let Some(previous) = previous {
-        window
-            .set_size(Size::Logical(LogicalSize::new(
-                previous.logical_width,
-                previous.logical_height,
-            )))
-            .map_err(|e| e.to_string())?;
-        window
-            .set_always_on_top(previous.always_on_top)
-            .map_err(|e| e.to_string())?;
+        if let Err(e) = save_pip_geometry(&window) {
+            tracing::warn!(error = %e, "failed to save pip geometry");
+        }
+        window.set_size(Size::Logical(LogicalSize::new(
+            previous.logical_width,
+            previous.logical_height,
+        )))?;
+        window.set_position(Position::Logical(LogicalPosition::new(
+            previous.logical_x,
+            previous.logical_y,
+        )))?;
+        window.set_always_on_top(previous.always_on_top)?;
         return Ok(());
     }
 
-    window.set_always_on_top(false).map_err(|e| e.to_string())
+    Ok(window.set_always_on_top(false)?)
+}
+
+fn save_pip_geometry(window: &WebviewWindow) -> Result<(), PixelAgentsError> {
+    let size = window.inner_size()?;
+    let position = window.outer_position()?;
+    let scale_factor = window.scale_factor()?;
+    Ok(write_pip_geometry(&PipGeometry {
+        x: f64::from(position.x) / scale_factor,
+        y: f64::from(position.y) / scale_factor,
+        width: f64::from(size.width) / scale_factor,
+        height: f64::from(size.height) / scale_factor,
+    })?)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app), err)]
+async fn desktop_open_agent_window(
+    app: AppHandle,
+    agent_key: String,
+) -> Result<(), PixelAgentsError> {
+    if agent_key.split_once(':').is_none() {
+        return Err(PixelAgentsError::Invalid(format!(
+            "invalid agent key: {}",
+            agent_key
+        )));
+    }
+    let label = format!(
+        "agent-{}",
+        agent_key
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+    );
+    if let Some(window) = app.get_webview_window(&label) {
+        window.unminimize()?;
+        window.show()?;
+        window.set_focus()?;
+        return Ok(());
+    }
+    let url = format!("index.html?view=agent&key={}", url_query_escape(&agent_key));
+    WebviewWindowBuilder::new(&app, label, WebviewUrl::App(url.into()))
+        .title(format!("Pixel Agents - {}", agent_key))
+        .inner_size(AGENT_WINDOW_WIDTH_PX, AGENT_WINDOW_HEIGHT_PX)
+        .always_on_top(true)
+        .build()?;
+    Ok(())
 }
 
 #[tauri::command]
-fn desktop_bind_repo(source: String, session_id: String, repo_path: String) -> Result<(), String> {
-    let mut bindings = read_repo_bindings();
-    bindings.insert(format!("{}:{}", source, session_id), repo_path);
-    write_json_file(
-        &repo_bindings_file(),
-        &serde_json::to_value(bindings).map_err(|e| e.to_string())?,
+#[tracing::instrument(skip_all, err)]
+async fn desktop_open_dashboard_window(app: AppHandle) -> Result<(), PixelAgentsError> {
+    if let Some(window) = app.get_webview_window(DASHBOARD_WINDOW_LABEL) {
+        window.show()?;
+        window.set_focus()?;
+        return Ok(());
+    }
+    WebviewWindowBuilder::new(
+        &app,
+        DASHBOARD_WINDOW_LABEL,
+        WebviewUrl::App("index.html?view=dashboard".into()),
     )
+    .title("Pixel Agents - Dashboard")
+    .fullscreen(true)
+    .build()?;
+    Ok(())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_close_dashboard_window(app: AppHandle) -> Result<(), PixelAgentsError> {
+    if let Some(window) = app.get_webview_window(DASHBOARD_WINDOW_LABEL) {
+        window.close()?;
+    }
+    Ok(())
+}
+
+fn url_query_escape(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_bind_repo(
+    source: String,
+    session_id: String,
+    repo_path: String,
+) -> Result<(), PixelAgentsError> {
+    Ok(write_repo_binding(
+        &format!("{}:{}", source, session_id),
+        &repo_path,
+    )?)
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_unbind_repo(source: String, session_id: String) -> Result<bool, PixelAgentsError> {
+    Ok(remove_repo_binding(&format!("{}:{}", source, session_id))?)
+}
+
+#[tauri::command]
+fn desktop_list_repo_bindings() -> Vec<RepoBindingView> {
+    list_repo_bindings()
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_list_repos(state: State<AppState>) -> Result<Vec<MonitorRepoView>, PixelAgentsError> {
+    Ok(state.monitor.list_repos()?)
 }
 
 #[tauri::command]
@@ -312,25 +549,333 @@ fn desktop_sessions_folder() -> Option<String> {
 }
 
 #[tauri::command]
-fn desktop_open_path(path: String) -> Result<(), String> {
-    opener::open(path).map_err(|e| e.to_string())
+#[tracing::instrument(err)]
+async fn desktop_reveal_session(
+    source: String,
+    session_id: String,
+) -> Result<String, PixelAgentsError> {
+    let path = tauri::async_runtime::spawn_blocking(move || session_location(&source, &session_id))
+        .await
+        .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    opener::reveal(&path)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_open_path(path: String) -> Result<(), PixelAgentsError> {
+    Ok(opener::open(path)?)
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_open_in_editor(repo_path: String, file: Option<String>) -> Result<(), PixelAgentsError> {
+    if !Path::new(&repo_path).is_dir() {
+        return Err(PixelAgentsError::Invalid(format!(
+            "{} is not a directory",
+            repo_path
+        )));
+    }
+    let file = match file.filter(|file| !file.trim().is_empty()) {
+        Some(file) => {
+            let root = fs::canonicalize(&repo_path)?;
+            let path = Path::new(&repo_path).join(&file);
+            let inside = fs::canonicalize(&path)
+                .ok()
+                .is_some_and(|resolved| resolved.starts_with(&root));
+            if !inside {
+                return Err(PixelAgentsError::Invalid(format!(
+                    "{} is not a file in {}",
+                    file, repo_path
+                )));
+            }
+            Some(path.to_string_lossy().into_owned())
+        }
+        None => None,
+    };
+    let args = editor_command_args(&read_editor_command(), &repo_path, file.as_deref());
+    let Some((program, args)) = args.split_first() else {
+        return Err(PixelAgentsError::Invalid(
+            "Editor command is empty".to_string(),
+        ));
+    };
+    // Spawned directly, never through `cmd /C`, so `&`, `|` or `^` in a path
+    // stay literal; std escapes arguments for `.cmd` shims itself.
+    let program_path = if cfg!(target_os = "windows") {
+        resolve_windows_program(program).unwrap_or_else(|| PathBuf::from(program))
+    } else {
+        PathBuf::from(program)
+    };
+    Command::new(program_path)
+        .args(args)
+        .current_dir(&repo_path)
+        .spawn()
+        .map_err(|e| PixelAgentsError::Launch(format!("{}: {}", program, e)))?;
+    Ok(())
+}
+
+/// Finds `program` on PATH the way cmd would, trying each PATHEXT extension,
+/// so shims such as `code.cmd` can be spawned without a shell.
+fn resolve_windows_program(program: &str) -> Option<PathBuf> {
+    let names: Vec<String> = if Path::new(program).extension().is_some() {
+        vec![program.to_string()]
+    } else {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+            .split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(|ext| format!("{}{}", program, ext))
+            .collect()
+    };
+    if Path::new(program).components().count() > 1 {
+        return names
+            .into_iter()
+            .map(PathBuf::from)
+            .find(|candidate| candidate.is_file());
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?).find_map(|dir| {
+        names
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+fn editor_command_args(template: &str, repo_path: &str, file: Option<&str>) -> Vec<String> {
+    let template = if template.contains('{') {
+        template.to_string()
+    } else {
+        format!("{} {{repo}} {{file}}", template)
+    };
+    template
+        .split_whitespace()
+        .filter(|arg| file.is_some() || !arg.contains("{file}"))
+        .map(|arg| {
+            arg.replace("{repo}", repo_path)
+                .replace("{file}", file.unwrap_or_default())
+        })
+        .collect()
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_set_editor_command(command: String) -> Result<(), PixelAgentsError> {
+    let command = command.trim();
+    let value = if command.is_empty() {
+        Value::Null
+    } else {
+        Value::String(command.to_string())
+    };
+    Ok(write_desktop_setting("editorCommand", value)?)
+}
+
+#[tauri::command]
+fn desktop_get_editor_command() -> String {
+    read_editor_command()
+}
+
+#[tauri::command]
+fn desktop_get_data_dir() -> DataDirView {
+    read_data_dir()
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_set_data_dir(dir: Option<String>) -> Result<DataDirView, PixelAgentsError> {
+    write_data_dir(dir.as_deref())?;
+    Ok(read_data_dir())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+async fn desktop_check_for_updates() -> Result<UpdateInfo, PixelAgentsError> {
+    let info = tauri::async_runtime::spawn_blocking(check_updates_now)
+        .await
+        .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(info)
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_set_auto_check_updates(enabled: bool) -> Result<(), PixelAgentsError> {
+    Ok(write_desktop_setting_bool("autoCheckUpdates", enabled)?)
+}
+
+#[tauri::command]
+fn desktop_get_auto_check_updates() -> bool {
+    read_auto_check_updates()
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_set_close_to_tray(enabled: bool) -> Result<(), PixelAgentsError> {
+    Ok(write_desktop_setting_bool("closeToTray", enabled)?)
+}
+
+#[tauri::command]
+fn desktop_get_close_to_tray() -> bool {
+    read_close_to_tray()
+}
+
+fn check_updates_now() -> Result<UpdateInfo, PixelAgentsError> {
+    let info = check_for_updates(env!("CARGO_PKG_VERSION"))?;
+    write_desktop_setting("lastUpdateCheckMs", Value::from(now_ms()))?;
+    Ok(info)
+}
+
+async fn run_update_check_loop(app: AppHandle) {
+    loop {
+        if read_auto_check_updates()
+            && now_ms() - read_last_update_check_ms() >= UPDATE_CHECK_INTERVAL_MS
+        {
+            match tauri::async_runtime::spawn_blocking(check_updates_now).await {
+                Ok(Ok(info)) if info.update_available => {
+                    tracing::info!(version = %info.latest_version, "update available");
+                    let snoozed = notifications_snoozed_until(&app.state::<AppState>())
+                        .map(|until| until > now_ms())
+                        .unwrap_or(false);
+                    let notifications = apply_quiet_hours(
+                        vec![MonitorNotification::new(
+                            "Update available",
+                            format!(
+                                "Pixel Agents {} - see Settings > Updates",
+                                info.latest_version
+                            ),
+                            "update".to_string(),
+                            "app:update".to_string(),
+                        )],
+                        quiet_mode_now(),
+                    );
+                    if !snoozed && !notifications.is_empty() {
+                        let _ = app.emit(
+                            "monitor://tick",
+                            MonitorTickPayload {
+                                snapshot: None,
+                                delta: None,
+                                notifications,
+                                history: Vec::new(),
+                                remote_notifications: Vec::new(),
+                            },
+                        );
+                    }
+                }
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::warn!(error = %e, "update check failed"),
+                Err(e) => tracing::warn!(error = %e, "update check task failed"),
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(UPDATE_CHECK_POLL_SECS)).await;
+    }
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_open_url(url: String) -> Result<(), PixelAgentsError> {
+    Ok(opener::open(url)?)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+fn desktop_archive_agent(
+    state: State<AppState>,
+    agent_key: String,
+) -> Result<ArchivedAgent, PixelAgentsError> {
+    Ok(state.monitor.archive_agent(&agent_key)?)
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_unarchive_agent(agent_key: String) -> Result<(), PixelAgentsError> {
+    Ok(update_agent_prefs(|prefs| {
+        prefs.archived.retain(|agent| agent.key != agent_key)
+    })?)
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_pin_agent(agent_key: String, pinned: bool) -> Result<(), PixelAgentsError> {
+    Ok(update_agent_prefs(|prefs| {
+        prefs.set_pinned(&agent_key, pinned)
+    })?)
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_dismiss_alert(agent_key: String, ts_ms: i64) -> Result<(), PixelAgentsError> {
+    Ok(update_agent_prefs(|prefs| {
+        prefs.dismiss_alert(&agent_key, ts_ms)
+    })?)
+}
+
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_set_agent_muted(agent_key: String, muted: bool) -> Result<(), PixelAgentsError> {
+    Ok(update_agent_prefs(|prefs| {
+        prefs.set_muted(&agent_key, muted)
+    })?)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+fn desktop_set_agent_name(
+    state: State<AppState>,
+    agent_key: String,
+    name: Option<String>,
+) -> Result<(), PixelAgentsError> {
+    Ok(state.monitor.set_agent_name(&agent_key, name.as_deref())?)
 }
 
 #[tauri::command]
-fn desktop_open_url(url: String) -> Result<(), String> {
-    opener::open(url).map_err(|e| e.to_string())
+#[tracing::instrument(skip(text), err)]
+fn desktop_set_agent_note(agent_key: String, text: Option<String>) -> Result<(), PixelAgentsError> {
+    Ok(update_agent_prefs(|prefs| {
+        prefs.set_note(&agent_key, text.as_deref())
+    })?)
 }
 
 #[tauri::command]
-fn desktop_launch_agent(source: String, cwd: Option<String>) -> Result<(), String> {
+#[tracing::instrument(err)]
+fn desktop_list_archived_agents() -> Result<Vec<ArchivedAgent>, PixelAgentsError> {
+    Ok(read_agent_prefs().archived)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+fn desktop_launch_agent(
+    state: State<AppState>,
+    source: String,
+    cwd: Option<String>,
+) -> Result<(), PixelAgentsError> {
+    launch_agent(&state, source, cwd)
+}
+
+fn launch_agent(
+    state: &AppState,
+    source: String,
+    cwd: Option<String>,
+) -> Result<(), PixelAgentsError> {
     let normalized = source.trim().to_lowercase();
     let command = match normalized.as_str() {
         "claude" => "claude",
         "opencode" => "opencode",
         "codex" => "codex",
-        _ => return Err("Unknown agent source".to_string()),
+        _ => {
+            return Err(PixelAgentsError::Invalid(
+                "Unknown agent source".to_string(),
+            ))
+        }
     };
 
+    #[cfg(not(target_os = "macos"))]
+    {
+        if !cli_available(state, command) {
+            return Err(PixelAgentsError::Launch(format!(
+                "Command `{}` not found in PATH",
+                command
+            )));
+        }
+    }
+
     let resolved_cwd = cwd
         .filter(|value| !value.trim().is_empty())
         .unwrap_or_else(|| {
@@ -338,27 +883,50 @@ fn desktop_launch_agent(source: String, cwd: Option<String>) -> Result<(), Strin
                 .map(|path| path.to_string_lossy().into_owned())
                 .unwrap_or_else(|_| ".".to_string())
         });
+    open_terminal(state, &resolved_cwd, Some(command))
+}
 
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+fn desktop_open_terminal_at(state: State<AppState>, path: String) -> Result<(), PixelAgentsError> {
+    if !Path::new(&path).is_dir() {
+        return Err(PixelAgentsError::Invalid(format!(
+            "{} is not a directory",
+            path
+        )));
+    }
+    open_terminal(&state, &path, None)
+}
+
+#[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(unused_variables))]
+fn open_terminal(
+    state: &AppState,
+    cwd: &str,
+    command: Option<&str>,
+) -> Result<(), PixelAgentsError> {
     #[cfg(target_os = "windows")]
     {
-        if !command_available(command) {
-            return Err(format!("Command `{}` not found in PATH", command));
+        // The path is never part of a command line; `start` inherits the directory.
+        let mut args = vec!["/C", "start", "cmd"];
+        if let Some(command) = command {
+            args.extend(["/K", command]);
         }
-
-        let cwd = resolved_cwd.replace('"', "\\\"");
-        let launch_cmd = format!("cd \"{}\" && {}", cwd, command);
-
         Command::new("cmd")
-            .args(["/C", "start", "cmd", "/K", &launch_cmd])
+            .args(args)
+            .current_dir(cwd)
             .spawn()
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| PixelAgentsError::Launch(e.to_string()))?;
         return Ok(());
     }
 
     #[cfg(target_os = "macos")]
     {
-        let escaped_cwd = resolved_cwd.replace('\\', "\\\\").replace('"', "\\\"");
-        let launch_cmd = format!("cd \"{}\" && {}", escaped_cwd, command);
+        // Single quotes keep the shell from expanding `$(...)` or backticks in the path.
+        let quoted_cwd = format!("'{}'", cwd.replace('\'', "'\\''"));
+        let launch_cmd = match command {
+            Some(command) => format!("cd {} && {}", quoted_cwd, command),
+            None => format!("cd {}", quoted_cwd),
+        };
         let escaped_command = launch_cmd.replace('\\', "\\\\").replace('"', "\\\"");
         let script = format!(
             "tell application \"Terminal\" to activate\ntell application \"Terminal\" to do script \"{}\"",
@@ -367,18 +935,17 @@ fn desktop_launch_agent(source: String, cwd: Option<String>) -> Result<(), Strin
         Command::new("osascript")
             .args(["-e", &script])
             .spawn()
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| PixelAgentsError::Launch(e.to_string()))?;
         return Ok(());
     }
 
     #[cfg(all(unix, not(target_os = "macos")))]
     {
-        if !command_available(command) {
-            return Err(format!("Command `{}` not found in PATH", command));
-        }
-
-        let cwd = resolved_cwd.replace('"', "\\\"");
-        let launch_cmd = format!("cd \"{}\" && {}", cwd, command);
+        // The path goes in as `$1` so the shell never parses it.
+        let launch_cmd = match command {
+            Some(command) => format!("cd -- \"$1\" && {}", command),
+            None => "cd -- \"$1\" && exec \"${SHELL:-sh}\"".to_string(),
+        };
 
         let mut launched = false;
         for candidate in [
@@ -388,13 +955,23 @@ fn desktop_launch_agent(source: String, cwd: Option<String>) -> Result<(), Strin
                 "sh",
                 "-lc",
                 launch_cmd.as_str(),
+                "sh",
+                cwd,
+            ],
+            vec![
+                "gnome-terminal",
+                "--",
+                "sh",
+                "-lc",
+                launch_cmd.as_str(),
+                "sh",
+                cwd,
             ],
-            vec!["gnome-terminal", "--", "sh", "-lc", launch_cmd.as_str()],
-            vec!["konsole", "-e", "sh", "-lc", launch_cmd.as_str()],
-            vec!["xterm", "-e", "sh", "-lc", launch_cmd.as_str()],
+            vec!["konsole", "-e", "sh", "-lc", launch_cmd.as_str(), "sh", cwd],
+            vec!["xterm", "-e", "sh", "-lc", launch_cmd.as_str(), "sh", cwd],
         ] {
             let program = candidate[0];
-            if !command_available(program) {
+            if !cli_available(state, program) {
                 continue;
             }
             let args: Vec<&str> = candidate[1..].to_vec();
@@ -406,1272 +983,744 @@ fn desktop_launch_agent(source: String, cwd: Option<String>) -> Result<(), Strin
         if launched {
             return Ok(());
         }
-        return Err("No supported terminal emulator found".to_string());
+        return Err(PixelAgentsError::Launch(
+            "No supported terminal emulator found".to_string(),
+        ));
     }
 
     #[allow(unreachable_code)]
-    Err("Unsupported platform".to_string())
+    Err(PixelAgentsError::Launch("Unsupported platform".to_string()))
 }
 
 #[tauri::command]
-fn desktop_choose_repo_folder() -> Option<String> {
-    rfd::FileDialog::new()
-        .pick_folder()
-        .map(|p| p.to_string_lossy().into_owned())
+#[tracing::instrument(skip(state), err)]
+fn desktop_launch_agent_worktree(
+    state: State<AppState>,
+    source: String,
+    repo_path: String,
+    branch_name: String,
+) -> Result<String, PixelAgentsError> {
+    let normalized = normalize_source_name(&source);
+    let worktree_path = git::add_worktree(&repo_path, branch_name.trim())?;
+    launch_agent(&state, normalized.clone(), Some(worktree_path.clone()))?;
+    state
+        .monitor
+        .bind_pending(normalized, worktree_path.clone())?;
+    Ok(worktree_path)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+fn desktop_mark_pr_ready(
+    state: State<AppState>,
+    agent_key: String,
+) -> Result<String, PixelAgentsError> {
+    Ok(state.monitor.mark_pr_ready(&agent_key)?)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+fn desktop_open_pr(state: State<AppState>, agent_key: String) -> Result<String, PixelAgentsError> {
+    let url = state.monitor.pr_url(&agent_key)?;
+    opener::open(&url)?;
+    Ok(url)
+}
+
+#[tauri::command]
+fn desktop_get_logs(tail_lines: usize) -> Result<String, PixelAgentsError> {
+    logging::tail(tail_lines)
+}
+
+#[tauri::command]
+fn desktop_choose_repo_folder() -> Option<String> {
+    rfd::FileDialog::new()
+        .pick_folder()
+        .map(|p| p.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_copy_text(text: String) -> Result<(), PixelAgentsError> {
+    let mut clipboard = Clipboard::new()?;
+    Ok(clipboard.set_text(text)?)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_read_clipboard_text() -> Result<Option<String>, PixelAgentsError> {
+    let mut clipboard = Clipboard::new()?;
+    match clipboard.get_text() {
+        Ok(text) => Ok(Some(text)),
+        Err(arboard::Error::ContentNotAvailable) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
 }
 
 #[tauri::command]
-fn desktop_copy_text(text: String) -> Result<(), String> {
-    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
-    clipboard.set_text(text).map_err(|e| e.to_string())
+#[tracing::instrument(skip(state), err)]
+fn desktop_copy_snapshot(
+    state: State<AppState>,
+    format: SnapshotFormat,
+) -> Result<(), PixelAgentsError> {
+    let text = render_snapshot(&state.monitor.snapshot()?, format)?;
+    let mut clipboard = Clipboard::new()?;
+    Ok(clipboard.set_text(text)?)
 }
 
 #[tauri::command]
-fn desktop_export_layout() -> Result<(), String> {
+#[tracing::instrument(skip_all, err)]
+fn desktop_export_layout() -> Result<(), PixelAgentsError> {
     let save = rfd::FileDialog::new()
         .set_file_name("pixel-agents-layout.json")
         .save_file();
     if let Some(path) = save {
         let layout = read_layout_or_default()?;
-        let text = serde_json::to_string_pretty(&layout).map_err(|e| e.to_string())?;
-        fs::write(path, text).map_err(|e| e.to_string())?;
+        let text = serde_json::to_string_pretty(&layout)?;
+        fs::write(path, text)?;
     }
     Ok(())
 }
 
 #[tauri::command]
-fn desktop_import_layout() -> Result<Option<Value>, String> {
+#[tracing::instrument(skip_all, err)]
+fn desktop_export_bundle() -> Result<(), PixelAgentsError> {
+    let save = rfd::FileDialog::new()
+        .add_filter("json", &["json"])
+        .set_file_name("pixel-agents-bundle.json")
+        .save_file();
+    if let Some(path) = save {
+        let text = serde_json::to_string_pretty(&export_bundle()?)?;
+        fs::write(path, text)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_import_bundle(
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<Option<Vec<String>>, PixelAgentsError> {
     let file = rfd::FileDialog::new()
         .add_filter("json", &["json"])
         .pick_file();
     let Some(path) = file else {
         return Ok(None);
     };
-    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let parsed: Value = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
-    if parsed.get("version").and_then(Value::as_i64) != Some(1) {
-        return Err("Invalid layout version".to_string());
-    }
-    if !parsed.get("tiles").map(|v| v.is_array()).unwrap_or(false) {
-        return Err("Invalid layout tiles".to_string());
-    }
-    write_json_file(&layout_file(), &parsed)?;
-    Ok(Some(parsed))
+    let bundle: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let restored = import_bundle(&bundle)?;
+    register_hotkeys(&app, &read_hotkeys());
+    state.monitor.reset_scans()?;
+    Ok(Some(restored))
 }
 
 #[tauri::command]
-fn desktop_monitor_tick(state: State<AppState>) -> Result<MonitorTickPayload, String> {
-    let settings = read_monitor_settings();
-    if !settings.enabled {
-        let snapshot = MonitorSnapshot {
-            summary: MonitorSummary {
-                total: 0,
-                active: 0,
-                waiting: 0,
-                done: 0,
-                error: 0,
-                pr_pending: 0,
-                alerts: 0,
-            },
-            agents: Vec::new(),
-            now_ms: now_ms(),
-        };
-        return Ok(MonitorTickPayload {
-            snapshot,
-            notifications: Vec::new(),
-        });
+#[tracing::instrument(err)]
+fn desktop_export_transcript(
+    source: String,
+    session_id: String,
+    format: TranscriptFormat,
+) -> Result<(), PixelAgentsError> {
+    let entries = read_transcript(&source, &session_id)?;
+    let extension = format.extension();
+    let save = rfd::FileDialog::new()
+        .add_filter(extension, &[extension])
+        .set_file_name(format!(
+            "{}-{}.{}",
+            normalize_source_name(&source),
+            session_id,
+            extension
+        ))
+        .save_file();
+    if let Some(path) = save {
+        fs::write(
+            path,
+            render_transcript(&source, &session_id, &entries, format),
+        )?;
     }
+    Ok(())
+}
 
-    let mut map: HashMap<String, AgentTemp> = HashMap::new();
-    if settings.enable_opencode {
-        scan_opencode(&mut map);
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_import_layout() -> Result<Option<Value>, PixelAgentsError> {
+    let file = rfd::FileDialog::new()
+        .add_filter("json", &["json"])
+        .pick_file();
+    let Some(path) = file else {
+        return Ok(None);
+    };
+    let raw = fs::read_to_string(path)?;
+    let parsed: Value = serde_json::from_str(&raw)?;
+    if parsed.get("version").and_then(Value::as_i64) != Some(1) {
+        return Err(PixelAgentsError::Invalid(
+            "Invalid layout version".to_string(),
+        ));
     }
-    if settings.enable_codex {
-        scan_codex(&mut map);
+    if !parsed.get("tiles").map(|v| v.is_array()).unwrap_or(false) {
+        return Err(PixelAgentsError::Invalid(
+            "Invalid layout tiles".to_string(),
+        ));
     }
+    write_active_layout(&parsed)?;
+    Ok(Some(parsed))
+}
 
-    map.retain(|_, agent| {
-        let source = normalize_source_name(&agent.source);
-        if source == "claude" {
-            return settings.enable_claude;
-        }
-        true
-    });
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+async fn desktop_monitor_tick(app: AppHandle) -> Result<MonitorTickPayload, PixelAgentsError> {
+    monitor_tick(app, true).await
+}
 
-    let repo_bindings = read_repo_bindings();
-    for agent in map.values_mut() {
-        if agent.repo_path.is_none() {
-            if let Some(path) = repo_bindings.get(&agent.key) {
-                agent.repo_path = Some(path.clone());
-            }
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+async fn desktop_monitor_deep_scan(app: AppHandle) -> Result<MonitorTickPayload, PixelAgentsError> {
+    app.state::<AppState>().monitor.reset_scans()?;
+    monitor_tick(app, true).await
+}
+
+async fn run_monitor_loop(app: AppHandle) {
+    let mut tick: u64 = 0;
+    let mut settings_watcher: Option<DirWatcher> = None;
+    let mut last_settings = read_settings_changed_payload();
+    loop {
+        if settings_watcher.is_none() {
+            settings_watcher = DirWatcher::shallow(&pixel_agents_dir());
         }
-    }
-
-    let now = now_ms();
-    let mut agents: Vec<MonitorAgentView> = map
-        .into_values()
-        .map(|mut a| {
-            let silence = now - a.last_ts_ms;
-            if (a.state == "running" || a.state == "thinking" || a.state == "waiting")
-                && silence > IDLE_AFTER_MS
-            {
-                a.state = "idle".to_string();
-                if a.last_text.as_deref() == Some("Thinking") {
-                    a.last_text = Some("Idle".to_string());
-                }
-            }
-            if a.state == "idle" && silence > DONE_AFTER_MS {
-                a.state = "done".to_string();
-                if a.last_text.is_none()
-                    || a.last_text.as_deref() == Some("Idle")
-                    || a.last_text.as_deref() == Some("Thinking")
-                {
-                    a.last_text = Some("No recent activity".to_string());
+        if let Some(watcher) = &settings_watcher {
+            let changes = watcher.take_changes();
+            let watched = [monitor_settings_file(), sound_settings_file()];
+            let touched = changes.paths.iter().any(|path| {
+                watched
+                    .iter()
+                    .any(|file| path.file_name() == file.file_name())
+            });
+            if changes.full_rescan || touched {
+                let settings = read_settings_changed_payload();
+                if settings != last_settings {
+                    let _ = app.emit("settings://changed", &settings);
+                    last_settings = settings;
                 }
             }
-
-            let alerts = if a.state == "error" {
-                vec![MonitorAlert {
-                    kind: "error".to_string(),
-                    message: a
-                        .last_text
-                        .clone()
-                        .unwrap_or_else(|| "Error detected".to_string()),
-                    ts_ms: a.last_ts_ms,
-                }]
-            } else {
-                Vec::new()
-            };
-
-            MonitorAgentView {
-                key: a.key.clone(),
-                source: normalize_source_name(&a.source),
-                session_id: a.session_id.clone(),
-                agent_id: a.session_id.clone(),
-                display_name: format_agent_display_name(
-                    &a.source,
-                    &a.session_id,
-                    a.agent_name.as_deref(),
-                    a.repo_path.as_deref(),
-                ),
-                state: a.state.clone(),
-                last_ts_ms: a.last_ts_ms,
-                last_text: a.last_text.clone(),
-                repo_path: a.repo_path.clone(),
-                files_touched: Vec::new(),
-                alerts,
-                recent_events: a.recent_events.clone(),
+        }
+        let full_snapshot = tick.is_multiple_of(FULL_SNAPSHOT_EVERY_TICKS);
+        tick += 1;
+        let interval_ms = read_monitor_settings_async()
+            .await
+            .source_poll_interval_ms
+            .max(MIN_MONITOR_LOOP_INTERVAL_MS);
+        match monitor_tick(app.clone(), full_snapshot).await {
+            Ok(payload) => {
+                let _ = app.emit("monitor://tick", payload);
             }
-        })
-        .collect();
-
-    agents.sort_by(|a, b| b.last_ts_ms.cmp(&a.last_ts_ms));
-
-    let summary = MonitorSummary {
-        total: agents.len(),
-        active: agents
-            .iter()
-            .filter(|a| a.state == "running" || a.state == "thinking")
-            .count(),
-        waiting: agents.iter().filter(|a| a.state == "waiting").count(),
-        done: agents.iter().filter(|a| a.state == "done").count(),
-        error: agents.iter().filter(|a| a.state == "error").count(),
-        pr_pending: 0,
-        alerts: agents.iter().map(|a| a.alerts.len()).sum(),
-    };
-
-    let snapshot = MonitorSnapshot {
-        summary,
-        agents: agents.clone(),
-        now_ms: now,
-    };
-
-    let mut notifications = Vec::new();
-    let mut lock = state
-        .previous_states
-        .lock()
-        .map_err(|_| "state lock failed".to_string())?;
-    let mut next_states: HashMap<String, String> = HashMap::new();
-    for agent in &agents {
-        next_states.insert(agent.key.clone(), agent.state.clone());
-        if (agent.state == "done" || agent.state == "error")
-            && lock.get(&agent.key) != Some(&agent.state)
-        {
-            notifications.push(MonitorNotification {
-                title: if agent.state == "error" {
-                    "Agent error".to_string()
-                } else {
-                    "Agent done".to_string()
-                },
-                message: format!(
-                    "{} - {}",
-                    agent.display_name,
-                    agent.last_text.clone().unwrap_or_else(|| {
-                        if agent.state == "error" {
-                            "Error".to_string()
-                        } else {
-                            "Completed".to_string()
-                        }
-                    })
-                ),
-                kind: if agent.state == "error" {
-                    "error".to_string()
-                } else {
-                    "done".to_string()
-                },
-                key: agent.key.clone(),
-            });
+            Err(e) => tracing::warn!(error = %e, "monitor tick failed"),
         }
+        tokio::time::sleep(Duration::from_millis(interval_ms as u64)).await;
     }
-    *lock = next_states;
-
-    Ok(MonitorTickPayload {
-        snapshot,
-        notifications,
-    })
-}
-
-fn normalize_source_name(source: &str) -> String {
-    let normalized = source.trim().to_lowercase();
-    if normalized == "claude"
-        || normalized == "claude code"
-        || normalized == "claude-code"
-        || normalized == "claudecode"
-    {
-        return "claude".to_string();
-    }
-    if normalized == "opencode"
-        || normalized == "open"
-        || normalized == "open-code"
-        || normalized == "open_code"
-    {
-        return "opencode".to_string();
-    }
-    if normalized == "codex" {
-        return "codex".to_string();
-    }
-    source.to_string()
 }
 
-fn scan_opencode_db(map: &mut HashMap<String, AgentTemp>) -> bool {
-    let db_path = opencode_db_file();
-    if !db_path.exists() {
-        return false;
+async fn monitor_tick(
+    app: AppHandle,
+    full_snapshot: bool,
+) -> Result<MonitorTickPayload, PixelAgentsError> {
+    let state = app.state::<AppState>();
+    let _tick_guard = state.monitor_tick_lock.lock().await;
+    let settings = read_monitor_settings_async().await;
+    if !settings.enabled {
+        return Ok(disabled_tick());
     }
 
-    let conn = match Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY) {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-
-    let mut session_repo: HashMap<String, String> = HashMap::new();
-    let mut session_name: HashMap<String, String> = HashMap::new();
-
-    {
-        let mut stmt = match conn.prepare(
-            "SELECT id, directory, title, time_updated
-             FROM session
-             WHERE time_archived IS NULL OR time_archived = 0
-             ORDER BY time_updated DESC
-             LIMIT ?1",
-        ) {
-            Ok(s) => s,
-            Err(_) => return false,
-        };
-
-        let rows = stmt.query_map([MAX_OPENCODE_DB_SESSIONS as i64], |row| {
-            let id: String = row.get(0)?;
-            let directory: String = row.get(1)?;
-            let title: Option<String> = row.get(2)?;
-            let time_updated: i64 = row.get(3)?;
-            Ok((id, directory, title, time_updated))
-        });
-
-        if let Ok(rows) = rows {
-            for row in rows.flatten() {
-                let (session_id, directory, title, time_updated) = row;
-                let ts = normalize_epoch_ms(time_updated);
-                session_repo.insert(session_id.clone(), directory.clone());
-                if let Some(name) = title.clone() {
-                    session_name.insert(session_id.clone(), name);
-                }
-                upsert_agent(
-                    map,
-                    AgentTemp {
-                        key: format!("opencode:{}", session_id),
-                        source: "opencode".to_string(),
-                        session_id,
-                        agent_name: title.clone(),
-                        state: "running".to_string(),
-                        last_ts_ms: ts,
-                        last_text: Some("Session activity".to_string()),
-                        repo_path: Some(directory),
-                        recent_events: vec![MonitorEventView {
-                            ts_ms: ts,
-                            event_type: "status".to_string(),
-                            state_hint: "running".to_string(),
-                            text: Some("Session activity".to_string()),
-                            files_touched: Vec::new(),
-                        }],
-                    },
-                );
+    let limits = settings.scan_limits();
+    let tasks: Vec<_> = Monitor::enabled_sources(&settings)
+        .into_iter()
+        .map(|name| {
+            let app = app.clone();
+            let task = tauri::async_runtime::spawn_blocking(move || {
+                app.state::<AppState>().monitor.scan_source(name, &limits)
+            });
+            (name, task)
+        })
+        .collect();
+    let mut map: HashMap<String, AgentTemp> = HashMap::new();
+    for (name, task) in tasks {
+        let result = task
+            .await
+            .map_err(|_| PixelAgentsError::State(format!("{} scanner failed", name)))?;
+        match result {
+            Ok(agents) => {
+                tracing::debug!(source = name, agents = agents.len(), "scanner finished");
+                map.extend(agents);
             }
+            Err(e) => tracing::warn!(source = name, error = %e, "scanner failed"),
         }
     }
+    let repo_bindings = read_repo_bindings_async().await;
 
-    {
-        let mut stmt = match conn.prepare(
-            "SELECT session_id, time_updated, data
-             FROM part
-             ORDER BY time_updated DESC
-             LIMIT ?1",
-        ) {
-            Ok(s) => s,
-            Err(_) => return false,
-        };
-
-        let rows = stmt.query_map([MAX_OPENCODE_DB_PARTS as i64], |row| {
-            let session_id: String = row.get(0)?;
-            let time_updated: i64 = row.get(1)?;
-            let data: String = row.get(2)?;
-            Ok((session_id, time_updated, data))
+    let handle = app.clone();
+    let mut payload = tauri::async_runtime::spawn_blocking(move || {
+        handle
+            .state::<AppState>()
+            .monitor
+            .build_tick(settings, map, repo_bindings, full_snapshot)
+    })
+    .await
+    .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    let mut notifications = std::mem::take(&mut payload.remote_notifications);
+    if notifications_snoozed_until(&state)? > now_ms() {
+        payload.notifications.clear();
+        notifications.clear();
+    }
+    let primed = state.remote_dispatch_primed.swap(true, Ordering::Relaxed);
+    if primed && !notifications.is_empty() {
+        tauri::async_runtime::spawn_blocking(move || {
+            dispatch_webhooks(&notifications);
+            dispatch_push(&notifications);
         });
-
-        if let Ok(rows) = rows {
-            for row in rows.flatten() {
-                let (session_id, time_updated, data) = row;
-                let value: Value = match serde_json::from_str(&data) {
-                    Ok(v) => v,
-                    Err(_) => continue,
-                };
-                let part_type = string_at(&value, &["type"]).unwrap_or_default();
-                let fallback_ts = normalize_epoch_ms(time_updated);
-
-                let (state, event_type, text, ts) = if part_type == "tool" {
-                    let state_obj = value
-                        .get("state")
-                        .and_then(Value::as_object)
-                        .cloned()
-                        .unwrap_or_else(Map::new);
-                    let status = state_obj
-                        .get("status")
-                        .and_then(Value::as_str)
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| "running".to_string());
-                    let normalized_status = status.to_lowercase();
-                    let tool_name =
-                        string_at(&value, &["tool"]).unwrap_or_else(|| "tool".to_string());
-                    let start_ts = state_obj
-                        .get("time")
-                        .and_then(Value::as_object)
-                        .and_then(|t| t.get("start"))
-                        .and_then(to_i64)
-                        .map(normalize_epoch_ms)
-                        .unwrap_or(fallback_ts);
-                    let end_ts = state_obj
-                        .get("time")
-                        .and_then(Value::as_object)
-                        .and_then(|t| t.get("end"))
-                        .and_then(to_i64)
-                        .map(normalize_epoch_ms);
-                    let hint = if normalized_status == "error" {
-                        "error".to_string()
-                    } else if normalized_status == "completed" || end_ts.is_some() {
-                        "done".to_string()
-                    } else {
-                        "running".to_string()
-                    };
-                    (
-                        hint,
-                        if normalized_status == "error" {
-                            "error".to_string()
-                        } else {
-                            "tool".to_string()
-                        },
-                        Some(format!("{}: {}", tool_name, normalized_status)),
-                        end_ts.unwrap_or(start_ts),
-                    )
-                } else if part_type == "reasoning" {
-                    let start_ts = number_at(&value, &["time", "start"])
-                        .map(normalize_epoch_ms)
-                        .unwrap_or(fallback_ts);
-                    let end_ts = number_at(&value, &["time", "end"]).map(normalize_epoch_ms);
-                    (
-                        "thinking".to_string(),
-                        "status".to_string(),
-                        string_at(&value, &["text"]).or_else(|| Some("Thinking".to_string())),
-                        end_ts.unwrap_or(start_ts),
-                    )
-                } else if part_type == "step-start" {
-                    (
-                        "running".to_string(),
-                        "status".to_string(),
-                        Some("Step started".to_string()),
-                        fallback_ts,
-                    )
-                } else if part_type == "step-finish" {
-                    let reason =
-                        string_at(&value, &["reason"]).unwrap_or_else(|| "stop".to_string());
-                    (
-                        "done".to_string(),
-                        "status".to_string(),
-                        Some(format!("Step finished: {}", reason)),
-                        fallback_ts,
-                    )
-                } else {
-                    continue;
-                };
-
-                let text = truncate_option_text(text);
-                upsert_agent(
-                    map,
-                    AgentTemp {
-                        key: format!("opencode:{}", session_id),
-                        source: "opencode".to_string(),
-                        session_id: session_id.clone(),
-                        agent_name: session_name.get(&session_id).cloned(),
-                        state: state.clone(),
-                        last_ts_ms: ts,
-                        last_text: text.clone(),
-                        repo_path: session_repo.get(&session_id).cloned(),
-                        recent_events: vec![MonitorEventView {
-                            ts_ms: ts,
-                            event_type,
-                            state_hint: state,
-                            text,
-                            files_touched: Vec::new(),
-                        }],
-                    },
-                );
-            }
-        }
     }
-
-    true
+    let history = std::mem::take(&mut payload.history);
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = append_notification_history(&history) {
+            tracing::warn!(error = %e, "failed to record notification history");
+        }
+    });
+    Ok(payload)
 }
 
-fn scan_opencode(map: &mut HashMap<String, AgentTemp>) {
-    if scan_opencode_db(map) {
-        return;
-    }
-
-    let root = opencode_message_root();
-    if !root.exists() {
-        return;
-    }
-
-    let session_repo = load_opencode_session_repo_map();
-    let session_name = load_opencode_session_name_map();
-
-    let files = collect_files(&root, "json", MAX_OPENCODE_FILES);
-    for file in files {
-        let raw = match fs::read_to_string(&file) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let value: Value = match serde_json::from_str(&raw) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let session_id = string_at(&value, &["sessionID", "sessionId"])
-            .or_else(|| {
-                file.parent()
-                    .and_then(|p| p.file_name())
-                    .map(|s| s.to_string_lossy().into_owned())
-            })
-            .unwrap_or_else(|| "unknown".to_string());
-        let key = format!("opencode:{}", session_id);
-        let ts = normalize_epoch_ms(
-            number_at(&value, &["time", "created"]).unwrap_or_else(|| modified_ms(&file)),
-        );
-        let completed = number_at(&value, &["time", "completed"]).is_some();
-        let state = if completed { "done" } else { "running" }.to_string();
-        let text = truncate_option_text(
-            string_at(&value, &["summary"]).or_else(|| string_at(&value, &["finish"])),
-        );
-        let repo_path = string_at(&value, &["path", "root"])
-            .or_else(|| string_at(&value, &["path", "cwd"]))
-            .or_else(|| session_repo.get(&session_id).cloned());
-
-        upsert_agent(
-            map,
-            AgentTemp {
-                key: key.clone(),
-                source: "opencode".to_string(),
-                session_id: session_id.clone(),
-                agent_name: session_name.get(&session_id).cloned(),
-                state: state.clone(),
-                last_ts_ms: ts,
-                last_text: text.clone(),
-                repo_path,
-                recent_events: vec![MonitorEventView {
-                    ts_ms: ts,
-                    event_type: "message".to_string(),
-                    state_hint: state,
-                    text,
-                    files_touched: Vec::new(),
-                }],
-            },
-        );
-    }
-
-    let part_root = opencode_part_root();
-    if !part_root.exists() {
-        return;
-    }
-
-    let part_files = collect_files(&part_root, "json", MAX_OPENCODE_PART_FILES);
-    for file in part_files {
-        let raw = match fs::read_to_string(&file) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let value: Value = match serde_json::from_str(&raw) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        let session_id = string_at(&value, &["sessionID", "sessionId"]);
-        let Some(session_id) = session_id else {
-            continue;
-        };
-
-        let key = format!("opencode:{}", session_id);
-        let part_type = string_at(&value, &["type"]).unwrap_or_default();
-        let modified = normalize_epoch_ms(modified_ms(&file));
-
-        let (state, event_type, text, ts) = if part_type == "tool" {
-            let state_obj = value
-                .get("state")
-                .and_then(Value::as_object)
-                .cloned()
-                .unwrap_or_else(Map::new);
-            let status = state_obj
-                .get("status")
-                .and_then(Value::as_str)
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| "running".to_string());
-            let normalized_status = status.to_lowercase();
-            let tool_name = string_at(&value, &["tool"]).unwrap_or_else(|| "tool".to_string());
-            let start_ts = state_obj
-                .get("time")
-                .and_then(Value::as_object)
-                .and_then(|t| t.get("start"))
-                .and_then(to_i64)
-                .map(normalize_epoch_ms)
-                .unwrap_or(modified);
-            let end_ts = state_obj
-                .get("time")
-                .and_then(Value::as_object)
-                .and_then(|t| t.get("end"))
-                .and_then(to_i64)
-                .map(normalize_epoch_ms);
-            let hint = if normalized_status == "error" {
-                "error".to_string()
-            } else if normalized_status == "completed" || end_ts.is_some() {
-                "done".to_string()
-            } else {
-                "running".to_string()
-            };
-            (
-                hint,
-                if normalized_status == "error" {
-                    "error".to_string()
-                } else {
-                    "tool".to_string()
-                },
-                Some(format!("{}: {}", tool_name, normalized_status)),
-                end_ts.unwrap_or(start_ts),
-            )
-        } else if part_type == "reasoning" {
-            let start_ts = number_at(&value, &["time", "start"])
-                .map(normalize_epoch_ms)
-                .unwrap_or(modified);
-            let end_ts = number_at(&value, &["time", "end"]).map(normalize_epoch_ms);
-            (
-                "thinking".to_string(),
-                "status".to_string(),
-                string_at(&value, &["text"]).or_else(|| Some("Thinking".to_string())),
-                end_ts.unwrap_or(start_ts),
-            )
-        } else if part_type == "step-start" {
-            (
-                "running".to_string(),
-                "status".to_string(),
-                Some("Step started".to_string()),
-                modified,
-            )
-        } else if part_type == "step-finish" {
-            let reason = string_at(&value, &["reason"]).unwrap_or_else(|| "stop".to_string());
-            (
-                "done".to_string(),
-                "status".to_string(),
-                Some(format!("Step finished: {}", reason)),
-                modified,
-            )
-        } else {
-            continue;
-        };
-
-        let text = truncate_option_text(text);
-
-        upsert_agent(
-            map,
-            AgentTemp {
-                key: key.clone(),
-                source: "opencode".to_string(),
-                session_id: session_id.clone(),
-                agent_name: session_name.get(&session_id).cloned(),
-                state: state.clone(),
-                last_ts_ms: ts,
-                last_text: text.clone(),
-                repo_path: session_repo.get(&session_id).cloned(),
-                recent_events: vec![MonitorEventView {
-                    ts_ms: ts,
-                    event_type,
-                    state_hint: state,
-                    text,
-                    files_touched: Vec::new(),
-                }],
-            },
-        );
-    }
+fn notifications_snoozed_until(state: &AppState) -> Result<i64, PixelAgentsError> {
+    Ok(*state
+        .notifications_snoozed_until
+        .lock()
+        .map_err(|_| PixelAgentsError::lock("notification snooze"))?)
 }
 
-fn load_opencode_session_repo_map() -> HashMap<String, String> {
-    let mut out = HashMap::new();
-    let session_root = opencode_session_root();
-    if !session_root.exists() {
-        return out;
-    }
-
-    let project_root = opencode_project_root();
-    let session_files = collect_files(&session_root, "json", MAX_OPENCODE_FILES);
-    for file in session_files {
-        let raw = match fs::read_to_string(&file) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let value: Value = match serde_json::from_str(&raw) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        let session_id = string_at(&value, &["id"])
-            .or_else(|| {
-                file.parent()
-                    .and_then(|p| p.file_name())
-                    .map(|s| s.to_string_lossy().into_owned())
-            })
-            .or_else(|| file.file_stem().map(|s| s.to_string_lossy().into_owned()));
-        let project_id = string_at(&value, &["projectID", "projectId"]);
-        let Some(session_id) = session_id else {
-            continue;
-        };
-        let Some(project_id) = project_id else {
-            continue;
-        };
-
-        let project_file = project_root.join(format!("{}.json", project_id));
-        let project_raw = match fs::read_to_string(project_file) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let project: Value = match serde_json::from_str(&project_raw) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        if let Some(repo) = string_at(&project, &["worktree"]) {
-            out.insert(session_id, repo);
-        }
-    }
-
-    out
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+fn desktop_snooze_notifications(
+    state: State<AppState>,
+    minutes: i64,
+) -> Result<Option<i64>, PixelAgentsError> {
+    set_notification_snooze(&state, minutes)
 }
 
-fn load_opencode_session_name_map() -> HashMap<String, String> {
-    let mut out = HashMap::new();
-    let session_root = opencode_session_root();
-    if !session_root.exists() {
-        return out;
-    }
-
-    let session_files = collect_files(&session_root, "json", MAX_OPENCODE_FILES);
-    for file in session_files {
-        let raw = match fs::read_to_string(&file) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let value: Value = match serde_json::from_str(&raw) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        let session_id = string_at(&value, &["id"])
-            .or_else(|| {
-                file.parent()
-                    .and_then(|p| p.file_name())
-                    .map(|s| s.to_string_lossy().into_owned())
-            })
-            .or_else(|| file.file_stem().map(|s| s.to_string_lossy().into_owned()));
-        let title = string_at(&value, &["title"]);
-        if let (Some(session_id), Some(title)) = (session_id, title) {
-            out.insert(session_id, title);
-        }
-    }
-
-    out
+fn set_notification_snooze(
+    state: &AppState,
+    minutes: i64,
+) -> Result<Option<i64>, PixelAgentsError> {
+    let until = if minutes > 0 {
+        now_ms() + minutes * 60_000
+    } else {
+        0
+    };
+    write_desktop_setting("notificationsSnoozedUntil", Value::from(until))?;
+    *state
+        .notifications_snoozed_until
+        .lock()
+        .map_err(|_| PixelAgentsError::lock("notification snooze"))? = until;
+    Ok((until > 0).then_some(until))
 }
 
-fn scan_codex(map: &mut HashMap<String, AgentTemp>) {
-    let root = codex_sessions_root();
-    if !root.exists() {
-        return;
-    }
-    let files = collect_files(&root, "jsonl", MAX_CODEX_FILES);
-    for file in files {
-        let modified = modified_ms(&file);
-        let fallback_session = parse_session_from_filename(&file).unwrap_or_else(|| {
-            file.file_stem()
-                .map(|s| s.to_string_lossy().into_owned())
-                .unwrap_or_else(|| "unknown".to_string())
-        });
-        let tail = match read_tail(&file, CODEX_TAIL_BYTES) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        for line in tail.lines() {
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            let record: Value = match serde_json::from_str(trimmed) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-            let payload = record
-                .get("payload")
-                .and_then(Value::as_object)
-                .cloned()
-                .unwrap_or_else(Map::new);
-            let kind = record
-                .get("type")
-                .and_then(Value::as_str)
-                .unwrap_or_default();
-            let payload_type = payload
-                .get("type")
-                .and_then(Value::as_str)
-                .unwrap_or_default();
-            let session_id = payload
-                .get("id")
-                .and_then(Value::as_str)
-                .map(|s| s.to_string())
-                .or_else(|| {
-                    record
-                        .get("session_id")
-                        .and_then(Value::as_str)
-                        .map(|s| s.to_string())
-                })
-                .or_else(|| {
-                    record
-                        .get("sessionId")
-                        .and_then(Value::as_str)
-                        .map(|s| s.to_string())
-                })
-                .unwrap_or_else(|| fallback_session.clone());
-            let key = format!("codex:{}", session_id);
-            let ts = number_direct(&record, "ts")
-                .or_else(|| number_direct(&record, "timestamp"))
-                .or_else(|| payload.get("ts").and_then(to_i64))
-                .or_else(|| payload.get("timestamp").and_then(to_i64))
-                .map(normalize_epoch_ms)
-                .unwrap_or(modified);
-            let repo_path = payload
-                .get("cwd")
-                .and_then(Value::as_str)
-                .map(|s| s.to_string())
-                .or_else(|| {
-                    record
-                        .get("cwd")
-                        .and_then(Value::as_str)
-                        .map(|s| s.to_string())
-                });
-            let (state, event_type, text) =
-                classify_codex_event(kind, payload_type, &record, &payload);
-            let agent_name = extract_codex_agent_name(kind, payload_type, &record, &payload);
-
-            let event = MonitorEventView {
-                ts_ms: ts,
-                event_type,
-                state_hint: state.clone(),
-                text: Some(text.clone()),
-                files_touched: Vec::new(),
-            };
-
-            let existing = map.entry(key.clone()).or_insert(AgentTemp {
-                key: key.clone(),
-                source: "codex".to_string(),
-                session_id: session_id.clone(),
-                agent_name: None,
-                state: "idle".to_string(),
-                last_ts_ms: ts,
-                last_text: Some("Session discovered".to_string()),
-                repo_path: repo_path.clone(),
-                recent_events: Vec::new(),
-            });
-
-            if existing.repo_path.is_none() && repo_path.is_some() {
-                existing.repo_path = repo_path;
-            }
-            if existing.agent_name.is_none() && agent_name.is_some() {
-                existing.agent_name = agent_name.clone();
-            }
-            existing.recent_events.insert(0, event);
-            if existing.recent_events.len() > 20 {
-                existing.recent_events.truncate(20);
-            }
-            if ts >= existing.last_ts_ms {
-                existing.last_ts_ms = ts;
-                existing.state = state;
-                existing.last_text = Some(text);
-                if agent_name.is_some() {
-                    existing.agent_name = agent_name;
-                }
-            }
-        }
-    }
+#[tauri::command]
+#[tracing::instrument(skip(app, state), err)]
+fn desktop_test_notification(
+    app: AppHandle,
+    state: State<AppState>,
+    kind: String,
+) -> Result<bool, PixelAgentsError> {
+    let notifications = apply_quiet_hours(vec![test_notification(&kind)?], quiet_mode_now());
+    if notifications.is_empty() || notifications_snoozed_until(&state)? > now_ms() {
+        return Ok(false);
+    }
+    app.emit(
+        "monitor://tick",
+        MonitorTickPayload {
+            snapshot: None,
+            delta: None,
+            notifications,
+            history: Vec::new(),
+            remote_notifications: Vec::new(),
+        },
+    )?;
+    Ok(true)
 }
 
-fn extract_codex_agent_name(
-    kind: &str,
-    payload_type: &str,
-    record: &Value,
-    payload: &Map<String, Value>,
-) -> Option<String> {
-    if kind == "event_msg" && payload_type == "user_message" {
-        let message = payload
-            .get("message")
-            .and_then(Value::as_str)
-            .map(|s| s.to_string())
-            .or_else(|| {
-                record
-                    .get("message")
-                    .and_then(Value::as_str)
-                    .map(|s| s.to_string())
-            });
-        if let Some(message) = message {
-            let first_line = message
-                .lines()
-                .next()
-                .unwrap_or_default()
-                .trim()
-                .to_string();
-            if !first_line.is_empty() {
-                return Some(first_line);
-            }
-        }
-    }
-    None
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_get_notification_history(
+    limit: Option<usize>,
+) -> Result<Vec<NotificationHistoryEntry>, PixelAgentsError> {
+    Ok(read_notification_history(
+        limit.unwrap_or(NOTIFICATION_HISTORY_PAGE),
+    )?)
 }
 
-fn classify_codex_event(
-    kind: &str,
-    payload_type: &str,
-    record: &Value,
-    payload: &Map<String, Value>,
-) -> (String, String, String) {
-    let lower = format!("{} {}", kind.to_lowercase(), payload_type.to_lowercase());
-    if lower.contains("task_complete")
-        || lower.contains("turn_completed")
-        || lower.contains("turn.complete")
-        || lower.contains("item.completed")
-        || lower.contains("completed")
-    {
-        return (
-            "done".to_string(),
-            "status".to_string(),
-            "Turn completed".to_string(),
-        );
-    }
-    if lower.contains("turn_aborted") || lower.contains("task_aborted") || lower.contains("aborted")
-    {
-        return (
-            "waiting".to_string(),
-            "status".to_string(),
-            "Turn aborted".to_string(),
-        );
-    }
-    if lower.contains("error")
-        || lower.contains("failed")
-        || lower.contains("exception")
-        || lower.contains("fatal")
-    {
-        return (
-            "error".to_string(),
-            "error".to_string(),
-            "Codex error".to_string(),
-        );
-    }
-    if payload_type == "agent_message" || payload_type == "message" {
-        let message = record
-            .get("message")
-            .and_then(Value::as_str)
-            .map(|s| s.to_string())
-            .or_else(|| {
-                payload
-                    .get("message")
-                    .and_then(Value::as_str)
-                    .map(|s| s.to_string())
-            })
-            .unwrap_or_else(|| "Assistant message".to_string());
-        return ("running".to_string(), "message".to_string(), message);
-    }
-    if payload_type == "agent_reasoning"
-        || payload_type == "reasoning"
-        || payload_type == "token_count"
-    {
-        return (
-            "thinking".to_string(),
-            "status".to_string(),
-            "Thinking".to_string(),
-        );
-    }
-    if payload_type == "task_started" {
-        return (
-            "running".to_string(),
-            "status".to_string(),
-            "Task started".to_string(),
-        );
-    }
-    if payload_type == "user_message" {
-        return (
-            "waiting".to_string(),
-            "message".to_string(),
-            "Waiting for input".to_string(),
-        );
-    }
-    if payload_type == "function_call" || payload_type == "custom_tool_call" {
-        let name = payload
-            .get("name")
-            .and_then(Value::as_str)
-            .map(|s| s.to_string())
-            .or_else(|| {
-                payload
-                    .get("function")
-                    .and_then(Value::as_object)
-                    .and_then(|m| m.get("name"))
-                    .and_then(Value::as_str)
-                    .map(|s| s.to_string())
-            })
-            .unwrap_or_else(|| "tool".to_string());
-        return (
-            "running".to_string(),
-            "tool".to_string(),
-            format!("{}: running", name),
-        );
-    }
-    if payload_type == "function_call_output" || payload_type == "custom_tool_call_output" {
-        return (
-            "running".to_string(),
-            "tool".to_string(),
-            "Tool output".to_string(),
-        );
-    }
+#[tauri::command]
+fn desktop_get_quiet_hours() -> QuietHours {
+    read_quiet_hours()
+}
 
-    let fallback = record
-        .get("message")
-        .and_then(Value::as_str)
-        .map(|s| s.to_string())
-        .or_else(|| {
-            payload
-                .get("message")
-                .and_then(Value::as_str)
-                .map(|s| s.to_string())
-        })
-        .unwrap_or_else(|| kind.to_string());
-    ("running".to_string(), "message".to_string(), fallback)
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_set_quiet_hours(quiet_hours: QuietHours) -> Result<QuietHours, PixelAgentsError> {
+    write_quiet_hours(&quiet_hours)?;
+    Ok(read_quiet_hours())
 }
 
-fn upsert_agent(map: &mut HashMap<String, AgentTemp>, incoming: AgentTemp) {
-    match map.get_mut(&incoming.key) {
-        Some(existing) => {
-            if incoming.last_ts_ms >= existing.last_ts_ms {
-                let mut merged = incoming;
-                if merged.repo_path.is_none() {
-                    merged.repo_path = existing.repo_path.clone();
-                }
-                if merged.last_text.is_none() {
-                    merged.last_text = existing.last_text.clone();
-                }
-                if merged.agent_name.is_none() {
-                    merged.agent_name = existing.agent_name.clone();
-                }
-                *existing = merged;
-            }
-        }
-        None => {
-            map.insert(incoming.key.clone(), incoming);
-        }
-    }
+#[tauri::command]
+fn desktop_get_webhooks() -> Vec<Webhook> {
+    read_webhooks()
 }
 
-fn read_layout_or_default() -> Result<Value, String> {
-    let path = layout_file();
-    if path.exists() {
-        return read_json_file(&path);
-    }
-    serde_json::from_str(include_str!("../../public/assets/default-layout.json"))
-        .map_err(|e| e.to_string())
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_set_webhooks(webhooks: Vec<Webhook>) -> Result<Vec<Webhook>, PixelAgentsError> {
+    write_webhooks(&webhooks)?;
+    Ok(read_webhooks())
 }
 
-fn read_monitor_settings() -> MonitorSettings {
-    match read_json_file(&monitor_settings_file()) {
-        Ok(value) => serde_json::from_value(value).unwrap_or_default(),
-        Err(_) => MonitorSettings::default(),
-    }
+#[tauri::command]
+fn desktop_get_push_settings() -> Option<PushSettings> {
+    read_push_settings()
 }
 
-fn read_sound_enabled() -> bool {
-    read_desktop_settings()
-        .ok()
-        .and_then(|v| v.get("soundEnabled").and_then(Value::as_bool))
-        .unwrap_or(true)
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_set_push_settings(
+    settings: Option<PushSettings>,
+) -> Result<Option<PushSettings>, PixelAgentsError> {
+    write_push_settings(settings.as_ref())?;
+    Ok(read_push_settings())
 }
 
-fn read_demo_mode() -> bool {
-    read_desktop_settings()
-        .ok()
-        .and_then(|v| v.get("demoMode").and_then(Value::as_bool))
-        .unwrap_or(false)
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+async fn desktop_test_push(settings: PushSettings) -> Result<(), PixelAgentsError> {
+    let notification = test_notification("done")?;
+    tauri::async_runtime::spawn_blocking(move || send_push(&settings, &notification))
+        .await
+        .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(())
 }
 
-fn read_desktop_settings() -> Result<Value, String> {
-    read_json_file(&sound_settings_file())
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+async fn desktop_test_webhook(webhook: Webhook) -> Result<(), PixelAgentsError> {
+    let notification = test_notification("done")?;
+    tauri::async_runtime::spawn_blocking(move || send_webhook(&webhook, &notification))
+        .await
+        .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(())
 }
 
-fn write_desktop_setting_bool(key: &str, value: bool) -> Result<(), String> {
-    let mut settings = match read_desktop_settings() {
-        Ok(existing) => existing,
-        Err(_) => json!({}),
-    };
-    if !settings.is_object() {
-        settings = json!({});
-    }
-    if let Some(map) = settings.as_object_mut() {
-        map.insert(key.to_string(), Value::Bool(value));
-    }
-    write_json_file(&sound_settings_file(), &settings)
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_get_notification_snooze(
+    state: State<AppState>,
+) -> Result<Option<i64>, PixelAgentsError> {
+    let until = notifications_snoozed_until(&state)?;
+    Ok((until > now_ms()).then_some(until))
 }
 
-fn read_repo_bindings() -> HashMap<String, String> {
-    match read_json_file(&repo_bindings_file()) {
-        Ok(value) => serde_json::from_value(value).unwrap_or_default(),
-        Err(_) => HashMap::new(),
+#[tauri::command]
+#[tracing::instrument(skip(app), err)]
+async fn desktop_reset_data(
+    app: AppHandle,
+    scope: ResetScope,
+) -> Result<ResetReport, PixelAgentsError> {
+    let report = tauri::async_runtime::spawn_blocking(move || reset_data(scope))
+        .await
+        .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    let state = app.state::<AppState>();
+    if scope != ResetScope::Layouts {
+        state.monitor.reset_scans()?;
+    }
+    if scope == ResetScope::All {
+        *state
+            .notifications_snoozed_until
+            .lock()
+            .map_err(|_| PixelAgentsError::lock("notification snooze"))? = 0;
     }
+    Ok(report)
 }
 
-fn collect_files(root: &Path, ext: &str, max_files: usize) -> Vec<PathBuf> {
-    let mut files: Vec<PathBuf> = WalkDir::new(root)
-        .into_iter()
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.file_type().is_file())
-        .filter_map(|entry| {
-            let path = entry.path().to_path_buf();
-            let matches = path
-                .extension()
-                .and_then(|e| e.to_str())
-                .map(|e| e.eq_ignore_ascii_case(ext))
-                .unwrap_or(false);
-            if matches {
-                Some(path)
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    files.sort_by(|a, b| modified_ms(b).cmp(&modified_ms(a)));
-    if files.len() > max_files {
-        files.truncate(max_files);
-    }
-    files
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_monitor_diagnostics(
+    state: State<AppState>,
+) -> Result<Vec<MonitorSourceDiagnostics>, PixelAgentsError> {
+    Ok(state.monitor.diagnostics(&read_monitor_settings())?)
 }
 
-fn truncate_option_text(text: Option<String>) -> Option<String> {
-    text.map(truncate_text)
+#[tauri::command]
+#[tracing::instrument(skip(app), err)]
+async fn desktop_kill_agent(
+    app: AppHandle,
+    agent_key: String,
+    force: Option<bool>,
+) -> Result<u32, PixelAgentsError> {
+    let pid = tauri::async_runtime::spawn_blocking(move || {
+        app.state::<AppState>()
+            .monitor
+            .kill_agent(&agent_key, force.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(pid)
 }
 
-fn truncate_text(text: String) -> String {
-    if text.chars().count() <= MAX_MONITOR_TEXT_CHARS {
-        return text;
-    }
-    let mut out = String::new();
-    for (idx, ch) in text.chars().enumerate() {
-        if idx >= MAX_MONITOR_TEXT_CHARS {
-            break;
+#[tauri::command]
+#[tracing::instrument(skip(state, text), err)]
+fn desktop_send_prompt(
+    state: State<AppState>,
+    source: String,
+    session_id: String,
+    text: String,
+) -> Result<(), PixelAgentsError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(PixelAgentsError::Invalid("Prompt is empty".to_string()));
+    }
+    let agent = state
+        .monitor
+        .find_agent(&format!("{}:{}", source, session_id))
+        .ok();
+    if let Some(agent) = agent.as_ref() {
+        if matches!(agent.state.as_str(), "running" | "thinking") {
+            return Err(PixelAgentsError::Invalid(format!(
+                "{} is still {}",
+                agent.display_name, agent.state
+            )));
         }
-        out.push(ch);
     }
-    out.push_str("...");
-    out
-}
-
-fn modified_ms(path: &Path) -> i64 {
-    fs::metadata(path)
-        .ok()
-        .and_then(|m| m.modified().ok())
-        .and_then(system_time_to_ms)
-        .unwrap_or_else(now_ms)
+    let (program, args) = resume_prompt_command(&source, &session_id, text)?;
+    if !cli_available(&state, program) {
+        return Err(PixelAgentsError::Launch(format!(
+            "Command `{}` not found in PATH",
+            program
+        )));
+    }
+
+    let mut command = Command::new(program);
+    command
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    if let Some(repo_path) = agent.and_then(|agent| agent.repo_path) {
+        command.current_dir(repo_path);
+    }
+    let child = command
+        .spawn()
+        .map_err(|e| PixelAgentsError::Launch(e.to_string()))?;
+    tauri::async_runtime::spawn_blocking(move || match child.wait_with_output() {
+        Ok(output) if !output.status.success() => tracing::warn!(
+            source,
+            session_id,
+            status = %output.status,
+            stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+            "prompt command failed"
+        ),
+        Ok(_) => tracing::info!(source, session_id, "prompt delivered"),
+        Err(e) => tracing::warn!(source, session_id, error = %e, "prompt command failed"),
+    });
+    Ok(())
 }
 
-fn normalize_epoch_ms(value: i64) -> i64 {
-    if value <= 0 {
-        return value;
-    }
-    if value < 10_000_000_000 {
-        return value.saturating_mul(1000);
-    }
-    if value > 10_000_000_000_000_000 {
-        return value / 1_000_000;
-    }
-    if value > 10_000_000_000_000 {
-        return value / 1000;
-    }
-    value
+fn resume_prompt_command(
+    source: &str,
+    session_id: &str,
+    text: &str,
+) -> Result<(&'static str, Vec<String>), PixelAgentsError> {
+    // `--` keeps a prompt starting with `-` (or a bare `-`, stdin for codex) from
+    // being read as an option.
+    let (program, args) = match source {
+        "claude" => ("claude", vec!["--resume", session_id, "-p", "--", text]),
+        "codex" => ("codex", vec!["exec", "resume", session_id, "--", text]),
+        "opencode" => ("opencode", vec!["run", "--session", session_id, "--", text]),
+        _ => {
+            return Err(PixelAgentsError::Invalid(format!(
+                "Cannot send prompts to {} sessions",
+                source
+            )))
+        }
+    };
+    Ok((program, args.into_iter().map(str::to_string).collect()))
 }
 
-fn system_time_to_ms(time: SystemTime) -> Option<i64> {
-    time.duration_since(UNIX_EPOCH)
-        .ok()
-        .map(|d| d.as_millis() as i64)
+#[tauri::command]
+#[tracing::instrument(err)]
+async fn desktop_get_transcript(
+    source: String,
+    session_id: String,
+) -> Result<Vec<TranscriptEntry>, PixelAgentsError> {
+    let entries =
+        tauri::async_runtime::spawn_blocking(move || read_transcript(&source, &session_id))
+            .await
+            .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(entries)
 }
 
-fn read_tail(path: &Path, max_bytes: usize) -> Result<String, String> {
-    let mut file = fs::File::open(path).map_err(|e| e.to_string())?;
-    let size = file.metadata().map_err(|e| e.to_string())?.len() as usize;
-    let bytes = size.min(max_bytes);
-    if bytes == 0 {
-        return Ok(String::new());
-    }
-    file.seek(SeekFrom::Start((size - bytes) as u64))
-        .map_err(|e| e.to_string())?;
-    let mut buf = vec![0_u8; bytes];
-    file.read_exact(&mut buf).map_err(|e| e.to_string())?;
-    Ok(String::from_utf8_lossy(&buf).into_owned())
+#[tauri::command]
+#[tracing::instrument(err)]
+async fn desktop_get_agent_events(
+    agent_key: String,
+    limit: Option<usize>,
+    before_ts: Option<i64>,
+) -> Result<Vec<MonitorEventView>, PixelAgentsError> {
+    let limit = limit.unwrap_or(AGENT_EVENTS_PAGE);
+    let events = tauri::async_runtime::spawn_blocking(move || {
+        read_agent_events(&agent_key, limit, before_ts)
+    })
+    .await
+    .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(events)
 }
 
-fn parse_session_from_filename(path: &Path) -> Option<String> {
-    let stem = path.file_stem()?.to_string_lossy().to_string();
-    let re = Regex::new(
-        r"([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})$",
-    )
-    .ok()?;
-    re.captures(&stem)
-        .and_then(|c| c.get(1).map(|m| m.as_str().to_string()))
+#[tauri::command]
+#[tracing::instrument(err)]
+async fn desktop_search_sessions(query: String) -> Result<Vec<SessionSearchHit>, PixelAgentsError> {
+    let hits = tauri::async_runtime::spawn_blocking(move || search_sessions(&query))
+        .await
+        .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(hits)
 }
 
-fn string_at(value: &Value, path: &[&str]) -> Option<String> {
-    let mut current = value;
-    for key in path {
-        current = current.get(*key)?;
+#[tauri::command]
+#[tracing::instrument(err)]
+fn desktop_export_stats(
+    range: Option<UsageRange>,
+    format: StatsFormat,
+) -> Result<(), PixelAgentsError> {
+    let stats = read_usage_stats(range.unwrap_or_default())?;
+    let extension = format.extension();
+    let save = rfd::FileDialog::new()
+        .add_filter(extension, &[extension])
+        .set_file_name(format!("pixel-agents-usage.{}", extension))
+        .save_file();
+    if let Some(path) = save {
+        fs::write(path, render_session_usage(&stats.sessions, format)?)?;
     }
-    current.as_str().map(|s| s.to_string())
+    Ok(())
 }
 
-fn number_at(value: &Value, path: &[&str]) -> Option<i64> {
-    let mut current = value;
-    for key in path {
-        current = current.get(*key)?;
+#[tauri::command]
+#[tracing::instrument(err)]
+async fn desktop_get_usage_stats(
+    range: Option<UsageRange>,
+) -> Result<UsageStatsPayload, PixelAgentsError> {
+    let range = range.unwrap_or_default();
+    let stats = tauri::async_runtime::spawn_blocking(move || read_usage_stats(range))
+        .await
+        .map_err(|e| PixelAgentsError::State(e.to_string()))??;
+    Ok(stats)
+}
+
+fn read_layout_or_default() -> Result<Value, PixelAgentsError> {
+    let path = layout_file();
+    if path.exists() {
+        return Ok(read_json_file(&path)?);
     }
-    to_i64(current)
+    Ok(serde_json::from_str(include_str!(
+        "../../public/assets/default-layout.json"
+    ))?)
 }
 
-fn number_direct(value: &Value, key: &str) -> Option<i64> {
-    value.get(key).and_then(to_i64)
+fn write_active_layout(layout: &Value) -> Result<(), PixelAgentsError> {
+    write_layout(layout)?;
+    if let Some(name) = read_active_layout_name() {
+        write_named_layout(&name, layout)?;
+    }
+    Ok(())
 }
 
-fn to_i64(value: &Value) -> Option<i64> {
-    if let Some(v) = value.as_i64() {
-        return Some(v);
-    }
-    if let Some(v) = value.as_u64() {
-        return Some(v as i64);
-    }
-    if let Some(v) = value.as_f64() {
-        return Some(v as i64);
+fn read_settings_changed_payload() -> SettingsChangedPayload {
+    SettingsChangedPayload {
+        sound_enabled: read_sound_enabled(),
+        demo_mode: read_demo_mode(),
+        monitor_settings: read_monitor_settings(),
+        github_token_configured: read_github_token().is_some(),
     }
-    None
 }
 
-fn short_session(session: &str) -> String {
-    session.chars().take(8).collect()
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_refresh_cli_availability(
+    state: State<AppState>,
+) -> Result<Vec<DetectedAgent>, PixelAgentsError> {
+    state
+        .cli_probes
+        .lock()
+        .map_err(|_| PixelAgentsError::lock("cli probe"))?
+        .clear();
+    Ok(detected_agents(&state))
 }
 
-fn normalize_agent_name(name: &str) -> Option<String> {
-    let compact = name
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-        .trim()
-        .to_string();
-    if compact.is_empty() {
-        return None;
-    }
-    let mut out = String::new();
-    for (idx, ch) in compact.chars().enumerate() {
-        if idx >= 56 {
-            break;
-        }
-        out.push(ch);
-    }
-    if compact.chars().count() > 56 {
-        out.push_str("...");
-    }
-    Some(out)
+fn detected_agents(state: &AppState) -> Vec<DetectedAgent> {
+    let settings = read_monitor_settings();
+    AGENT_CLIS
+        .iter()
+        .map(|command| {
+            let probe = cli_probe(state, command);
+            let data_dir = match *command {
+                "opencode" => opencode_data_root(),
+                "codex" => codex_sessions_root(),
+                _ => claude_data_root(),
+            };
+            DetectedAgent {
+                name: command.to_string(),
+                available: probe.available,
+                version: probe.version,
+                data_dir_exists: data_dir.exists(),
+                data_dir: data_dir.to_string_lossy().into_owned(),
+                monitored: settings.enabled && settings.source_enabled(command),
+            }
+        })
+        .collect()
 }
 
-fn repo_label(repo_path: &str) -> Option<String> {
-    let trimmed = repo_path.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
-    let path = Path::new(trimmed);
-    let name = path
-        .file_name()
-        .and_then(|v| v.to_str())
-        .unwrap_or(trimmed)
-        .trim();
-    if name.is_empty() {
-        return None;
-    }
-    Some(name.to_string())
+fn cli_available(state: &AppState, command: &str) -> bool {
+    cli_probe(state, command).available
 }
 
-fn format_agent_display_name(
-    source: &str,
-    session_id: &str,
-    agent_name: Option<&str>,
-    repo_path: Option<&str>,
-) -> String {
-    let normalized_source = normalize_source_name(source);
-    if let Some(name) = agent_name.and_then(normalize_agent_name) {
-        return format!("{}: {}", normalized_source, name);
+fn cli_probe(state: &AppState, command: &str) -> CliProbe {
+    let now = now_ms();
+    if let Ok(probes) = state.cli_probes.lock() {
+        if let Some(probe) = probes
+            .get(command)
+            .filter(|probe| now - probe.checked_ms < CLI_PROBE_TTL_MS)
+        {
+            return probe.clone();
+        }
     }
-    if let Some(repo) = repo_path
-        .and_then(repo_label)
-        .and_then(|name| normalize_agent_name(&name))
-    {
-        return format!("{}: {}", normalized_source, repo);
+    let available = command_available(command);
+    let probe = CliProbe {
+        available,
+        version: available.then(|| command_version(command)).flatten(),
+        checked_ms: now,
+    };
+    if let Ok(mut probes) = state.cli_probes.lock() {
+        probes.insert(command.to_string(), probe.clone());
     }
-    format!("{}: {}", normalized_source, short_session(session_id))
+    probe
 }
 
-fn now_ms() -> i64 {
-    system_time_to_ms(SystemTime::now()).unwrap_or(0)
-}
-
-fn claude_available() -> bool {
-    command_available("claude")
+fn command_version(command: &str) -> Option<String> {
+    let output = if cfg!(target_os = "windows") {
+        Command::new("cmd")
+            .args(["/C", command, "--version"])
+            .output()
+    } else {
+        Command::new("sh")
+            .arg("-lc")
+            .arg(format!("{} --version", command))
+            .output()
+    }
+    .ok()
+    .filter(|o| o.status.success())?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
 }
 
 fn command_available(command: &str) -> bool {
@@ -1691,135 +1740,248 @@ fn command_available(command: &str) -> bool {
     }
 }
 
-fn pixel_agents_dir() -> PathBuf {
-    home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".pixel-agents")
-}
-
-fn layout_file() -> PathBuf {
-    pixel_agents_dir().join("layout.json")
-}
-
-fn agent_seats_file() -> PathBuf {
-    pixel_agents_dir().join("agent-seats.json")
-}
-
-fn monitor_settings_file() -> PathBuf {
-    pixel_agents_dir().join("monitor-settings.json")
-}
-
-fn sound_settings_file() -> PathBuf {
-    pixel_agents_dir().join("desktop-settings.json")
+#[tauri::command]
+#[tracing::instrument(skip_all, err)]
+fn desktop_take_deep_link(state: State<AppState>) -> Result<Option<String>, PixelAgentsError> {
+    Ok(state
+        .pending_deep_link
+        .lock()
+        .map_err(|_| PixelAgentsError::lock("deep link"))?
+        .take())
 }
 
-fn repo_bindings_file() -> PathBuf {
-    pixel_agents_dir().join("monitor-repo-bindings.json")
+fn open_deep_link(app: &AppHandle, link: &str) {
+    let Some(agent_key) = agent_key_from_link(link) else {
+        tracing::warn!(link, "ignoring unsupported deep link");
+        return;
+    };
+    tracing::info!(agent_key, "opening deep link");
+    match app.state::<AppState>().pending_deep_link.lock() {
+        Ok(mut pending) => *pending = Some(agent_key),
+        Err(_) => return,
+    }
+    focus_main_window(app);
+    let _ = app.emit("app://deep-link", ());
 }
 
-fn ensure_parent(path: &Path) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+fn toggle_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let shown = window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(false);
+    if shown {
+        let _ = window.hide();
+    } else {
+        focus_main_window(app);
+    }
+}
+
+fn build_tray(app: &AppHandle) -> tauri::Result<()> {
+    let show = MenuItem::with_id(app, TRAY_SHOW_ID, "Show Pixel Agents", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, TRAY_QUIT_ID, "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show, &quit])?;
+    let mut tray = TrayIconBuilder::with_id("main")
+        .menu(&menu)
+        .tooltip("Pixel Agents")
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            TRAY_SHOW_ID => focus_main_window(app),
+            TRAY_QUIT_ID => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                focus_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
     }
+    tray.build(app)?;
     Ok(())
 }
 
-fn read_json_file(path: &Path) -> Result<Value, String> {
-    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&raw).map_err(|e| e.to_string())
-}
-
-fn write_json_file(path: &Path, value: &Value) -> Result<(), String> {
-    ensure_parent(path)?;
-    let text = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
-    fs::write(path, text).map_err(|e| e.to_string())
-}
-
-fn opencode_message_root() -> PathBuf {
-    opencode_storage_root().join("message")
-}
-
-fn opencode_part_root() -> PathBuf {
-    opencode_storage_root().join("part")
-}
-
-fn opencode_session_root() -> PathBuf {
-    opencode_storage_root().join("session")
-}
-
-fn opencode_project_root() -> PathBuf {
-    opencode_storage_root().join("project")
-}
-
-fn opencode_storage_root() -> PathBuf {
-    opencode_data_root().join("storage")
-}
-
-fn opencode_db_file() -> PathBuf {
-    opencode_data_root().join("opencode.db")
-}
-
-fn opencode_data_root() -> PathBuf {
-    let configured = std::env::var("OPENCODE_DATA_DIR")
-        .ok()
-        .map(PathBuf::from)
-        .unwrap_or_else(|| {
-            home_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join(".local")
-                .join("share")
-                .join("opencode")
-        });
-
-    if configured
-        .file_name()
-        .and_then(|n| n.to_str())
-        .map(|n| n.eq_ignore_ascii_case("storage"))
-        .unwrap_or(false)
-    {
-        return configured
-            .parent()
-            .map(Path::to_path_buf)
-            .unwrap_or(configured);
+fn handle_window_event(window: &Window, event: &WindowEvent) {
+    let WindowEvent::CloseRequested { api, .. } = event else {
+        return;
+    };
+    if window.label() != "main" {
+        return;
+    }
+    if read_close_to_tray() {
+        api.prevent_close();
+        let _ = window.hide();
+    } else {
+        window.app_handle().exit(0);
     }
-    configured
 }
 
-fn codex_sessions_root() -> PathBuf {
-    std::env::var("CODEX_HOME")
-        .ok()
-        .map(PathBuf::from)
-        .unwrap_or_else(|| {
-            home_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join(".codex")
-        })
-        .join("sessions")
+fn focus_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let _log_guard = logging::init();
+    tracing::info!(
+        version = env!("CARGO_PKG_VERSION"),
+        "starting pixel-agents desktop"
+    );
     tauri::Builder::default()
-        .manage(AppState::default())
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            tracing::info!(?args, "second instance launched");
+            focus_main_window(app);
+            let _ = app.emit("app://second-instance", SecondInstancePayload { args, cwd });
+        }))
+        .plugin(tauri_plugin_autostart::init(
+            MacosLauncher::LaunchAgent,
+            Some(vec![AUTOSTART_ARG]),
+        ))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(handle_hotkey)
+                .build(),
+        )
+        .manage(AppState {
+            notifications_snoozed_until: Mutex::new(read_notifications_snoozed_until()),
+            ..AppState::default()
+        })
+        .on_window_event(handle_window_event)
+        .setup(|app| {
+            if std::env::args().any(|arg| arg == AUTOSTART_ARG) {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.minimize();
+                }
+            }
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(run_monitor_loop(handle));
+            tauri::async_runtime::spawn(run_update_check_loop(app.handle().clone()));
+            register_hotkeys(app.handle(), &read_hotkeys());
+            if let Err(e) = build_tray(app.handle()) {
+                tracing::warn!(error = %e, "failed to create tray icon");
+            }
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                tracing::warn!(error = %e, "failed to register deep link scheme");
+            }
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    open_deep_link(&handle, url.as_str());
+                }
+            });
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    open_deep_link(app.handle(), url.as_str());
+                }
+            }
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                let state = handle.state::<AppState>();
+                for command in AGENT_CLIS {
+                    cli_available(&state, command);
+                }
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             desktop_bootstrap,
             desktop_save_layout,
             desktop_read_layout,
+            desktop_list_layouts,
+            desktop_save_layout_as,
+            desktop_switch_layout,
+            desktop_list_layout_backups,
+            desktop_restore_layout_backup,
             desktop_save_agent_seats,
             desktop_set_monitor_settings,
             desktop_set_sound_enabled,
             desktop_set_demo_mode,
+            desktop_set_autostart,
+            desktop_get_hotkeys,
+            desktop_take_deep_link,
+            desktop_set_hotkeys,
+            desktop_set_github_token,
             desktop_set_picture_in_picture,
+            desktop_open_agent_window,
+            desktop_open_dashboard_window,
+            desktop_close_dashboard_window,
             desktop_bind_repo,
+            desktop_list_repos,
+            desktop_list_repo_bindings,
+            desktop_unbind_repo,
             desktop_sessions_folder,
+            desktop_reveal_session,
             desktop_open_path,
+            desktop_open_in_editor,
+            desktop_set_editor_command,
+            desktop_get_editor_command,
+            desktop_get_data_dir,
+            desktop_set_data_dir,
             desktop_open_url,
+            desktop_check_for_updates,
+            desktop_set_auto_check_updates,
+            desktop_get_auto_check_updates,
+            desktop_set_close_to_tray,
+            desktop_get_close_to_tray,
             desktop_launch_agent,
+            desktop_launch_agent_worktree,
+            desktop_open_terminal_at,
+            desktop_mark_pr_ready,
+            desktop_archive_agent,
+            desktop_unarchive_agent,
+            desktop_list_archived_agents,
+            desktop_pin_agent,
+            desktop_set_agent_muted,
+            desktop_dismiss_alert,
+            desktop_set_agent_name,
+            desktop_set_agent_note,
+            desktop_open_pr,
+            desktop_get_logs,
+            desktop_monitor_diagnostics,
+            desktop_get_transcript,
+            desktop_get_agent_events,
+            desktop_get_usage_stats,
+            desktop_export_stats,
+            desktop_search_sessions,
+            desktop_send_prompt,
+            desktop_kill_agent,
+            desktop_export_transcript,
+            desktop_refresh_cli_availability,
             desktop_choose_repo_folder,
             desktop_copy_text,
+            desktop_copy_snapshot,
+            desktop_read_clipboard_text,
             desktop_export_layout,
+            desktop_export_bundle,
+            desktop_import_bundle,
             desktop_import_layout,
-            desktop_monitor_tick
+            desktop_monitor_tick,
+            desktop_snooze_notifications,
+            desktop_get_notification_snooze,
+            desktop_test_notification,
+            desktop_get_notification_history,
+            desktop_get_quiet_hours,
+            desktop_set_quiet_hours,
+            desktop_get_webhooks,
+            desktop_set_webhooks,
+            desktop_test_webhook,
+            desktop_get_push_settings,
+            desktop_set_push_settings,
+            desktop_test_push,
+            desktop_reset_data,
+            desktop_monitor_deep_scan
         ])
         .run(tauri::generate_context!())
         .expect("error while running pixel-agents desktop");
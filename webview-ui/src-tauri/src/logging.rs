@@ -0,0 +1,73 @@
+use crate::error::PixelAgentsError;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::EnvFilter;
+
+const LOG_FILE_PREFIX: &str = "pixel-agents";
+const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+const MAX_TAIL_LINES: usize = 5_000;
+const DEFAULT_FILTER: &str = "pixel_agents_desktop_lib=info";
+
+pub(crate) fn logs_dir() -> PathBuf {
+    crate::pixel_agents_dir().join("logs")
+}
+
+pub(crate) fn init() -> Option<WorkerGuard> {
+    let dir = logs_dir();
+    fs::create_dir_all(&dir).ok()?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .ok()?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let filter = EnvFilter::try_from_env("PIXEL_AGENTS_LOG")
+        .unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .try_init()
+        .ok()?;
+    Some(guard)
+}
+
+pub(crate) fn tail(lines: usize) -> Result<String, PixelAgentsError> {
+    let wanted = lines.clamp(1, MAX_TAIL_LINES);
+    let mut tail: VecDeque<String> = VecDeque::with_capacity(wanted);
+    for path in log_files(&logs_dir())?.iter().rev() {
+        let text = fs::read_to_string(path)?;
+        for line in text.lines().rev() {
+            if tail.len() == wanted {
+                break;
+            }
+            tail.push_front(line.to_string());
+        }
+        if tail.len() == wanted {
+            break;
+        }
+    }
+    Ok(Vec::from(tail).join("\n"))
+}
+
+fn log_files(dir: &Path) -> Result<Vec<PathBuf>, PixelAgentsError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
@@ -177,8 +177,8 @@ function App() {
     vscode.postMessage({ type: 'closeAgent', id })
   }, [])
 
-  const handleOpenAgent = useCallback((source: 'claude' | 'opencode' | 'codex') => {
-    vscode.postMessage({ type: 'openAgent', source })
+  const handleOpenAgent = useCallback((source: 'claude' | 'opencode' | 'codex', cwd?: string) => {
+    vscode.postMessage({ type: 'openAgent', source, cwd })
   }, [])
 
   const handleClick = useCallback((agentId: number, anchor: { x: number; y: number }) => {
@@ -240,6 +240,16 @@ function App() {
     }
   }, [])
 
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'togglePictureInPicture') {
+        setIsPictureInPicture((prev) => !prev)
+      }
+    }
+    window.addEventListener('message', handler)
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
   useEffect(() => {
     if (!isPictureInPicture) {
       if (pipFollowAgentId !== null && officeState.cameraFollowId === pipFollowAgentId) {
@@ -331,6 +341,7 @@ function App() {
         onZoomChange={editor.handleZoomChange}
         pipEnabled={isPictureInPicture}
         onTogglePiP={() => setIsPictureInPicture((prev) => !prev)}
+        onPipCorner={(corner) => vscode.postMessage({ type: 'setPictureInPicture', enabled: true, corner })}
       />
 
       {/* Vignette overlay */}
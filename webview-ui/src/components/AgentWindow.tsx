@@ -0,0 +1,47 @@
+import { useEffect, useState } from 'react'
+import type { MonitorSnapshot } from '../hooks/useExtensionMessages.js'
+import { vscode } from '../vscodeApi.js'
+import { MONITOR_AGENT_LABEL_FONT_DEFAULT_PX } from '../constants.js'
+import { MonitorAgentCard, MonitorDetail } from './MonitorDashboard.js'
+
+export function AgentWindow({ agentKey }: { agentKey: string }) {
+  const [snapshot, setSnapshot] = useState<MonitorSnapshot | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'monitorStateUpdate') {
+        setSnapshot(event.data.snapshot as MonitorSnapshot)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'monitorWindowReady' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  const agent = snapshot?.agents.find((a) => a.key === agentKey) ?? null
+  const baseFontPx = MONITOR_AGENT_LABEL_FONT_DEFAULT_PX
+
+  return (
+    <div style={{ height: '100%', overflow: 'auto', background: 'var(--pixel-bg)', color: 'var(--pixel-text)' }}>
+      {agent ? (
+        <>
+          <div style={{ padding: '6px 8px' }}>
+            <MonitorAgentCard
+              agent={agent}
+              selected
+              onSelect={() => {}}
+              titleFontPx={Math.max(16, baseFontPx - 2)}
+              bodyFontPx={Math.max(14, baseFontPx - 4)}
+              buttonFontPx={Math.max(13, baseFontPx - 5)}
+            />
+          </div>
+          <MonitorDetail agent={agent} bodyFontPx={Math.max(14, baseFontPx - 4)} tinyFontPx={Math.max(13, baseFontPx - 5)} />
+        </>
+      ) : (
+        <div style={{ padding: '6px 8px', fontSize: '20px', color: 'var(--pixel-text-dim)' }}>
+          {snapshot ? `${agentKey} is no longer listed` : 'Loading...'}
+        </div>
+      )}
+    </div>
+  )
+}
@@ -1,10 +1,11 @@
-import { useState } from 'react'
+import { useEffect, useState } from 'react'
 import { SettingsModal } from './SettingsModal.js'
-import type { MonitorSettings } from '../hooks/useExtensionMessages.js'
+import { vscode } from '../vscodeApi.js'
+import type { MonitorRepoView, MonitorSettings } from '../hooks/useExtensionMessages.js'
 
 interface BottomToolbarProps {
   isEditMode: boolean
-  onOpenAgent: (source: 'claude' | 'opencode' | 'codex') => void
+  onOpenAgent: (source: 'claude' | 'opencode' | 'codex', cwd?: string) => void
   onToggleEditMode: () => void
   isDebugMode: boolean
   onToggleDebugMode: () => void
@@ -60,6 +61,20 @@ export function BottomToolbar({
   const [hovered, setHovered] = useState<string | null>(null)
   const [isSettingsOpen, setIsSettingsOpen] = useState(false)
   const [isAgentMenuOpen, setIsAgentMenuOpen] = useState(false)
+  const [repos, setRepos] = useState<MonitorRepoView[]>([])
+  const [launchCwd, setLaunchCwd] = useState('')
+
+  useEffect(() => {
+    if (!isAgentMenuOpen) return
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'repos') {
+        setRepos(event.data.repos as MonitorRepoView[])
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'listRepos' })
+    return () => window.removeEventListener('message', handler)
+  }, [isAgentMenuOpen])
 
   return (
     <div style={panelStyle}>
@@ -97,9 +112,23 @@ export function BottomToolbar({
             padding: 4,
           }}
         >
-          <button style={btnBase} onClick={() => { onOpenAgent('claude'); setIsAgentMenuOpen(false) }}>Claude Code</button>
-          <button style={btnBase} onClick={() => { onOpenAgent('opencode'); setIsAgentMenuOpen(false) }}>OpenCode</button>
-          <button style={btnBase} onClick={() => { onOpenAgent('codex'); setIsAgentMenuOpen(false) }}>Codex</button>
+          {repos.length > 0 && (
+            <select
+              value={launchCwd}
+              onChange={(event) => setLaunchCwd(event.target.value)}
+              style={{ ...btnBase, maxWidth: 260 }}
+            >
+              <option value="">Choose folder...</option>
+              {repos.map((repo) => (
+                <option key={repo.path} value={repo.path}>
+                  {repo.branch ? `${repo.path} (${repo.branch})` : repo.path}
+                </option>
+              ))}
+            </select>
+          )}
+          <button style={btnBase} onClick={() => { onOpenAgent('claude', launchCwd || undefined); setIsAgentMenuOpen(false) }}>Claude Code</button>
+          <button style={btnBase} onClick={() => { onOpenAgent('opencode', launchCwd || undefined); setIsAgentMenuOpen(false) }}>OpenCode</button>
+          <button style={btnBase} onClick={() => { onOpenAgent('codex', launchCwd || undefined); setIsAgentMenuOpen(false) }}>Codex</button>
         </div>
       )}
       <button
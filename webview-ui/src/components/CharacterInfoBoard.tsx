@@ -9,7 +9,7 @@ interface CharacterInfoBoardProps {
   agentTools: Record<number, ToolActivity[]>
   agentStatuses: Record<number, string>
   subagentCharacters: SubagentCharacter[]
-  monitorActivityById: Record<number, { state: MonitorAgentView['state']; text: string }>
+  monitorActivityById: Record<number, { state: MonitorAgentView['state']; text: string; note?: string | null }>
   anchor: { x: number; y: number } | null
   hideMonitorAgent?: boolean
   demoMode?: boolean
@@ -27,7 +27,7 @@ function buildTitle(ch: Character, subagentCharacters: SubagentCharacter[]): str
 function latestActivity(
   ch: Character,
   agentTools: Record<number, ToolActivity[]>,
-  monitorActivityById: Record<number, { state: MonitorAgentView['state']; text: string }>,
+  monitorActivityById: Record<number, { state: MonitorAgentView['state']; text: string; note?: string | null }>,
   demoMode: boolean,
 ): string {
   if (demoMode && !ch.isSubagent) {
@@ -135,6 +135,7 @@ export function CharacterInfoBoard({
         <div>State: {ch.state}</div>
         <div>Status: {statusText}</div>
         <div>Activity: {activityText}</div>
+        {!demoMode && monitorActivityById[selectedId]?.note && <div>Note: {monitorActivityById[selectedId]?.note}</div>}
         <div>Tile: ({ch.tileCol}, {ch.tileRow})</div>
         <div>Seat: {ch.seatId ?? 'none'}</div>
         <div>Tool: {ch.currentTool ?? 'none'}</div>
@@ -0,0 +1,68 @@
+import { useEffect, useState } from 'react'
+import type { MonitorSnapshot } from '../hooks/useExtensionMessages.js'
+import { vscode } from '../vscodeApi.js'
+import { MONITOR_STATE_COLORS } from '../constants.js'
+
+export function DashboardWindow() {
+  const [snapshot, setSnapshot] = useState<MonitorSnapshot | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'monitorStateUpdate') {
+        setSnapshot(event.data.snapshot as MonitorSnapshot)
+      }
+    }
+    const keyHandler = (event: KeyboardEvent) => {
+      if (event.key === 'Escape') {
+        vscode.postMessage({ type: 'closeDashboardWindow' })
+      }
+    }
+    window.addEventListener('message', handler)
+    window.addEventListener('keydown', keyHandler)
+    vscode.postMessage({ type: 'monitorWindowReady' })
+    return () => {
+      window.removeEventListener('message', handler)
+      window.removeEventListener('keydown', keyHandler)
+    }
+  }, [])
+
+  if (!snapshot) {
+    return <div style={{ padding: 16, fontSize: '28px', color: 'var(--pixel-text-dim)', background: 'var(--pixel-bg)', height: '100%' }}>Loading...</div>
+  }
+
+  const { summary } = snapshot
+  return (
+    <div style={{ height: '100%', overflow: 'auto', padding: 16, boxSizing: 'border-box', background: 'var(--pixel-bg)', color: 'var(--pixel-text)' }}>
+      <div style={{ display: 'flex', gap: 24, fontSize: '32px', marginBottom: 12 }}>
+        <span>total {summary.total}</span>
+        <span style={{ color: '#74d680' }}>active {summary.active}</span>
+        <span style={{ color: '#ffd166' }}>waiting {summary.waiting}</span>
+        <span>done {summary.done}</span>
+        <span style={{ color: '#ff6b6b' }}>error {summary.error}</span>
+        <span>pr pending {summary.pr_pending}</span>
+        <span>alerts {summary.alerts}</span>
+      </div>
+      <div style={{ display: 'grid', gridTemplateColumns: 'repeat(auto-fill, minmax(320px, 1fr))', gap: 12 }}>
+        {snapshot.agents.map((agent) => (
+          <div key={agent.key} style={{ border: '2px solid var(--pixel-border)', padding: '8px 10px', minWidth: 0 }}>
+            <div style={{ fontSize: '26px', color: MONITOR_STATE_COLORS[agent.state], overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}>
+              {agent.pinned ? '* ' : ''}{agent.display_name} ({agent.state})
+            </div>
+            <div style={{ fontSize: '20px', color: 'var(--pixel-text-dim)', overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}>
+              {agent.repo_path || 'Repo not bound'}
+              {agent.git ? ` | ${agent.git.branch || 'unknown'}${agent.git.dirty ? ' dirty' : ''}` : ''}
+            </div>
+            <div style={{ fontSize: '20px', color: 'var(--pixel-text-dim)', overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}>
+              {agent.last_text || 'No recent text'}
+            </div>
+            {agent.alerts.length > 0 && (
+              <div style={{ fontSize: '20px', color: '#ff8f8f' }}>
+                {agent.alerts.map((alert) => alert.kind).join(', ')}
+              </div>
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  )
+}
@@ -1,10 +1,11 @@
-import { useState } from 'react'
-import type { MonitorAgentView, MonitorSnapshot } from '../hooks/useExtensionMessages.js'
-import { vscode } from '../vscodeApi.js'
+import { useEffect, useState } from 'react'
+import type { MonitorAgentView, MonitorEventView, MonitorRepoView, MonitorSnapshot, SessionSearchHit, TranscriptEntry } from '../hooks/useExtensionMessages.js'
+import { isDesktopRuntime, vscode } from '../vscodeApi.js'
 import {
   MONITOR_AGENT_LABEL_FONT_DEFAULT_PX,
   MONITOR_AGENT_LABEL_FONT_MAX_PX,
   MONITOR_AGENT_LABEL_FONT_MIN_PX,
+  MONITOR_STATE_COLORS,
 } from '../constants.js'
 
 interface MonitorDashboardProps {
@@ -46,15 +47,19 @@ function clampLabelFontPx(value: number): number {
   return Math.min(MONITOR_AGENT_LABEL_FONT_MAX_PX, Math.max(MONITOR_AGENT_LABEL_FONT_MIN_PX, rounded))
 }
 
-function stateTextColor(state: MonitorAgentView['state']): string {
-  if (state === 'running' || state === 'thinking') return '#74d680'
-  if (state === 'error') return '#ff6b6b'
-  if (state === 'waiting') return '#ffd166'
-  return '#ffffff'
-}
-
 export function MonitorDashboard({ snapshot, agentLabelFontPx }: MonitorDashboardProps) {
   const [selectedKey, setSelectedKey] = useState<string | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'selectMonitorAgent') {
+        setSelectedKey(event.data.agentKey as string)
+      }
+    }
+    window.addEventListener('message', handler)
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
   if (!snapshot) {
     return null
   }
@@ -66,18 +71,64 @@ export function MonitorDashboard({ snapshot, agentLabelFontPx }: MonitorDashboar
   const buttonFontPx = Math.max(13, baseFontPx - 5)
 
   const selected = snapshot.agents.find((a) => a.key === selectedKey) || null
+  const unhealthySources = (snapshot.source_status ?? []).filter(
+    (source) => source.status === 'error' || source.status === 'degraded',
+  )
 
   return (
     <div style={panelStyle}>
       <div style={{ padding: '6px 8px', borderBottom: '1px solid var(--pixel-border)' }}>
-        <div style={sectionTitleStyle}>Monitor</div>
+        <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center' }}>
+          <div style={sectionTitleStyle}>Monitor</div>
+          {isDesktopRuntime && (
+            <div style={{ display: 'flex', gap: 4 }}>
+              {(['markdown', 'json'] as const).map((format) => (
+                <button
+                  key={format}
+                  style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+                  title="Copy the summary and agent list to the clipboard"
+                  onClick={() => {
+                    vscode.postMessage({ type: 'monitorCopySnapshot', format })
+                  }}
+                >
+                  {format === 'json' ? 'Copy JSON' : 'Copy MD'}
+                </button>
+              ))}
+              <button
+                style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+                title="Open a full-screen dashboard window"
+                onClick={() => {
+                  vscode.postMessage({ type: 'openDashboardWindow' })
+                }}
+              >
+                Dashboard
+              </button>
+            </div>
+          )}
+        </div>
         <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>
           total {snapshot.summary.total} | active {snapshot.summary.active} | waiting {snapshot.summary.waiting} | done {snapshot.summary.done} | error {snapshot.summary.error}
         </div>
         <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>
           pr pending {snapshot.summary.pr_pending} | alerts {snapshot.summary.alerts}
         </div>
+        {unhealthySources.map((source) => (
+          <div
+            key={source.source}
+            title={source.error ?? undefined}
+            style={{ fontSize: `${tinyFontPx}px`, color: source.status === 'error' ? '#ff6b6b' : '#ffd166' }}
+          >
+            {source.source} {source.status}
+            {source.error ? `: ${source.error}` : source.parse_errors > 0 ? `: ${source.parse_errors} parse errors` : ''}
+          </div>
+        ))}
       </div>
+      <SessionSearch
+        knownKeys={snapshot.agents.map((agent) => agent.key)}
+        onSelect={setSelectedKey}
+        bodyFontPx={bodyFontPx}
+        tinyFontPx={tinyFontPx}
+      />
       <div style={{ padding: '4px 6px', display: 'flex', flexDirection: 'column', gap: 6 }}>
         {snapshot.agents.slice(0, 20).map((agent) => (
           <MonitorAgentCard
@@ -96,7 +147,84 @@ export function MonitorDashboard({ snapshot, agentLabelFontPx }: MonitorDashboar
   )
 }
 
-function MonitorAgentCard({
+function SessionSearch({
+  knownKeys,
+  onSelect,
+  bodyFontPx,
+  tinyFontPx,
+}: {
+  knownKeys: string[]
+  onSelect: (key: string) => void
+  bodyFontPx: number
+  tinyFontPx: number
+}) {
+  const [query, setQuery] = useState('')
+  const [hits, setHits] = useState<SessionSearchHit[] | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'sessionSearchResults') {
+        setHits(event.data.hits as SessionSearchHit[])
+      }
+    }
+    window.addEventListener('message', handler)
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ padding: '4px 8px', borderBottom: '1px solid var(--pixel-border)' }}>
+      <form
+        style={{ display: 'flex', gap: 4 }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          if (query.trim()) {
+            vscode.postMessage({ type: 'searchSessions', query: query.trim() })
+          } else {
+            setHits(null)
+          }
+        }}
+      >
+        <input
+          value={query}
+          onChange={(event) => setQuery(event.target.value)}
+          placeholder="Search sessions"
+          style={{ flex: 1, fontSize: `${bodyFontPx}px`, background: 'var(--pixel-btn-bg)', color: 'var(--pixel-text)', border: '1px solid var(--pixel-border)' }}
+        />
+        <button type="submit" style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}>Search</button>
+        {hits && (
+          <button type="button" style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }} onClick={() => setHits(null)}>
+            Clear
+          </button>
+        )}
+      </form>
+      {hits && hits.length === 0 && (
+        <div style={{ fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text-dim)' }}>No matching sessions</div>
+      )}
+      {hits?.map((hit) => {
+        const known = knownKeys.includes(hit.key)
+        return (
+          <div
+            key={hit.key}
+            onClick={known ? () => onSelect(hit.key) : undefined}
+            title={hit.repo_path ?? undefined}
+            style={{ marginTop: 4, cursor: known ? 'pointer' : 'default' }}
+          >
+            <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text)' }}>
+              {hit.title || hit.session_id} ({hit.source}, {hit.matches} {hit.matches === 1 ? 'match' : 'matches'})
+            </div>
+            {hit.snippets.map((snippet, index) => (
+              <div key={index} style={{ fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text-dim)', wordBreak: 'break-word' }}>
+                {snippet}
+              </div>
+            ))}
+          </div>
+        )
+      })}
+    </div>
+  )
+}
+
+export function MonitorAgentCard({
   agent,
   selected,
   onSelect,
@@ -111,11 +239,12 @@ function MonitorAgentCard({
   bodyFontPx: number
   buttonFontPx: number
 }) {
-  const head = `${agent.display_name} (${agent.state})`
-  const titleColor = stateTextColor(agent.state)
+  const head = `${agent.pinned ? '* ' : ''}${agent.display_name} (${agent.state})${agent.muted ? ' [muted]' : ''}`
+  const titleColor = MONITOR_STATE_COLORS[agent.state]
   return (
     <div style={{ border: selected ? '1px solid var(--pixel-accent)' : '1px solid var(--pixel-border)', padding: '5px 6px', cursor: 'pointer' }} onClick={onSelect}>
       <div style={{ fontSize: `${titleFontPx}px`, color: titleColor }}>{head}</div>
+      {agent.note && <div style={{ fontSize: `${bodyFontPx}px`, color: '#ffd166' }}>note {agent.note}</div>}
       <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>{agent.last_text || 'No recent text'}</div>
       <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>{agent.repo_path || 'Repo not bound'}</div>
       {agent.git && (
@@ -123,6 +252,25 @@ function MonitorAgentCard({
           git {agent.git.branch || 'unknown'} | {agent.git.dirty ? 'dirty' : 'clean'}
         </div>
       )}
+      {typeof agent.pid === 'number' && (
+        <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>
+          pid {agent.pid} | cpu {(agent.cpu_percent ?? 0).toFixed(1)}% | rss {Math.round((agent.rss_bytes ?? 0) / (1024 * 1024))} MB
+        </div>
+      )}
+      {agent.alerts.map((alert) => (
+        <div key={`${alert.kind}:${alert.ts_ms}`} style={{ display: 'flex', justifyContent: 'space-between', gap: 4, fontSize: `${bodyFontPx}px`, color: '#ff8f8f' }}>
+          <span>{alert.kind} | {alert.message}</span>
+          <button
+            style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+            title="Dismiss alert"
+            onClick={() => {
+              vscode.postMessage({ type: 'monitorDismissAlert', agentKey: agent.key, tsMs: alert.ts_ms })
+            }}
+          >
+            x
+          </button>
+        </div>
+      ))}
       {agent.pr && (
         <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>
           pr {agent.pr.has_open_pr ? agent.pr.state || 'OPEN' : 'none'}
@@ -141,6 +289,16 @@ function MonitorAgentCard({
         >
           {agent.repo_path ? 'Open Repo' : 'Bind Repo'}
         </button>
+        {agent.repo_path && (
+          <button
+            style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+            onClick={() => {
+              vscode.postMessage({ type: 'monitorOpenInEditor', repoPath: agent.repo_path })
+            }}
+          >
+            Editor
+          </button>
+        )}
         <button
           style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
           onClick={() => {
@@ -149,13 +307,31 @@ function MonitorAgentCard({
         >
           Copy Session
         </button>
+        <button
+          style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+          onClick={() => {
+            vscode.postMessage({ type: 'monitorCopyText', text: `pixel-agents://agent/${encodeURIComponent(agent.key)}` })
+          }}
+        >
+          Copy Link
+        </button>
+        {isDesktopRuntime && (
+          <button
+            style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+            onClick={() => {
+              vscode.postMessage({ type: 'monitorOpenAgentWindow', agentKey: agent.key })
+            }}
+          >
+            Pop Out
+          </button>
+        )}
         <button
           style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
           onClick={() => {
             vscode.postMessage({ type: 'monitorRevealTerminal', sessionId: agent.session_id, repoPath: agent.repo_path || '' })
           }}
         >
-          Reveal Terminal
+          Open Terminal
         </button>
         <button
           style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
@@ -170,28 +346,266 @@ function MonitorAgentCard({
         >
           Copy Resume
         </button>
+        <button
+          style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+          onClick={() => {
+            vscode.postMessage({ type: 'monitorPinAgent', agentKey: agent.key, pinned: !agent.pinned })
+          }}
+        >
+          {agent.pinned ? 'Unpin' : 'Pin'}
+        </button>
+        <button
+          style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+          onClick={() => {
+            vscode.postMessage({ type: 'monitorMuteAgent', agentKey: agent.key, muted: !agent.muted })
+          }}
+        >
+          {agent.muted ? 'Unmute' : 'Mute'}
+        </button>
+        {(agent.state === 'done' || agent.state === 'idle') && (
+          <button
+            style={{ ...actionBtn, fontSize: `${buttonFontPx}px` }}
+            onClick={() => {
+              vscode.postMessage({ type: 'monitorArchiveAgent', agentKey: agent.key })
+            }}
+          >
+            Archive
+          </button>
+        )}
       </div>
     </div>
   )
 }
 
-function MonitorDetail({ agent, bodyFontPx, tinyFontPx }: { agent: MonitorAgentView; bodyFontPx: number; tinyFontPx: number }) {
+export function MonitorDetail({ agent, bodyFontPx, tinyFontPx }: { agent: MonitorAgentView; bodyFontPx: number; tinyFontPx: number }) {
+  const [transcript, setTranscript] = useState<TranscriptEntry[] | null>(null)
+  const [nameDraft, setNameDraft] = useState('')
+  const [noteDraft, setNoteDraft] = useState(agent.note ?? '')
+  const [promptDraft, setPromptDraft] = useState('')
+  const [repos, setRepos] = useState<MonitorRepoView[]>([])
+  const [olderEvents, setOlderEvents] = useState<MonitorEventView[] | null>(null)
+
+  useEffect(() => {
+    setTranscript(null)
+    setNameDraft('')
+    setNoteDraft(agent.note ?? '')
+    setPromptDraft('')
+    setOlderEvents(null)
+    const handler = (event: MessageEvent) => {
+      const msg = event.data
+      if (msg?.type === 'monitorTranscript' && msg.source === agent.source && msg.sessionId === agent.session_id) {
+        setTranscript(msg.entries as TranscriptEntry[])
+      }
+      if (msg?.type === 'monitorAgentEvents' && msg.agentKey === agent.key) {
+        setOlderEvents((prev) => [...(prev ?? []), ...(msg.events as MonitorEventView[])])
+      }
+      if (msg?.type === 'clipboardText' && msg.agentKey === agent.key && typeof msg.text === 'string') {
+        setPromptDraft(msg.text)
+      }
+    }
+    window.addEventListener('message', handler)
+    return () => window.removeEventListener('message', handler)
+  }, [agent.source, agent.session_id, agent.key])
+
+  useEffect(() => {
+    if (agent.repo_path) return
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'repos') {
+        setRepos(event.data.repos as MonitorRepoView[])
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'listRepos' })
+    return () => window.removeEventListener('message', handler)
+  }, [agent.repo_path])
+
   return (
     <div style={{ borderTop: '1px solid var(--pixel-border)', padding: '6px 8px' }}>
-      <div style={sectionTitleStyle}>Details</div>
+      <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center' }}>
+        <div style={sectionTitleStyle}>Details</div>
+        <div style={{ display: 'flex', gap: 4 }}>
+          {transcript && transcript.length > 0 && (['markdown', 'html'] as const).map((format) => (
+            <button
+              key={format}
+              style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}
+              onClick={() => {
+                vscode.postMessage({ type: 'monitorExportTranscript', source: agent.source, sessionId: agent.session_id, format })
+              }}
+            >
+              {format === 'html' ? 'Export HTML' : 'Export MD'}
+            </button>
+          ))}
+          {agent.state !== 'done' && ([false, true] as const).map((force) => (
+            <button
+              key={force ? 'force' : 'stop'}
+              style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}
+              onClick={() => {
+                vscode.postMessage({ type: 'monitorKillAgent', agentKey: agent.key, force })
+              }}
+            >
+              {force ? 'Force Kill' : 'Stop'}
+            </button>
+          ))}
+          {agent.source !== 'claude' && (
+            <button
+              style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}
+              onClick={() => {
+                vscode.postMessage({ type: 'monitorRevealSession', source: agent.source, sessionId: agent.session_id })
+              }}
+            >
+              Reveal Log
+            </button>
+          )}
+          <button
+            style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}
+            onClick={() => {
+              if (transcript) {
+                setTranscript(null)
+              } else {
+                vscode.postMessage({ type: 'monitorGetTranscript', source: agent.source, sessionId: agent.session_id })
+              }
+            }}
+          >
+            {transcript ? 'Hide Transcript' : 'Transcript'}
+          </button>
+        </div>
+      </div>
       <div style={{ fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>source {agent.source} | session {agent.session_id}</div>
+      {!agent.repo_path && repos.length > 0 && (
+        <select
+          value=""
+          onChange={(event) => {
+            if (event.target.value) {
+              vscode.postMessage({ type: 'monitorBindRepo', source: agent.source, sessionId: agent.session_id, repoPath: event.target.value })
+            }
+          }}
+          style={{ marginTop: 4, width: '100%', fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0 }}
+        >
+          <option value="">Bind to known repo...</option>
+          {repos.map((repo) => (
+            <option key={repo.path} value={repo.path}>{repo.path}</option>
+          ))}
+        </select>
+      )}
+      <form
+        style={{ display: 'flex', gap: 4, marginTop: 4 }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          vscode.postMessage({ type: 'monitorSetAgentName', agentKey: agent.key, name: nameDraft })
+          setNameDraft('')
+        }}
+      >
+        <input
+          value={nameDraft}
+          onChange={(event) => setNameDraft(event.target.value)}
+          placeholder={agent.display_name}
+          style={{ flex: 1, minWidth: 0, fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }}
+        />
+        <button type="submit" style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}>
+          {nameDraft.trim() ? 'Rename' : 'Reset Name'}
+        </button>
+      </form>
+      <form
+        style={{ display: 'flex', gap: 4, marginTop: 4 }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          vscode.postMessage({ type: 'monitorSetAgentNote', agentKey: agent.key, text: noteDraft })
+        }}
+      >
+        <input
+          value={noteDraft}
+          onChange={(event) => setNoteDraft(event.target.value)}
+          placeholder="Note"
+          style={{ flex: 1, minWidth: 0, fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }}
+        />
+        <button type="submit" style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}>
+          {noteDraft.trim() ? 'Save Note' : 'Clear Note'}
+        </button>
+      </form>
+      {agent.state !== 'running' && agent.state !== 'thinking' && (
+        <form
+          style={{ display: 'flex', gap: 4, marginTop: 4 }}
+          onSubmit={(event) => {
+            event.preventDefault()
+            if (!promptDraft.trim()) return
+            vscode.postMessage({ type: 'monitorSendPrompt', source: agent.source, sessionId: agent.session_id, text: promptDraft })
+            setPromptDraft('')
+          }}
+        >
+          <input
+            value={promptDraft}
+            onChange={(event) => setPromptDraft(event.target.value)}
+            placeholder="Reply, e.g. yes, continue"
+            style={{ flex: 1, minWidth: 0, fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }}
+          />
+          {isDesktopRuntime && (
+            <button
+              type="button"
+              style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}
+              title="Fill the reply with the clipboard text"
+              onClick={() => {
+                vscode.postMessage({ type: 'readClipboardText', agentKey: agent.key })
+              }}
+            >
+              Paste
+            </button>
+          )}
+          <button type="submit" style={{ ...actionBtn, fontSize: `${tinyFontPx}px` }}>
+            Send
+          </button>
+        </form>
+      )}
       {agent.files_touched.length > 0 && (
         <div style={{ marginTop: 4, fontSize: `${bodyFontPx}px`, color: 'var(--pixel-text-dim)' }}>
-          files {agent.files_touched.slice(0, 10).join(', ')}
+          files{' '}
+          {agent.files_touched.slice(0, 10).map((file, index) => (
+            <span key={file}>
+              {index > 0 && ', '}
+              {agent.repo_path ? (
+                <span
+                  style={{ cursor: 'pointer', textDecoration: 'underline' }}
+                  onClick={() => vscode.postMessage({ type: 'monitorOpenInEditor', repoPath: agent.repo_path, file })}
+                >
+                  {file}
+                </span>
+              ) : file}
+            </span>
+          ))}
+        </div>
+      )}
+      {transcript ? (
+        <div style={{ marginTop: 4, display: 'flex', flexDirection: 'column', gap: 4 }}>
+          {transcript.length === 0 && (
+            <div style={{ fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text-dim)' }}>No transcript entries</div>
+          )}
+          {transcript.map((entry, index) => (
+            <div key={index} style={{ fontSize: `${tinyFontPx}px`, color: entry.role === 'user' ? '#ffd166' : 'var(--pixel-text-dim)', whiteSpace: 'pre-wrap', wordBreak: 'break-word' }}>
+              {entry.ts_ms ? `${new Date(entry.ts_ms).toLocaleTimeString()} | ` : ''}
+              {entry.tool ? `${entry.role} ${entry.tool}` : entry.role} | {entry.text || '-'}
+            </div>
+          ))}
+        </div>
+      ) : (
+        <div style={{ marginTop: 4, display: 'flex', flexDirection: 'column', gap: 2 }}>
+          {[...agent.recent_events.slice(0, 20), ...(olderEvents ?? [])].map((event, index) => (
+            <div key={`${event.ts_ms}:${index}`} style={{ fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text-dim)' }}>
+              {new Date(event.ts_ms).toLocaleTimeString()} | {event.type}/{event.state_hint} | {event.text || '-'}
+            </div>
+          ))}
+          {(agent.source === 'codex' || agent.source === 'opencode') && (
+            <button
+              style={{ ...actionBtn, fontSize: `${tinyFontPx}px`, alignSelf: 'flex-start' }}
+              onClick={() => {
+                const shown = [...agent.recent_events.slice(0, 20), ...(olderEvents ?? [])]
+                const beforeTs = shown.length > 0 ? Math.min(...shown.map((event) => event.ts_ms)) : null
+                vscode.postMessage({ type: 'monitorGetAgentEvents', agentKey: agent.key, beforeTs })
+              }}
+            >
+              Load Older Events
+            </button>
+          )}
         </div>
       )}
-      <div style={{ marginTop: 4, display: 'flex', flexDirection: 'column', gap: 2 }}>
-        {agent.recent_events.slice(0, 20).map((event, index) => (
-          <div key={`${event.ts_ms}:${index}`} style={{ fontSize: `${tinyFontPx}px`, color: 'var(--pixel-text-dim)' }}>
-            {new Date(event.ts_ms).toLocaleTimeString()} | {event.type}/{event.state_hint} | {event.text || '-'}
-          </div>
-        ))}
-      </div>
     </div>
   )
 }
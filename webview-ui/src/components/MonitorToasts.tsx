@@ -4,6 +4,7 @@ import {
   MONITOR_AGENT_LABEL_FONT_DEFAULT_PX,
   MONITOR_AGENT_LABEL_FONT_MAX_PX,
   MONITOR_AGENT_LABEL_FONT_MIN_PX,
+  MONITOR_STATE_COLORS,
   MONITOR_TOAST_DURATION_MS,
 } from '../constants.js'
 
@@ -36,7 +37,7 @@ export function MonitorToasts({ toasts, onDismiss, agentLabelFontPx }: MonitorTo
       {toasts.map((toast) => (
         <div key={toast.id} style={{ minWidth: 220, maxWidth: 360, background: 'var(--pixel-bg)', border: '2px solid var(--pixel-border)', boxShadow: 'var(--pixel-shadow)', padding: '5px 7px' }}>
           <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', gap: 8 }}>
-            <div style={{ fontSize: `${titleFontPx}px`, color: toast.kind === 'error' ? '#f38b8b' : '#9fe6b2' }}>{toast.title}</div>
+            <div style={{ fontSize: `${titleFontPx}px`, color: toast.kind === 'error' ? '#f38b8b' : toast.kind === 'waiting' || toast.kind === 'approval' || toast.kind === 'stuck' ? MONITOR_STATE_COLORS.waiting : '#9fe6b2' }}>{toast.title}</div>
             <button
               onClick={() => onDismiss(toast.id)}
               aria-label="Dismiss notification"
@@ -1,8 +1,8 @@
-import { useState } from 'react'
+import { useEffect, useState } from 'react'
 import { vscode } from '../vscodeApi.js'
 import { isSoundEnabled, setSoundEnabled } from '../notificationSound.js'
 import { DEFAULT_MONITOR_SETTINGS } from '../hooks/useExtensionMessages.js'
-import type { MonitorSettings } from '../hooks/useExtensionMessages.js'
+import type { ArchivedAgent, DataDirView, DetectedAgent, Hotkeys, LayoutBackupView, LayoutListView, MonitorSettings, NotificationHistoryEntry, PushSettings, QuietHours, RepoBindingView, ResetReport, UpdateInfo, UsageStatsPayload, UsageTotals, Webhook } from '../hooks/useExtensionMessages.js'
 import {
   MONITOR_CHARACTER_LIMIT,
   MONITOR_AGENT_LABEL_FONT_MAX_PX,
@@ -117,6 +117,20 @@ export function SettingsModal({ isOpen, onClose, isDebugMode, onToggleDebugMode,
         >
           Open Sessions Folder
         </button>
+        <button
+          onClick={() => {
+            vscode.postMessage({ type: 'monitorDeepScan' })
+            onClose()
+          }}
+          onMouseEnter={() => setHovered('deepScan')}
+          onMouseLeave={() => setHovered(null)}
+          style={{
+            ...menuItemBase,
+            background: hovered === 'deepScan' ? 'rgba(255, 255, 255, 0.08)' : 'transparent',
+          }}
+        >
+          Rescan All Sessions
+        </button>
         <button
           onClick={() => {
             vscode.postMessage({ type: 'exportLayout' })
@@ -145,6 +159,35 @@ export function SettingsModal({ isOpen, onClose, isDebugMode, onToggleDebugMode,
         >
           Import Layout
         </button>
+        <button
+          onClick={() => {
+            vscode.postMessage({ type: 'exportBundle' })
+            onClose()
+          }}
+          onMouseEnter={() => setHovered('exportBundle')}
+          onMouseLeave={() => setHovered(null)}
+          title="Layout, seats, monitor and desktop settings, names, and repo bindings in one file"
+          style={{
+            ...menuItemBase,
+            background: hovered === 'exportBundle' ? 'rgba(255, 255, 255, 0.08)' : 'transparent',
+          }}
+        >
+          Export Settings Bundle
+        </button>
+        <button
+          onClick={() => {
+            vscode.postMessage({ type: 'importBundle' })
+            onClose()
+          }}
+          onMouseEnter={() => setHovered('importBundle')}
+          onMouseLeave={() => setHovered(null)}
+          style={{
+            ...menuItemBase,
+            background: hovered === 'importBundle' ? 'rgba(255, 255, 255, 0.08)' : 'transparent',
+          }}
+        >
+          Import Settings Bundle
+        </button>
         <button
           onClick={() => {
             const newVal = !isSoundEnabled()
@@ -238,6 +281,7 @@ export function SettingsModal({ isOpen, onClose, isDebugMode, onToggleDebugMode,
           <MonitorToggle label="Codex Source" value={monitorSettings.enableCodex} onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, enableCodex: value })} />
           <MonitorToggle label="Git Polling" value={monitorSettings.enableGit} onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, enableGit: value })} />
           <MonitorToggle label="PR Polling" value={monitorSettings.enablePr} onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, enablePr: value })} />
+          <MonitorToggle label="Notify When Waiting" value={monitorSettings.notifyWaiting} onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, notifyWaiting: value })} />
           <MonitorInterval label="Flush ms" value={monitorSettings.flushIntervalMs} onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, flushIntervalMs: value })} />
           <MonitorInterval label="Source poll ms" value={monitorSettings.sourcePollIntervalMs} onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, sourcePollIntervalMs: value })} />
           <MonitorInterval label="Git poll ms" value={monitorSettings.gitPollIntervalMs} onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, gitPollIntervalMs: value })} />
@@ -258,6 +302,34 @@ export function SettingsModal({ isOpen, onClose, isDebugMode, onToggleDebugMode,
             max={MONITOR_CHARACTER_LIMIT}
             step={1}
           />
+          <MonitorInterval
+            label="Stuck After min (0 = off)"
+            value={Math.round(monitorSettings.stuckAfterMs / 60_000)}
+            onChange={(value) => onUpdateMonitorSettings({ ...monitorSettings, stuckAfterMs: Math.max(0, value) * 60_000 })}
+            min={0}
+            step={1}
+          />
+          <MonitorToggle
+            label="Hide Done Agents"
+            value={(monitorSettings.agentFilter?.excludeStates ?? []).includes('done')}
+            onChange={(value) => {
+              const excludeStates = (monitorSettings.agentFilter?.excludeStates ?? []).filter((state) => state !== 'done')
+              onUpdateMonitorSettings({
+                ...monitorSettings,
+                agentFilter: { ...monitorSettings.agentFilter, excludeStates: value ? [...excludeStates, 'done'] : excludeStates },
+              })
+            }}
+          />
+          <MonitorInterval
+            label="Max Agent Age h (0 = any)"
+            value={Math.round((monitorSettings.agentFilter?.maxAgeMs ?? 0) / 3_600_000)}
+            onChange={(value) => onUpdateMonitorSettings({
+              ...monitorSettings,
+              agentFilter: { ...monitorSettings.agentFilter, maxAgeMs: value > 0 ? value * 3_600_000 : undefined },
+            })}
+            min={0}
+            step={1}
+          />
           <button
             onClick={() => onUpdateMonitorSettings(DEFAULT_MONITOR_SETTINGS)}
             style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
@@ -265,11 +337,1124 @@ export function SettingsModal({ isOpen, onClose, isDebugMode, onToggleDebugMode,
             Reset Monitor Settings
           </button>
         </div>
+        <ErrorPatternsSection monitorSettings={monitorSettings} onUpdateMonitorSettings={onUpdateMonitorSettings} />
+        <NotificationTogglesSection monitorSettings={monitorSettings} onUpdateMonitorSettings={onUpdateMonitorSettings} />
+        <NotificationSnoozeSection />
+        <QuietHoursSection />
+        <NotificationHistorySection />
+        <UsageStatsSection />
+        <LayoutsSection />
+        <LayoutBackupsSection />
+        <EditorCommandSection />
+        <RepoBindingsSection />
+        <ArchivedAgentsSection />
+        <DetectedAgentsSection />
+        <WebhooksSection />
+        <PushSection />
+        <StartupSection />
+        <HotkeysSection />
+        <UpdatesSection />
+        <DataDirSection />
+        <ResetDataSection />
       </div>
     </>
   )
 }
 
+const ERROR_PATTERN_SOURCES = ['claude', 'opencode', 'codex', 'windsurf']
+
+function ErrorPatternsSection({ monitorSettings, onUpdateMonitorSettings }: { monitorSettings: MonitorSettings; onUpdateMonitorSettings: (settings: MonitorSettings) => void }) {
+  const [source, setSource] = useState(ERROR_PATTERN_SOURCES[0])
+  const [include, setInclude] = useState('')
+  const [exclude, setExclude] = useState('')
+
+  useEffect(() => {
+    const patterns = monitorSettings.errorPatterns?.[source]
+    setInclude((patterns?.include ?? []).join('\n'))
+    setExclude((patterns?.exclude ?? []).join('\n'))
+  }, [monitorSettings.errorPatterns, source])
+
+  const lines = (text: string) => text.split('\n').map((line) => line.trim()).filter(Boolean)
+  const areaStyle: React.CSSProperties = { width: '100%', boxSizing: 'border-box', fontSize: '16px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px', resize: 'vertical' }
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Error Patterns</div>
+      <div style={{ display: 'flex', flexWrap: 'wrap' }}>
+        {ERROR_PATTERN_SOURCES.map((name) => (
+          <button
+            key={name}
+            onClick={() => setSource(name)}
+            style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: source === name ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+          >
+            {name}
+          </button>
+        ))}
+      </div>
+      <form
+        style={{ padding: '2px 10px' }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          const errorPatterns = { ...monitorSettings.errorPatterns }
+          if (lines(include).length === 0 && lines(exclude).length === 0) {
+            delete errorPatterns[source]
+          } else {
+            errorPatterns[source] = { include: lines(include), exclude: lines(exclude) }
+          }
+          onUpdateMonitorSettings({ ...monitorSettings, errorPatterns })
+        }}
+      >
+        <textarea
+          value={include}
+          onChange={(event) => setInclude(event.target.value)}
+          placeholder="Count as error (one regex per line)"
+          title="Matched against the agent's latest event text"
+          rows={2}
+          style={areaStyle}
+        />
+        <textarea
+          value={exclude}
+          onChange={(event) => setExclude(event.target.value)}
+          placeholder="Never an error (one regex per line), e.g. ^bash: "
+          title="Matched against the agent's latest event text"
+          rows={2}
+          style={areaStyle}
+        />
+        <button type="submit" style={{ ...menuItemBase, width: 'auto', padding: '2px 8px', fontSize: '18px' }}>Save Patterns</button>
+      </form>
+    </div>
+  )
+}
+
+function NotificationTogglesSection({ monitorSettings, onUpdateMonitorSettings }: { monitorSettings: MonitorSettings; onUpdateMonitorSettings: (settings: MonitorSettings) => void }) {
+  const [repo, setRepo] = useState('')
+  const sources = monitorSettings.notifySources ?? {}
+  const repos = monitorSettings.notifyRepos ?? {}
+
+  const setSource = (name: string, enabled: boolean) => {
+    const notifySources = { ...sources }
+    if (enabled) {
+      delete notifySources[name]
+    } else {
+      notifySources[name] = false
+    }
+    onUpdateMonitorSettings({ ...monitorSettings, notifySources })
+  }
+  const setRepoEnabled = (path: string, enabled: boolean | null) => {
+    const notifyRepos = { ...repos }
+    if (enabled === null) {
+      delete notifyRepos[path]
+    } else {
+      notifyRepos[path] = enabled
+    }
+    onUpdateMonitorSettings({ ...monitorSettings, notifyRepos })
+  }
+  const inputStyle: React.CSSProperties = { flex: 1, minWidth: 0, fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Notify From</div>
+      <div style={{ display: 'flex', flexWrap: 'wrap' }}>
+        {ERROR_PATTERN_SOURCES.map((name) => {
+          const enabled = sources[name] !== false
+          return (
+            <button
+              key={name}
+              onClick={() => setSource(name, !enabled)}
+              style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: enabled ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+            >
+              {name}
+            </button>
+          )
+        })}
+      </div>
+      {Object.entries(repos).map(([path, enabled]) => (
+        <div key={path} style={{ display: 'flex', alignItems: 'center', gap: 4, padding: '2px 10px' }}>
+          <span style={{ flex: 1, minWidth: 0, fontSize: '18px', overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }} title={path}>{path}</span>
+          <button
+            onClick={() => setRepoEnabled(path, !enabled)}
+            title="Repo switches override the source switches"
+            style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: enabled ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+          >
+            {enabled ? 'Always' : 'Never'}
+          </button>
+          <button
+            onClick={() => setRepoEnabled(path, null)}
+            style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+          >
+            Remove
+          </button>
+        </div>
+      ))}
+      <form
+        style={{ display: 'flex', gap: 4, padding: '2px 10px' }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          if (repo.trim()) {
+            setRepoEnabled(repo.trim(), false)
+            setRepo('')
+          }
+        }}
+      >
+        <input
+          value={repo}
+          onChange={(event) => setRepo(event.target.value)}
+          placeholder="Repo path to silence"
+          style={inputStyle}
+        />
+        <button type="submit" style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}>
+          Add
+        </button>
+      </form>
+    </div>
+  )
+}
+
+const TEST_NOTIFICATION_KINDS = ['done', 'error', 'waiting', 'approval']
+
+function NotificationSnoozeSection() {
+  const [untilMs, setUntilMs] = useState<number | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'notificationSnooze') {
+        setUntilMs(event.data.untilMs as number | null)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getNotificationSnooze' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>
+        {untilMs ? `Notifications snoozed until ${new Date(untilMs).toLocaleTimeString()}` : 'Snooze Notifications'}
+      </div>
+      <div style={{ display: 'flex' }}>
+        {[30, 60, 120].map((minutes) => (
+          <button
+            key={minutes}
+            onClick={() => vscode.postMessage({ type: 'snoozeNotifications', minutes })}
+            style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+          >
+            {minutes < 60 ? `${minutes}m` : `${minutes / 60}h`}
+          </button>
+        ))}
+        {untilMs && (
+          <button
+            onClick={() => vscode.postMessage({ type: 'snoozeNotifications', minutes: 0 })}
+            style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+          >
+            Resume
+          </button>
+        )}
+      </div>
+      <div style={{ display: 'flex' }}>
+        {TEST_NOTIFICATION_KINDS.map((kind) => (
+          <button
+            key={kind}
+            onClick={() => vscode.postMessage({ type: 'testNotification', kind })}
+            title="Send a test notification"
+            style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+          >
+            Test {kind}
+          </button>
+        ))}
+      </div>
+    </div>
+  )
+}
+
+const NOTIFICATION_HISTORY_LIMIT = 50
+
+function NotificationHistorySection() {
+  const [entries, setEntries] = useState<NotificationHistoryEntry[] | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'notificationHistory') {
+        setEntries(event.data.entries as NotificationHistoryEntry[])
+      }
+    }
+    window.addEventListener('message', handler)
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <button
+        onClick={() => (entries ? setEntries(null) : vscode.postMessage({ type: 'getNotificationHistory', limit: NOTIFICATION_HISTORY_LIMIT }))}
+        style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+      >
+        {entries ? 'Hide Notification History' : 'Notification History'}
+      </button>
+      {entries && entries.length === 0 && (
+        <div style={{ fontSize: '16px', color: 'rgba(255,255,255,0.6)', padding: '2px 10px' }}>No notifications yet</div>
+      )}
+      {entries && entries.length > 0 && (
+        <div style={{ maxHeight: 200, overflowY: 'auto' }}>
+          {entries.map((entry, index) => (
+            <div key={`${entry.ts_ms}:${index}`} style={{ fontSize: '16px', padding: '2px 10px', color: entry.status === 'delivered' ? 'rgba(255,255,255,0.8)' : 'rgba(255,255,255,0.5)' }}>
+              {new Date(entry.ts_ms).toLocaleString()} [{entry.status}] {entry.title}: {entry.message}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  )
+}
+
+function QuietHoursSection() {
+  const [quiet, setQuiet] = useState<QuietHours | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'quietHours') {
+        setQuiet(event.data.quietHours as QuietHours)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getQuietHours' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  if (!quiet) return null
+
+  const save = (next: QuietHours) => {
+    setQuiet(next)
+    vscode.postMessage({ type: 'setQuietHours', quietHours: next })
+  }
+  const timeStyle: React.CSSProperties = { fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Quiet Hours</div>
+      <MonitorToggle label="Enabled" value={quiet.enabled} onChange={(enabled) => save({ ...quiet, enabled })} />
+      <div style={{ display: 'flex', alignItems: 'center', gap: 4, padding: '2px 10px', fontSize: '18px', color: 'rgba(255,255,255,0.8)' }}>
+        <input type="time" value={quiet.start} onChange={(event) => save({ ...quiet, start: event.target.value })} style={timeStyle} />
+        <span>to</span>
+        <input type="time" value={quiet.end} onChange={(event) => save({ ...quiet, end: event.target.value })} style={timeStyle} />
+      </div>
+      <MonitorToggle label="All Weekend" value={quiet.weekends} onChange={(weekends) => save({ ...quiet, weekends })} />
+      <div style={{ display: 'flex' }}>
+        {(['silent', 'suppress'] as const).map((mode) => (
+          <button
+            key={mode}
+            onClick={() => save({ ...quiet, mode })}
+            title={mode === 'silent' ? 'Show notifications without sound' : 'Drop notifications and webhooks'}
+            style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: quiet.mode === mode ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+          >
+            {mode === 'silent' ? 'Silent' : 'Suppress'}
+          </button>
+        ))}
+      </div>
+    </div>
+  )
+}
+
+const USAGE_RANGES: Array<{ label: string; days: number | null }> = [
+  { label: '7d', days: 7 },
+  { label: '30d', days: 30 },
+  { label: 'All', days: null },
+]
+
+function usageSinceMs(days: number | null): number | null {
+  return days === null ? null : Date.now() - days * 86_400_000
+}
+
+function formatUsage(totals: UsageTotals): string {
+  const tokens = totals.input_tokens + totals.output_tokens
+  const tokenText = tokens >= 1_000_000 ? `${(tokens / 1_000_000).toFixed(1)}M` : `${Math.round(tokens / 1000)}k`
+  const hours = (totals.active_ms / 3_600_000).toFixed(1)
+  return `${tokenText} tok | $${totals.cost_usd.toFixed(2)} | ${totals.turns} turns | ${hours}h`
+}
+
+function UsageStatsSection() {
+  const [days, setDays] = useState<number | null>(7)
+  const [stats, setStats] = useState<UsageStatsPayload | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'usageStats') {
+        setStats(event.data.stats as UsageStatsPayload)
+      }
+    }
+    window.addEventListener('message', handler)
+    setStats(null)
+    vscode.postMessage({ type: 'getUsageStats', sinceMs: usageSinceMs(days) })
+    return () => window.removeEventListener('message', handler)
+  }, [days])
+
+  const rowStyle = { fontSize: '18px', color: 'rgba(255,255,255,0.7)', padding: '1px 10px' }
+  const topRepos = stats ? [...stats.by_repo].sort((a, b) => b.cost_usd - a.cost_usd || b.output_tokens - a.output_tokens).slice(0, 5) : []
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Usage</div>
+      <div style={{ display: 'flex' }}>
+        {USAGE_RANGES.map((range) => (
+          <button
+            key={range.label}
+            onClick={() => setDays(range.days)}
+            style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px', color: range.days === days ? '#ffffff' : undefined }}
+          >
+            {range.label}
+          </button>
+        ))}
+        <button
+          onClick={() => vscode.postMessage({ type: 'exportUsageStats', sinceMs: usageSinceMs(days), format: 'csv' })}
+          style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+        >
+          Export CSV
+        </button>
+      </div>
+      {!stats && <div style={rowStyle}>Loading...</div>}
+      {stats && (
+        <>
+          <div style={rowStyle}>Total ({stats.total.sessions} sessions): {formatUsage(stats.total)}</div>
+          {stats.by_source.map((bucket) => (
+            <div key={`source:${bucket.key}`} style={rowStyle}>{bucket.key}: {formatUsage(bucket)}</div>
+          ))}
+          {topRepos.map((bucket) => (
+            <div key={`repo:${bucket.key}`} style={rowStyle} title={bucket.key}>
+              {bucket.key.split(/[\\/]/).pop() || bucket.key}: {formatUsage(bucket)}
+            </div>
+          ))}
+          {stats.by_day.slice(-7).reverse().map((bucket) => (
+            <div key={`day:${bucket.key}`} style={rowStyle}>{bucket.key}: {formatUsage(bucket)}</div>
+          ))}
+        </>
+      )}
+    </div>
+  )
+}
+
+function LayoutsSection() {
+  const [layouts, setLayouts] = useState<LayoutListView>({ names: [], active: null })
+  const [name, setName] = useState('')
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'layouts') {
+        setLayouts(event.data.layouts as LayoutListView)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'listLayouts' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Layouts</div>
+      {layouts.names.map((layout) => (
+        <button
+          key={layout}
+          onClick={() => vscode.postMessage({ type: 'switchLayout', name: layout })}
+          style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px', color: layouts.active === layout ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+        >
+          <span>{layout}</span>
+          <span style={{ color: 'rgba(255,255,255,0.5)' }}>{layouts.active === layout ? 'Active' : 'Switch'}</span>
+        </button>
+      ))}
+      <form
+        style={{ display: 'flex', gap: 4, padding: '2px 10px' }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          if (name.trim()) {
+            vscode.postMessage({ type: 'saveLayoutAs', name: name.trim() })
+            setName('')
+          }
+        }}
+      >
+        <input
+          value={name}
+          onChange={(event) => setName(event.target.value)}
+          placeholder="office, focus, streaming"
+          title="Letters, digits, '-' or '_'"
+          style={{ flex: 1, minWidth: 0, fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }}
+        />
+        <button type="submit" style={{ ...menuItemBase, width: 'auto', padding: '2px 8px', fontSize: '18px' }}>Save As</button>
+      </form>
+    </div>
+  )
+}
+
+function LayoutBackupsSection() {
+  const [backups, setBackups] = useState<LayoutBackupView[]>([])
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'layoutBackups') {
+        setBackups(event.data.backups as LayoutBackupView[])
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'listLayoutBackups' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  if (backups.length === 0) return null
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Layout Backups</div>
+      <div style={{ maxHeight: 160, overflowY: 'auto' }}>
+        {backups.map((backup) => (
+          <button
+            key={backup.ts_ms}
+            onClick={() => vscode.postMessage({ type: 'restoreLayoutBackup', index: backup.index })}
+            style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+          >
+            <span>{new Date(backup.ts_ms).toLocaleString()}</span>
+            <span style={{ color: 'rgba(255,255,255,0.5)' }}>Restore</span>
+          </button>
+        ))}
+      </div>
+    </div>
+  )
+}
+
+const EDITOR_PRESETS = ['code', 'cursor', 'zed', 'idea']
+
+function EditorCommandSection() {
+  const [command, setCommand] = useState('')
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'editorCommand') {
+        setCommand(event.data.command as string)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getEditorCommand' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Editor Command</div>
+      <form
+        style={{ display: 'flex', gap: 4, padding: '2px 10px' }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          vscode.postMessage({ type: 'setEditorCommand', command })
+        }}
+      >
+        <input
+          value={command}
+          onChange={(event) => setCommand(event.target.value)}
+          placeholder="code {repo} {file}"
+          title="Use {repo} and {file} placeholders, or just an editor name"
+          style={{ flex: 1, minWidth: 0, fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }}
+        />
+        <button type="submit" style={{ ...menuItemBase, width: 'auto', padding: '2px 8px', fontSize: '18px' }}>Save</button>
+      </form>
+      <div style={{ display: 'flex' }}>
+        {EDITOR_PRESETS.map((preset) => (
+          <button
+            key={preset}
+            onClick={() => vscode.postMessage({ type: 'setEditorCommand', command: preset })}
+            style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px', color: command === preset ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+          >
+            {preset}
+          </button>
+        ))}
+      </div>
+    </div>
+  )
+}
+
+function RepoBindingsSection() {
+  const [bindings, setBindings] = useState<RepoBindingView[]>([])
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'repoBindings') {
+        setBindings(event.data.bindings as RepoBindingView[])
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'listRepoBindings' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  if (bindings.length === 0) return null
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Repo Bindings</div>
+      {bindings.map((binding) => (
+        <button
+          key={binding.key}
+          onClick={() => vscode.postMessage({ type: 'monitorUnbindRepo', source: binding.source, sessionId: binding.session_id })}
+          title={`${binding.source} ${binding.session_id}`}
+          style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px', gap: 8 }}
+        >
+          <span style={{ color: binding.exists ? undefined : '#ff8f8f' }}>
+            {binding.repo_path}{binding.exists ? '' : ' (missing)'}
+          </span>
+          <span style={{ color: 'rgba(255,255,255,0.5)' }}>Unbind</span>
+        </button>
+      ))}
+    </div>
+  )
+}
+
+function ArchivedAgentsSection() {
+  const [agents, setAgents] = useState<ArchivedAgent[]>([])
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'archivedAgents') {
+        setAgents(event.data.agents as ArchivedAgent[])
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'listArchivedAgents' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  if (agents.length === 0) return null
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Archived Agents</div>
+      {agents.map((agent) => (
+        <button
+          key={agent.key}
+          onClick={() => vscode.postMessage({ type: 'monitorUnarchiveAgent', agentKey: agent.key })}
+          title="Unarchive"
+          style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+        >
+          <span>{agent.display_name || agent.key}</span>
+          <span style={{ color: 'rgba(255,255,255,0.5)' }}>Unarchive</span>
+        </button>
+      ))}
+    </div>
+  )
+}
+
+function DetectedAgentsSection() {
+  const [agents, setAgents] = useState<DetectedAgent[]>([])
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'agentLauncherStatus' && Array.isArray(event.data.detectedAgents)) {
+        setAgents(event.data.detectedAgents as DetectedAgent[])
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getDetectedAgents' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  if (agents.length === 0) {
+    return null
+  }
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', padding: '2px 10px' }}>
+        <span style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)' }}>Detected Agents</span>
+        <button
+          onClick={() => vscode.postMessage({ type: 'refreshCliAvailability' })}
+          style={{ ...menuItemBase, width: 'auto', padding: '2px 8px', fontSize: '18px' }}
+        >
+          Refresh
+        </button>
+      </div>
+      {agents.map((agent) => (
+        <div key={agent.name} style={{ padding: '2px 10px' }} title={agent.dataDir}>
+          <div style={{ fontSize: '18px', color: agent.available ? 'rgba(255,255,255,0.8)' : 'rgba(255,255,255,0.5)' }}>
+            {agent.name}: {agent.available ? agent.version ?? 'installed' : 'not found'}
+            {agent.monitored ? ' | monitored' : ' | not monitored'}
+          </div>
+          <div style={{ fontSize: '16px', color: agent.dataDirExists ? 'rgba(255,255,255,0.6)' : '#ff8f8f', wordBreak: 'break-all' }}>
+            {agent.dataDir}{agent.dataDirExists ? '' : ' (missing)'}
+          </div>
+        </div>
+      ))}
+    </div>
+  )
+}
+
+const WEBHOOK_EVENTS = ['done', 'error', 'waiting', 'approval', 'limited', 'stuck']
+const WEBHOOK_FORMATS: Array<Webhook['format']> = ['json', 'slack', 'discord']
+
+function WebhooksSection() {
+  const [webhooks, setWebhooks] = useState<Webhook[]>([])
+  const [status, setStatus] = useState<string | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'webhooks') {
+        setWebhooks(event.data.webhooks as Webhook[])
+      } else if (event.data?.type === 'webhookStatus') {
+        setStatus(event.data.message as string)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getWebhooks' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  const update = (index: number, patch: Partial<Webhook>) => {
+    setWebhooks(webhooks.map((webhook, i) => (i === index ? { ...webhook, ...patch } : webhook)))
+  }
+  const inputStyle: React.CSSProperties = { flex: 1, minWidth: 0, fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Webhooks</div>
+      {webhooks.map((webhook, index) => (
+        <div key={index} style={{ borderBottom: '1px solid var(--pixel-border)', paddingBottom: 4 }}>
+          <div style={{ display: 'flex', gap: 4, padding: '2px 10px' }}>
+            <input
+              value={webhook.url}
+              onChange={(event) => update(index, { url: event.target.value })}
+              placeholder="https://hooks.slack.com/services/..."
+              style={inputStyle}
+            />
+          </div>
+          <div style={{ display: 'flex', gap: 4, padding: '2px 10px' }}>
+            <input
+              type="password"
+              value={webhook.secret ?? ''}
+              onChange={(event) => update(index, { secret: event.target.value })}
+              placeholder="secret (optional)"
+              title="Signs the body as X-Pixel-Agents-Signature: sha256=<hmac>"
+              style={inputStyle}
+            />
+          </div>
+          <div style={{ display: 'flex', flexWrap: 'wrap' }}>
+            {WEBHOOK_FORMATS.map((format) => (
+              <button
+                key={format}
+                onClick={() => update(index, { format })}
+                style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: webhook.format === format ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+              >
+                {format}
+              </button>
+            ))}
+          </div>
+          <div style={{ display: 'flex', flexWrap: 'wrap' }}>
+            {WEBHOOK_EVENTS.map((kind) => {
+              const enabled = webhook.events.includes(kind)
+              return (
+                <button
+                  key={kind}
+                  onClick={() => update(index, { events: enabled ? webhook.events.filter((e) => e !== kind) : [...webhook.events, kind] })}
+                  style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: enabled ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+                >
+                  {kind}
+                </button>
+              )
+            })}
+          </div>
+          <div style={{ display: 'flex' }}>
+            <button
+              onClick={() => {
+                setStatus('Sending...')
+                vscode.postMessage({ type: 'testWebhook', webhook })
+              }}
+              style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+            >
+              Test
+            </button>
+            <button
+              onClick={() => setWebhooks(webhooks.filter((_, i) => i !== index))}
+              style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+            >
+              Remove
+            </button>
+          </div>
+        </div>
+      ))}
+      <div style={{ display: 'flex' }}>
+        <button
+          onClick={() => setWebhooks([...webhooks, { url: '', events: ['done', 'error', 'waiting', 'approval'], format: 'json' }])}
+          style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+        >
+          Add Webhook
+        </button>
+        <button
+          onClick={() => vscode.postMessage({ type: 'setWebhooks', webhooks })}
+          style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+        >
+          Save Webhooks
+        </button>
+      </div>
+      {status && (
+        <div style={{ fontSize: '16px', color: 'rgba(255,255,255,0.6)', padding: '2px 10px' }}>{status}</div>
+      )}
+    </div>
+  )
+}
+
+const DEFAULT_PUSH: PushSettings = { provider: 'ntfy', server: 'https://ntfy.sh', topic: '', events: ['done', 'error', 'waiting', 'approval'] }
+
+function PushSection() {
+  const [push, setPush] = useState<PushSettings | null>(null)
+  const [status, setStatus] = useState<string | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'pushSettings') {
+        setPush(event.data.settings as PushSettings | null)
+      } else if (event.data?.type === 'pushStatus') {
+        setStatus(event.data.message as string)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getPushSettings' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  const update = (patch: Partial<PushSettings>) => setPush({ ...(push ?? DEFAULT_PUSH), ...patch })
+  const inputStyle: React.CSSProperties = { flex: 1, minWidth: 0, fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }
+  const field = (key: 'server' | 'topic' | 'token' | 'userKey' | 'appToken', placeholder: string, secret = false) => (
+    <div style={{ display: 'flex', gap: 4, padding: '2px 10px' }}>
+      <input
+        type={secret ? 'password' : 'text'}
+        value={push?.[key] ?? ''}
+        onChange={(event) => update({ [key]: event.target.value })}
+        placeholder={placeholder}
+        style={inputStyle}
+      />
+    </div>
+  )
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Push Notifications</div>
+      <div style={{ display: 'flex', flexWrap: 'wrap' }}>
+        {(['off', 'ntfy', 'pushover'] as const).map((provider) => {
+          const selected = provider === 'off' ? push === null : push?.provider === provider
+          return (
+            <button
+              key={provider}
+              onClick={() => (provider === 'off' ? setPush(null) : update({ provider }))}
+              style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: selected ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+            >
+              {provider}
+            </button>
+          )
+        })}
+      </div>
+      {push?.provider === 'ntfy' && (
+        <>
+          {field('server', 'https://ntfy.sh')}
+          {field('topic', 'topic')}
+          {field('token', 'access token (optional)', true)}
+        </>
+      )}
+      {push?.provider === 'pushover' && (
+        <>
+          {field('userKey', 'user key', true)}
+          {field('appToken', 'app token', true)}
+        </>
+      )}
+      {push && (
+        <div style={{ display: 'flex', flexWrap: 'wrap' }}>
+          {WEBHOOK_EVENTS.map((kind) => {
+            const enabled = push.events.includes(kind)
+            return (
+              <button
+                key={kind}
+                onClick={() => update({ events: enabled ? push.events.filter((e) => e !== kind) : [...push.events, kind] })}
+                style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: enabled ? 'rgba(90, 140, 255, 1)' : menuItemBase.color }}
+              >
+                {kind}
+              </button>
+            )
+          })}
+        </div>
+      )}
+      <div style={{ display: 'flex' }}>
+        {push && (
+          <button
+            onClick={() => {
+              setStatus('Sending...')
+              vscode.postMessage({ type: 'testPush', settings: push })
+            }}
+            style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+          >
+            Test
+          </button>
+        )}
+        <button
+          onClick={() => vscode.postMessage({ type: 'setPushSettings', settings: push })}
+          style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+        >
+          Save Push
+        </button>
+      </div>
+      {status && (
+        <div style={{ fontSize: '16px', color: 'rgba(255,255,255,0.6)', padding: '2px 10px' }}>{status}</div>
+      )}
+    </div>
+  )
+}
+
+function StartupSection() {
+  const [enabled, setEnabled] = useState(false)
+  const [closeToTray, setCloseToTray] = useState(false)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'autostartStatus') {
+        setEnabled(Boolean(event.data.enabled))
+      }
+      if (event.data?.type === 'closeToTray') {
+        setCloseToTray(Boolean(event.data.enabled))
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getAutostart' })
+    vscode.postMessage({ type: 'getCloseToTray' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <MonitorToggle label="Launch at Login" value={enabled} onChange={(value) => vscode.postMessage({ type: 'setAutostart', enabled: value })} />
+      <MonitorToggle label="Close to Tray" value={closeToTray} onChange={(value) => vscode.postMessage({ type: 'setCloseToTray', enabled: value })} />
+    </div>
+  )
+}
+
+const HOTKEY_ACTIONS: Array<{ label: string; action: keyof Hotkeys }> = [
+  { label: 'Show/Hide', action: 'toggleWindow' },
+  { label: 'PiP', action: 'togglePip' },
+  { label: 'Snooze', action: 'snoozeNotifications' },
+]
+
+function HotkeysSection() {
+  const [hotkeys, setHotkeys] = useState<Hotkeys | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'hotkeys') {
+        setHotkeys(event.data.hotkeys as Hotkeys)
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getHotkeys' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  if (!hotkeys) return null
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Global Hotkeys</div>
+      <form
+        onSubmit={(event) => {
+          event.preventDefault()
+          vscode.postMessage({ type: 'setHotkeys', hotkeys })
+        }}
+      >
+        {HOTKEY_ACTIONS.map(({ label, action }) => (
+          <label key={action} style={{ display: 'flex', alignItems: 'center', gap: 4, padding: '2px 10px', fontSize: '18px', color: 'rgba(255,255,255,0.8)' }}>
+            <span style={{ width: 90 }}>{label}</span>
+            <input
+              value={hotkeys[action]}
+              onChange={(event) => setHotkeys({ ...hotkeys, [action]: event.target.value })}
+              placeholder="unbound"
+              title="e.g. CommandOrControl+Shift+Alt+P; leave empty to disable"
+              style={{ flex: 1, minWidth: 0, fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }}
+            />
+          </label>
+        ))}
+        <button type="submit" style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}>Save Hotkeys</button>
+      </form>
+    </div>
+  )
+}
+
+function UpdatesSection() {
+  const [autoCheck, setAutoCheck] = useState(false)
+  const [info, setInfo] = useState<UpdateInfo | null>(null)
+  const [status, setStatus] = useState<string | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'autoCheckUpdates') {
+        setAutoCheck(Boolean(event.data.enabled))
+      } else if (event.data?.type === 'updateInfo') {
+        const next = event.data.info as UpdateInfo | null
+        setInfo(next)
+        if (!next) {
+          setStatus(`Update check failed: ${event.data.error as string}`)
+        } else if (next.update_available) {
+          setStatus(`Version ${next.latest_version} is available (running ${next.current_version})`)
+        } else {
+          setStatus(`Up to date (${next.current_version})`)
+        }
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getAutoCheckUpdates' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Updates</div>
+      <MonitorToggle label="Check Daily" value={autoCheck} onChange={(value) => vscode.postMessage({ type: 'setAutoCheckUpdates', enabled: value })} />
+      <button
+        onClick={() => {
+          setStatus('Checking...')
+          vscode.postMessage({ type: 'checkForUpdates' })
+        }}
+        style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+      >
+        Check for Updates
+      </button>
+      {status && (
+        <div style={{ fontSize: '16px', color: 'rgba(255,255,255,0.6)', padding: '2px 10px' }}>{status}</div>
+      )}
+      {info?.update_available && (
+        <>
+          {info.release_notes && (
+            <div style={{ fontSize: '16px', color: 'rgba(255,255,255,0.6)', padding: '2px 10px', whiteSpace: 'pre-wrap', maxHeight: 160, overflowY: 'auto' }}>
+              {info.release_notes}
+            </div>
+          )}
+          <button
+            onClick={() => vscode.postMessage({ type: 'openUrl', url: info.download_url })}
+            style={{ ...menuItemBase, padding: '4px 10px', fontSize: '20px' }}
+          >
+            Download {info.latest_version}
+          </button>
+        </>
+      )}
+    </div>
+  )
+}
+
+const RESET_SCOPES: Array<{ label: string; scope: string }> = [
+  { label: 'Monitor', scope: 'monitor' },
+  { label: 'Bindings', scope: 'bindings' },
+  { label: 'Layouts', scope: 'layouts' },
+  { label: 'Everything', scope: 'all' },
+]
+
+function DataDirSection() {
+  const [view, setView] = useState<DataDirView | null>(null)
+  const [draft, setDraft] = useState('')
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'dataDir') {
+        const next = event.data.view as DataDirView
+        setView(next)
+        setDraft(next.configured ?? '')
+      }
+    }
+    window.addEventListener('message', handler)
+    vscode.postMessage({ type: 'getDataDir' })
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  if (!view) {
+    return null
+  }
+  const next = view.env_override ?? view.configured ?? view.default_dir
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>Data Directory</div>
+      <div style={{ fontSize: '16px', color: 'rgba(255,255,255,0.6)', padding: '2px 10px', wordBreak: 'break-all' }}>
+        {view.current}
+        {view.env_override && ' (set by PIXEL_AGENTS_DIR)'}
+        {next !== view.current && ` - restart to use ${next}`}
+      </div>
+      <form
+        style={{ display: 'flex', gap: 4, padding: '2px 10px' }}
+        onSubmit={(event) => {
+          event.preventDefault()
+          vscode.postMessage({ type: 'setDataDir', dir: draft })
+        }}
+      >
+        <input
+          value={draft}
+          onChange={(event) => setDraft(event.target.value)}
+          placeholder={view.default_dir}
+          title="Absolute path; existing files are not moved"
+          style={{ flex: 1, minWidth: 0, fontSize: '18px', color: 'var(--pixel-text)', background: 'var(--pixel-btn-bg)', border: '1px solid var(--pixel-border)', borderRadius: 0, padding: '2px 4px' }}
+        />
+        <button type="submit" style={{ ...menuItemBase, width: 'auto', padding: '2px 8px', fontSize: '18px' }}>Save</button>
+      </form>
+      <div style={{ display: 'flex' }}>
+        <button
+          onClick={() => vscode.postMessage({ type: 'chooseDataDir' })}
+          style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+        >
+          Choose Folder
+        </button>
+        {view.configured && (
+          <button
+            onClick={() => vscode.postMessage({ type: 'setDataDir', dir: null })}
+            style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px' }}
+          >
+            Use Default
+          </button>
+        )}
+      </div>
+    </div>
+  )
+}
+
+function ResetDataSection() {
+  const [pending, setPending] = useState<string | null>(null)
+  const [report, setReport] = useState<ResetReport | null>(null)
+
+  useEffect(() => {
+    const handler = (event: MessageEvent) => {
+      if (event.data?.type === 'resetDataResult') {
+        setReport(event.data.report as ResetReport)
+      }
+    }
+    window.addEventListener('message', handler)
+    return () => window.removeEventListener('message', handler)
+  }, [])
+
+  return (
+    <div style={{ borderTop: '1px solid var(--pixel-border)', marginTop: 4, paddingTop: 4 }}>
+      <div style={{ fontSize: '20px', color: 'rgba(255,255,255,0.9)', padding: '2px 10px' }}>
+        {pending ? `Reset ${pending}? Click again to confirm` : 'Reset Data'}
+      </div>
+      <div style={{ display: 'flex', flexWrap: 'wrap' }}>
+        {RESET_SCOPES.map(({ label, scope }) => (
+          <button
+            key={scope}
+            onClick={() => {
+              if (pending === scope) {
+                setPending(null)
+                vscode.postMessage({ type: 'resetData', scope })
+              } else {
+                setPending(scope)
+              }
+            }}
+            title="A backup zip of ~/.pixel-agents is written first"
+            style={{ ...menuItemBase, width: 'auto', padding: '4px 10px', fontSize: '20px', color: pending === scope ? '#ff8f8f' : menuItemBase.color }}
+          >
+            {label}
+          </button>
+        ))}
+      </div>
+      {report && (
+        <div style={{ fontSize: '16px', color: 'rgba(255,255,255,0.6)', padding: '2px 10px', wordBreak: 'break-all' }}>
+          Removed {report.removed.length} item(s); backup saved to {report.backup_path}
+        </div>
+      )}
+    </div>
+  )
+}
+
 function MonitorToggle({ label, value, onChange }: { label: string; value: boolean; onChange: (value: boolean) => void }) {
   return (
     <button
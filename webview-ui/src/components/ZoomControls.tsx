@@ -13,8 +13,18 @@ interface ZoomControlsProps {
   onZoomChange: (zoom: number) => void
   pipEnabled: boolean
   onTogglePiP: () => void
+  onPipCorner: (corner: PipCorner) => void
 }
 
+export type PipCorner = 'top-left' | 'top-right' | 'bottom-left' | 'bottom-right'
+
+const PIP_CORNERS: Array<{ corner: PipCorner; label: string }> = [
+  { corner: 'top-left', label: 'TL' },
+  { corner: 'top-right', label: 'TR' },
+  { corner: 'bottom-left', label: 'BL' },
+  { corner: 'bottom-right', label: 'BR' },
+]
+
 const btnBase: React.CSSProperties = {
   width: 40,
   height: 40,
@@ -30,7 +40,7 @@ const btnBase: React.CSSProperties = {
   boxShadow: 'var(--pixel-shadow)',
 }
 
-export function ZoomControls({ zoom, onZoomChange, pipEnabled, onTogglePiP }: ZoomControlsProps) {
+export function ZoomControls({ zoom, onZoomChange, pipEnabled, onTogglePiP, onPipCorner }: ZoomControlsProps) {
   const [hovered, setHovered] = useState<'minus' | 'plus' | null>(null)
   const [showLevel, setShowLevel] = useState(false)
   const [fadeOut, setFadeOut] = useState(false)
@@ -194,6 +204,31 @@ export function ZoomControls({ zoom, onZoomChange, pipEnabled, onTogglePiP }: Zo
         PiP
       </button>
 
+      {pipEnabled && (
+        <div
+          style={{
+            position: 'absolute',
+            bottom: 88,
+            right: 8,
+            zIndex: 'var(--pixel-controls-z)',
+            display: 'grid',
+            gridTemplateColumns: 'repeat(2, 32px)',
+            gap: 4,
+          }}
+        >
+          {PIP_CORNERS.map(({ corner, label }) => (
+            <button
+              key={corner}
+              onClick={() => onPipCorner(corner)}
+              title={`Move to ${corner.replace('-', ' ')} corner`}
+              style={{ ...btnBase, width: 32, height: 32, fontSize: '14px' }}
+            >
+              {label}
+            </button>
+          ))}
+        </div>
+      )}
+
       <div
         style={{
           position: 'absolute',
@@ -140,6 +140,14 @@ export const MONITOR_AGENT_LABEL_FONT_DEFAULT_PX = 24
 export const MONITOR_AGENT_LABEL_FONT_MIN_PX = 14
 export const MONITOR_AGENT_LABEL_FONT_MAX_PX = 40
 export const MONITOR_TOAST_DURATION_MS = 10000
+export const MONITOR_STATE_COLORS: Record<'idle' | 'thinking' | 'running' | 'waiting' | 'done' | 'error', string> = {
+  idle: '#ffffff',
+  thinking: '#74d680',
+  running: '#74d680',
+  waiting: '#ffd166',
+  done: '#ffffff',
+  error: '#ff6b6b',
+}
 export const TOOL_OVERLAY_REFRESH_MS = 80
 export const INFO_BOARD_REFRESH_MS = 120
 export const CHARACTER_ASSET_COUNT = 6
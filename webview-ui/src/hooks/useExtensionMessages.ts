@@ -66,7 +66,7 @@ export interface ExtensionMessageState {
   updateDemoMode: (enabled: boolean) => void
   claudeAvailable: boolean
   monitorCharacterIds: number[]
-  monitorActivityById: Record<number, { state: MonitorAgentView['state']; text: string }>
+  monitorActivityById: Record<number, { state: MonitorAgentView['state']; text: string; note?: string | null }>
 }
 
 export interface MonitorSettings {
@@ -82,6 +82,35 @@ export interface MonitorSettings {
   prPollIntervalMs: number
   agentLabelFontPx: number
   maxIdleAgents: number
+  notifyWaiting: boolean
+  stuckAfterMs: number
+  maxCodexFiles?: number
+  codexTailBytes?: number
+  maxOpencodeFiles?: number
+  maxOpencodePartFiles?: number
+  maxOpencodeDbSessions?: number
+  maxOpencodeDbParts?: number
+  maxRecentEvents?: number
+  maxTotalRecentEvents?: number
+  agentFilter?: AgentFilter
+  errorPatterns?: Record<string, ErrorPatterns>
+  notifySources?: Record<string, boolean>
+  notifyRepos?: Record<string, boolean>
+}
+
+export interface ErrorPatterns {
+  include?: string[]
+  exclude?: string[]
+}
+
+export interface AgentFilter {
+  includeSources?: string[]
+  excludeSources?: string[]
+  includeRepoPrefixes?: string[]
+  excludeRepoPrefixes?: string[]
+  includeStates?: string[]
+  excludeStates?: string[]
+  maxAgeMs?: number
 }
 
 export const DEFAULT_MONITOR_SETTINGS: MonitorSettings = {
@@ -97,6 +126,8 @@ export const DEFAULT_MONITOR_SETTINGS: MonitorSettings = {
   prPollIntervalMs: 90000,
   agentLabelFontPx: MONITOR_AGENT_LABEL_FONT_DEFAULT_PX,
   maxIdleAgents: 3,
+  notifyWaiting: true,
+  stuckAfterMs: 600_000,
 }
 
 function normalizeMonitorSettings(settings: Partial<MonitorSettings> | MonitorSettings): MonitorSettings {
@@ -117,10 +148,17 @@ function normalizeMonitorSettings(settings: Partial<MonitorSettings> | MonitorSe
 export interface MonitorNotification {
   title: string
   message: string
-  kind: 'done' | 'error'
+  kind: 'done' | 'error' | 'waiting' | 'approval' | 'stuck'
   key: string
+  silent?: boolean
+  priority?: NotificationPriority
+  grouped_keys?: string[]
 }
 
+export type NotificationPriority = 'low' | 'normal' | 'high' | 'urgent'
+
+const NOTIFICATION_PRIORITY_RANK: Record<NotificationPriority, number> = { low: 0, normal: 1, high: 2, urgent: 3 }
+
 export interface MonitorToast extends MonitorNotification {
   id: string
   createdAt: number
@@ -167,10 +205,16 @@ export interface MonitorAgentView {
   session_id: string
   agent_id: string
   display_name: string
+  pinned?: boolean
+  note?: string | null
+  muted?: boolean
   state: 'idle' | 'thinking' | 'running' | 'waiting' | 'done' | 'error'
   last_ts_ms: number
   last_text?: string
   repo_path?: string
+  pid?: number | null
+  cpu_percent?: number | null
+  rss_bytes?: number | null
   files_touched: string[]
   alerts: MonitorAlert[]
   recent_events: MonitorEventView[]
@@ -188,9 +232,175 @@ export interface MonitorSummary {
   alerts: number
 }
 
+export interface MonitorSourceStatus {
+  source: string
+  status: 'ok' | 'degraded' | 'error' | 'disabled' | 'pending'
+  last_scan_ms: number | null
+  agents: number
+  parse_errors: number
+  error: string | null
+}
+
+export interface ArchivedAgent {
+  key: string
+  source: string
+  display_name: string | null
+  archived_ms: number
+}
+
+export interface MonitorRepoView {
+  path: string
+  last_activity_ms: number | null
+  session_count: number
+  bound: boolean
+  branch: string | null
+}
+
+export interface RepoBindingView {
+  key: string
+  source: string
+  session_id: string
+  repo_path: string
+  exists: boolean
+}
+
+export interface LayoutListView {
+  names: string[]
+  active: string | null
+}
+
+export interface Hotkeys {
+  toggleWindow: string
+  togglePip: string
+  snoozeNotifications: string
+}
+
+export interface UpdateInfo {
+  current_version: string
+  latest_version: string
+  update_available: boolean
+  release_notes: string | null
+  download_url: string
+  published_at: string | null
+}
+
+export interface DetectedAgent {
+  name: string
+  available: boolean
+  version: string | null
+  dataDir: string
+  dataDirExists: boolean
+  monitored: boolean
+}
+
+export interface NotificationHistoryEntry {
+  ts_ms: number
+  title: string
+  message: string
+  kind: string
+  key: string
+  status: 'delivered' | 'silent' | 'snoozed' | 'quiet' | 'muted' | 'cooldown'
+}
+
+export interface QuietHours {
+  enabled: boolean
+  start: string
+  end: string
+  weekends: boolean
+  mode: 'silent' | 'suppress'
+}
+
+export interface Webhook {
+  url: string
+  secret?: string
+  events: string[]
+  format: 'json' | 'slack' | 'discord'
+}
+
+export interface PushSettings {
+  provider: 'ntfy' | 'pushover'
+  server?: string
+  topic?: string
+  token?: string
+  userKey?: string
+  appToken?: string
+  events: string[]
+}
+
+export interface DataDirView {
+  current: string
+  configured: string | null
+  env_override: string | null
+  default_dir: string
+}
+
+export interface ResetReport {
+  backup_path: string
+  removed: string[]
+}
+
+export interface LayoutBackupView {
+  index: number
+  ts_ms: number
+}
+
+export interface UsageTotals {
+  input_tokens: number
+  cached_input_tokens: number
+  output_tokens: number
+  reasoning_tokens: number
+  cost_usd: number
+  turns: number
+  active_ms: number
+  sessions: number
+}
+
+export interface UsageBucket extends UsageTotals {
+  key: string
+}
+
+export interface SessionUsage extends UsageTotals {
+  key: string
+  source: string
+  session_id: string
+  repo_path: string | null
+  model: string | null
+  started_ts_ms: number
+  ended_ts_ms: number
+  state: string
+}
+
+export interface UsageStatsPayload {
+  since_ms: number | null
+  total: UsageTotals
+  sessions: SessionUsage[]
+  by_source: UsageBucket[]
+  by_repo: UsageBucket[]
+  by_day: UsageBucket[]
+}
+
+export interface SessionSearchHit {
+  key: string
+  source: string
+  session_id: string
+  title: string | null
+  repo_path: string | null
+  last_ts_ms: number | null
+  matches: number
+  snippets: string[]
+}
+
+export interface TranscriptEntry {
+  ts_ms: number | null
+  role: 'user' | 'assistant' | 'tool_call' | 'tool_output'
+  text: string
+  tool: string | null
+}
+
 export interface MonitorSnapshot {
   summary: MonitorSummary
   agents: MonitorAgentView[]
+  source_status?: MonitorSourceStatus[]
   now_ms: number
 }
 
@@ -240,10 +450,10 @@ function normalizeMonitorSnapshot(snapshot: MonitorSnapshot, settings: MonitorSe
       source: normalizeMonitorSource(String(agent.source), agent.key),
     }))
     .filter((agent) => isSourceEnabled(agent, settings))
-    .sort((a, b) => b.last_ts_ms - a.last_ts_ms)
+    .sort((a, b) => Number(Boolean(b.pinned)) - Number(Boolean(a.pinned)) || b.last_ts_ms - a.last_ts_ms)
 
-  const idleAgents = sortedAgents.filter((agent) => agent.state === 'idle')
-  const nonIdleAgents = sortedAgents.filter((agent) => agent.state !== 'idle')
+  const idleAgents = sortedAgents.filter((agent) => agent.state === 'idle' && !agent.pinned)
+  const nonIdleAgents = sortedAgents.filter((agent) => agent.state !== 'idle' || agent.pinned)
   const agents = [...nonIdleAgents, ...idleAgents.slice(0, settings.maxIdleAgents)]
 
   const summary: MonitorSummary = {
@@ -605,7 +815,7 @@ export function useExtensionMessages(
   const [demoMode, setDemoMode] = useState(false)
   const [claudeAvailable, setClaudeAvailable] = useState(true)
   const [monitorCharacterIds, setMonitorCharacterIds] = useState<number[]>([])
-  const [monitorActivityById, setMonitorActivityById] = useState<Record<number, { state: MonitorAgentView['state']; text: string }>>({})
+  const [monitorActivityById, setMonitorActivityById] = useState<Record<number, { state: MonitorAgentView['state']; text: string; note?: string | null }>>({})
   const monitorIdByKeyRef = useRef<Map<string, number>>(new Map())
   const monitorIdsRef = useRef<Set<number>>(new Set())
   const nextMonitorIdRef = useRef(MONITOR_AGENT_ID_BASE)
@@ -997,7 +1207,7 @@ export function useExtensionMessages(
           }
         }
 
-        const nextActivity: Record<number, { state: MonitorAgentView['state']; text: string }> = {}
+        const nextActivity: Record<number, { state: MonitorAgentView['state']; text: string; note?: string | null }> = {}
         for (const monitorAgent of visibleAgents) {
           const id = monitorIdByKeyRef.current.get(monitorAgent.key)
           if (!id) continue
@@ -1006,6 +1216,7 @@ export function useExtensionMessages(
           nextActivity[id] = {
             state: monitorAgent.state,
             text: `${monitorAgent.display_name} ${stateText}${tail}`,
+            note: monitorAgent.note,
           }
         }
         setMonitorActivityById(nextActivity)
@@ -1045,8 +1256,9 @@ export function useExtensionMessages(
           id: `${notification.key}:${Date.now()}:${Math.random().toString(36).slice(2, 8)}`,
           createdAt: Date.now(),
         }
-        setMonitorToasts((prev) => [toast, ...prev].slice(0, 6))
-        if (notification.kind === 'done') {
+        const rank = (item: MonitorNotification) => NOTIFICATION_PRIORITY_RANK[item.priority ?? 'normal']
+        setMonitorToasts((prev) => [toast, ...prev].sort((a, b) => rank(b) - rank(a)).slice(0, 6))
+        if ((notification.kind === 'done' || notification.kind === 'waiting' || notification.kind === 'approval') && !notification.silent) {
           playDoneSound()
         }
       } else if (msg.type === 'agentLauncherStatus') {
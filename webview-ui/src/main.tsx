@@ -2,9 +2,15 @@ import { StrictMode } from 'react'
 import { createRoot } from 'react-dom/client'
 import './index.css'
 import App from './App.tsx'
+import { AgentWindow } from './components/AgentWindow.tsx'
+import { DashboardWindow } from './components/DashboardWindow.tsx'
+
+const params = new URLSearchParams(window.location.search)
+const view = params.get('view')
+const agentKey = view === 'agent' ? params.get('key') : null
 
 createRoot(document.getElementById('root')!).render(
   <StrictMode>
-    <App />
+    {agentKey ? <AgentWindow agentKey={agentKey} /> : view === 'dashboard' ? <DashboardWindow /> : <App />}
   </StrictMode>,
 )
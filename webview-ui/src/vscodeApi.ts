@@ -1,6 +1,7 @@
 type AnyRecord = Record<string, unknown>
 
 import { invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
 
 interface VsCodeApiLike {
   postMessage: (msg: unknown) => void
@@ -21,24 +22,46 @@ const isTauriDesktop = typeof window !== 'undefined' && (
 
 export const isDesktopRuntime = isTauriDesktop
 
-let monitorTimer: number | null = null
+let monitorUnlisten: UnlistenFn | null = null
+let settingsUnlisten: UnlistenFn | null = null
+let hotkeyUnlisten: UnlistenFn | null = null
+let deepLinkUnlisten: UnlistenFn | null = null
 let monitorTickErrored = false
 let monitorTickInFlight = false
+let autostartEnabled = false
+let detectedAgents: DetectedAgentPayload[] = []
 
 function emitMessageToApp(data: unknown): void {
   window.dispatchEvent(new MessageEvent('message', { data }))
 }
 
+class TauriCommandError extends Error {
+  readonly command: string
+  readonly code: string
+  readonly detail: string
+
+  constructor(command: string, code: string, detail: string) {
+    super(`[Tauri:${command}] ${code}: ${detail}`)
+    this.command = command
+    this.code = code
+    this.detail = detail
+  }
+}
+
 async function tauriInvoke<T>(command: string, args?: AnyRecord): Promise<T> {
   try {
     return await invoke<T>(command, args)
   } catch (error) {
+    if (error && typeof error === 'object' && 'code' in error && 'message' in error) {
+      const { code, message } = error as { code: unknown; message: unknown }
+      throw new TauriCommandError(command, String(code), String(message))
+    }
     const message = error instanceof Error
       ? error.message
       : typeof error === 'string'
         ? error
         : JSON.stringify(error)
-    throw new Error(`[Tauri:${command}] ${message}`)
+    throw new TauriCommandError(command, 'unknown', message)
   }
 }
 
@@ -50,41 +73,150 @@ function reportTauriError(scope: string, error: unknown): void {
   console.error(`[Desktop Bridge] ${scope}:`, error)
 }
 
-async function startDesktopMonitorLoop(): Promise<void> {
-  if (monitorTimer !== null) {
+type MonitorAgentPayload = { key: string; last_ts_ms: number }
+type MonitorSnapshotPayload = {
+  summary: unknown
+  agents: MonitorAgentPayload[]
+  source_status: unknown[]
+  now_ms: number
+}
+type MonitorDeltaPayload = {
+  summary: unknown
+  added: MonitorAgentPayload[]
+  updated: MonitorAgentPayload[]
+  removed: string[]
+  source_status: unknown[]
+  now_ms: number
+}
+type MonitorTickPayload = {
+  snapshot: MonitorSnapshotPayload | null
+  delta: MonitorDeltaPayload | null
+  notifications: Array<{ title: string; message: string; kind: 'done' | 'error' | 'waiting' | 'approval' | 'stuck'; key: string; silent: boolean; priority: 'low' | 'normal' | 'high' | 'urgent'; grouped_keys?: string[] }>
+}
+
+const monitorAgents = new Map<string, MonitorAgentPayload>()
+
+function applyMonitorPayload(payload: MonitorTickPayload): MonitorSnapshotPayload | null {
+  if (payload.snapshot) {
+    monitorAgents.clear()
+    for (const agent of payload.snapshot.agents) {
+      monitorAgents.set(agent.key, agent)
+    }
+    return payload.snapshot
+  }
+  if (!payload.delta) {
+    return null
+  }
+  for (const key of payload.delta.removed) {
+    monitorAgents.delete(key)
+  }
+  for (const agent of [...payload.delta.added, ...payload.delta.updated]) {
+    monitorAgents.set(agent.key, agent)
+  }
+  const agents = [...monitorAgents.values()].sort((a, b) => b.last_ts_ms - a.last_ts_ms)
+  return {
+    summary: payload.delta.summary,
+    agents,
+    source_status: payload.delta.source_status,
+    now_ms: payload.delta.now_ms,
+  }
+}
+
+function emitMonitorTick(payload: MonitorTickPayload): void {
+  const snapshot = applyMonitorPayload(payload)
+  if (snapshot) {
+    emitMessageToApp({ type: 'monitorStateUpdate', snapshot })
+  }
+  for (const notification of payload.notifications) {
+    emitMessageToApp({ type: 'monitorNotification', notification })
+  }
+}
+
+type DetectedAgentPayload = { name: string; available: boolean }
+
+function emitAgentLauncherStatus(): void {
+  emitMessageToApp({
+    type: 'agentLauncherStatus',
+    claudeAvailable: detectedAgents.some((agent) => agent.name === 'claude' && agent.available),
+    detectedAgents,
+  })
+}
+
+async function openPendingDeepLink(): Promise<void> {
+  const agentKey = await tauriInvoke<string | null>('desktop_take_deep_link')
+  if (agentKey) {
+    emitMessageToApp({ type: 'selectMonitorAgent', agentKey })
+  }
+}
+
+async function startDesktopMonitorLoop(primary = true): Promise<void> {
+  if (monitorUnlisten !== null) {
     return
   }
-  const tick = async (): Promise<void> => {
-    if (monitorTickInFlight) {
-      return
+  monitorUnlisten = await listen<MonitorTickPayload>('monitor://tick', (event) => {
+    monitorTickErrored = false
+    emitMonitorTick(event.payload)
+  })
+  if (!primary) {
+    await tickDesktopMonitor()
+    return
+  }
+  settingsUnlisten = await listen<{ soundEnabled: boolean; demoMode: boolean; monitorSettings: unknown }>(
+    'settings://changed',
+    (event) => {
+      const { soundEnabled, demoMode, monitorSettings } = event.payload
+      emitMessageToApp({ type: 'settingsLoaded', soundEnabled, demoMode, monitorSettings })
+    },
+  )
+  hotkeyUnlisten = await listen<string>('app://hotkey', (event) => {
+    if (event.payload === 'togglePip') {
+      emitMessageToApp({ type: 'togglePictureInPicture' })
+    } else if (event.payload === 'snoozeNotifications') {
+      void tauriInvoke<number | null>('desktop_get_notification_snooze')
+        .then((untilMs) => emitMessageToApp({ type: 'notificationSnooze', untilMs }))
     }
-    monitorTickInFlight = true
-    try {
-      const payload = await tauriInvoke<{ snapshot: unknown; notifications: Array<{ title: string; message: string; kind: 'done' | 'error'; key: string }> }>('desktop_monitor_tick')
-      monitorTickErrored = false
-      emitMessageToApp({ type: 'monitorStateUpdate', snapshot: payload.snapshot })
-      for (const notification of payload.notifications) {
-        emitMessageToApp({ type: 'monitorNotification', notification })
-      }
-    } catch (error) {
-      if (!monitorTickErrored) {
-        monitorTickErrored = true
-        reportTauriError('monitor tick failed', error)
-      }
-    } finally {
-      monitorTickInFlight = false
+  })
+  deepLinkUnlisten = await listen('app://deep-link', () => {
+    void openPendingDeepLink()
+  })
+  await tickDesktopMonitor()
+}
+
+async function tickDesktopMonitor(): Promise<void> {
+  if (monitorTickInFlight) {
+    return
+  }
+  monitorTickInFlight = true
+  try {
+    const payload = await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick')
+    monitorTickErrored = false
+    emitMonitorTick(payload)
+  } catch (error) {
+    if (!monitorTickErrored) {
+      monitorTickErrored = true
+      reportTauriError('monitor tick failed', error)
     }
+  } finally {
+    monitorTickInFlight = false
   }
-  await tick()
-  monitorTimer = window.setInterval(() => {
-    void tick()
-  }, 2000)
 }
 
 async function stopDesktopMonitorLoop(): Promise<void> {
-  if (monitorTimer !== null) {
-    clearInterval(monitorTimer)
-    monitorTimer = null
+  if (monitorUnlisten !== null) {
+    monitorUnlisten()
+    monitorUnlisten = null
+  }
+  if (settingsUnlisten !== null) {
+    settingsUnlisten()
+    settingsUnlisten = null
+  }
+  if (hotkeyUnlisten !== null) {
+    hotkeyUnlisten()
+    hotkeyUnlisten = null
+  }
+  if (deepLinkUnlisten !== null) {
+    deepLinkUnlisten()
+    deepLinkUnlisten = null
   }
 }
 
@@ -97,13 +229,17 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
         soundEnabled: boolean
         demoMode: boolean
         monitorSettings: unknown
-        claudeAvailable: boolean
+        detectedAgents: DetectedAgentPayload[]
+        autostartEnabled: boolean
       }>('desktop_bootstrap')
+      autostartEnabled = bootstrap.autostartEnabled
       emitMessageToApp({ type: 'layoutLoaded', layout: bootstrap.layout })
       emitMessageToApp({ type: 'settingsLoaded', soundEnabled: bootstrap.soundEnabled, demoMode: bootstrap.demoMode, monitorSettings: bootstrap.monitorSettings })
-      emitMessageToApp({ type: 'agentLauncherStatus', claudeAvailable: bootstrap.claudeAvailable })
+      detectedAgents = bootstrap.detectedAgents
+      emitAgentLauncherStatus()
       emitMessageToApp({ type: 'existingAgents', agents: [] })
       await startDesktopMonitorLoop()
+      await openPendingDeepLink()
       return
     }
     case 'saveLayout': {
@@ -126,8 +262,59 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
       await tauriInvoke('desktop_set_monitor_settings', { settings: msg.settings })
       return
     }
+    case 'monitorWindowReady': {
+      await startDesktopMonitorLoop(false)
+      return
+    }
+    case 'monitorOpenAgentWindow': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke('desktop_open_agent_window', { agentKey: msg.agentKey })
+      }
+      return
+    }
+    case 'readClipboardText': {
+      const text = await tauriInvoke<string | null>('desktop_read_clipboard_text')
+      if (text !== null) {
+        emitMessageToApp({ type: 'clipboardText', agentKey: msg.agentKey, text })
+      }
+      return
+    }
+    case 'monitorCopySnapshot': {
+      await tauriInvoke('desktop_copy_snapshot', { format: msg.format === 'json' ? 'json' : 'markdown' })
+      return
+    }
+    case 'openDashboardWindow': {
+      await tauriInvoke('desktop_open_dashboard_window')
+      return
+    }
+    case 'closeDashboardWindow': {
+      await tauriInvoke('desktop_close_dashboard_window')
+      return
+    }
     case 'setPictureInPicture': {
-      await tauriInvoke('desktop_set_picture_in_picture', { enabled: Boolean(msg.enabled) })
+      await tauriInvoke('desktop_set_picture_in_picture', {
+        enabled: Boolean(msg.enabled),
+        corner: typeof msg.corner === 'string' ? msg.corner : null,
+      })
+      return
+    }
+    case 'monitorDeepScan': {
+      const payload = await tauriInvoke<MonitorTickPayload>('desktop_monitor_deep_scan')
+      emitMonitorTick(payload)
+      return
+    }
+    case 'resetData': {
+      if (typeof msg.scope !== 'string') {
+        return
+      }
+      const report = await tauriInvoke<unknown>('desktop_reset_data', { scope: msg.scope })
+      emitMessageToApp({ type: 'resetDataResult', report })
+      if (msg.scope === 'layouts' || msg.scope === 'all') {
+        emitMessageToApp({ type: 'layoutLoaded', layout: await tauriInvoke<unknown>('desktop_read_layout') })
+      }
+      if (msg.scope !== 'layouts') {
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_deep_scan'))
+      }
       return
     }
     case 'monitorBindRepo': {
@@ -136,6 +323,7 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
         sessionId: msg.sessionId,
         repoPath: msg.repoPath,
       })
+      emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
       return
     }
     case 'monitorOpenRepo': {
@@ -144,6 +332,70 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
       }
       return
     }
+    case 'monitorOpenInEditor': {
+      if (typeof msg.repoPath === 'string' && msg.repoPath.length > 0) {
+        await tauriInvoke('desktop_open_in_editor', {
+          repoPath: msg.repoPath,
+          file: typeof msg.file === 'string' ? msg.file : null,
+        })
+      }
+      return
+    }
+    case 'getUsageStats': {
+      const stats = await tauriInvoke<unknown>('desktop_get_usage_stats', {
+        range: { sinceMs: typeof msg.sinceMs === 'number' ? msg.sinceMs : null },
+      })
+      emitMessageToApp({ type: 'usageStats', stats })
+      return
+    }
+    case 'exportUsageStats': {
+      await tauriInvoke('desktop_export_stats', {
+        range: { sinceMs: typeof msg.sinceMs === 'number' ? msg.sinceMs : null },
+        format: msg.format === 'json' ? 'json' : 'csv',
+      })
+      return
+    }
+    case 'getDataDir': {
+      emitMessageToApp({ type: 'dataDir', view: await tauriInvoke<unknown>('desktop_get_data_dir') })
+      return
+    }
+    case 'setDataDir': {
+      const view = await tauriInvoke<unknown>('desktop_set_data_dir', { dir: typeof msg.dir === 'string' ? msg.dir : null })
+      emitMessageToApp({ type: 'dataDir', view })
+      return
+    }
+    case 'chooseDataDir': {
+      const dir = await tauriInvoke<string | null>('desktop_choose_repo_folder')
+      if (dir) {
+        emitMessageToApp({ type: 'dataDir', view: await tauriInvoke<unknown>('desktop_set_data_dir', { dir }) })
+      }
+      return
+    }
+    case 'getEditorCommand': {
+      emitMessageToApp({ type: 'editorCommand', command: await tauriInvoke<string>('desktop_get_editor_command') })
+      return
+    }
+    case 'setEditorCommand': {
+      await tauriInvoke('desktop_set_editor_command', { command: typeof msg.command === 'string' ? msg.command : '' })
+      emitMessageToApp({ type: 'editorCommand', command: await tauriInvoke<string>('desktop_get_editor_command') })
+      return
+    }
+    case 'listRepoBindings': {
+      emitMessageToApp({ type: 'repoBindings', bindings: await tauriInvoke<unknown[]>('desktop_list_repo_bindings') })
+      return
+    }
+    case 'monitorUnbindRepo': {
+      if (typeof msg.source === 'string' && typeof msg.sessionId === 'string') {
+        await tauriInvoke<boolean>('desktop_unbind_repo', { source: msg.source, sessionId: msg.sessionId })
+        emitMessageToApp({ type: 'repoBindings', bindings: await tauriInvoke<unknown[]>('desktop_list_repo_bindings') })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'listRepos': {
+      emitMessageToApp({ type: 'repos', repos: await tauriInvoke<unknown[]>('desktop_list_repos') })
+      return
+    }
     case 'monitorChooseRepo': {
       const picked = await tauriInvoke<string | null>('desktop_choose_repo_folder')
       if (typeof picked === 'string' && typeof msg.source === 'string' && typeof msg.sessionId === 'string') {
@@ -155,6 +407,239 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
       }
       return
     }
+    case 'monitorGetTranscript': {
+      if (typeof msg.source === 'string' && typeof msg.sessionId === 'string') {
+        const entries = await tauriInvoke<unknown[]>('desktop_get_transcript', {
+          source: msg.source,
+          sessionId: msg.sessionId,
+        })
+        emitMessageToApp({ type: 'monitorTranscript', source: msg.source, sessionId: msg.sessionId, entries })
+      }
+      return
+    }
+    case 'monitorGetAgentEvents': {
+      if (typeof msg.agentKey === 'string') {
+        const events = await tauriInvoke<unknown[]>('desktop_get_agent_events', {
+          agentKey: msg.agentKey,
+          beforeTs: typeof msg.beforeTs === 'number' ? msg.beforeTs : null,
+        })
+        emitMessageToApp({ type: 'monitorAgentEvents', agentKey: msg.agentKey, events })
+      }
+      return
+    }
+    case 'searchSessions': {
+      if (typeof msg.query === 'string' && msg.query.trim().length > 0) {
+        const hits = await tauriInvoke<unknown[]>('desktop_search_sessions', { query: msg.query })
+        emitMessageToApp({ type: 'sessionSearchResults', query: msg.query, hits })
+      }
+      return
+    }
+    case 'monitorExportTranscript': {
+      if (typeof msg.source === 'string' && typeof msg.sessionId === 'string') {
+        await tauriInvoke('desktop_export_transcript', {
+          source: msg.source,
+          sessionId: msg.sessionId,
+          format: msg.format === 'html' ? 'html' : 'markdown',
+        })
+      }
+      return
+    }
+    case 'monitorRevealSession': {
+      if (typeof msg.source === 'string' && typeof msg.sessionId === 'string') {
+        await tauriInvoke('desktop_reveal_session', { source: msg.source, sessionId: msg.sessionId })
+      }
+      return
+    }
+    case 'monitorArchiveAgent': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke('desktop_archive_agent', { agentKey: msg.agentKey })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'monitorUnarchiveAgent': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke('desktop_unarchive_agent', { agentKey: msg.agentKey })
+        emitMessageToApp({ type: 'archivedAgents', agents: await tauriInvoke<unknown[]>('desktop_list_archived_agents') })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'monitorPinAgent': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke('desktop_pin_agent', { agentKey: msg.agentKey, pinned: Boolean(msg.pinned) })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'monitorKillAgent': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke<number>('desktop_kill_agent', { agentKey: msg.agentKey, force: Boolean(msg.force) })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'monitorSendPrompt': {
+      if (typeof msg.source === 'string' && typeof msg.sessionId === 'string' && typeof msg.text === 'string') {
+        await tauriInvoke('desktop_send_prompt', { source: msg.source, sessionId: msg.sessionId, text: msg.text })
+      }
+      return
+    }
+    case 'monitorDismissAlert': {
+      if (typeof msg.agentKey === 'string' && typeof msg.tsMs === 'number') {
+        await tauriInvoke('desktop_dismiss_alert', { agentKey: msg.agentKey, tsMs: msg.tsMs })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'monitorMuteAgent': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke('desktop_set_agent_muted', { agentKey: msg.agentKey, muted: Boolean(msg.muted) })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'monitorSetAgentName': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke('desktop_set_agent_name', {
+          agentKey: msg.agentKey,
+          name: typeof msg.name === 'string' && msg.name.trim() ? msg.name.trim() : null,
+        })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'monitorSetAgentNote': {
+      if (typeof msg.agentKey === 'string') {
+        await tauriInvoke('desktop_set_agent_note', {
+          agentKey: msg.agentKey,
+          text: typeof msg.text === 'string' && msg.text.trim() ? msg.text.trim() : null,
+        })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_tick'))
+      }
+      return
+    }
+    case 'snoozeNotifications': {
+      const untilMs = await tauriInvoke<number | null>('desktop_snooze_notifications', { minutes: Number(msg.minutes) || 0 })
+      emitMessageToApp({ type: 'notificationSnooze', untilMs })
+      return
+    }
+    case 'testNotification': {
+      if (typeof msg.kind === 'string') {
+        await tauriInvoke<boolean>('desktop_test_notification', { kind: msg.kind })
+      }
+      return
+    }
+    case 'getHotkeys': {
+      emitMessageToApp({ type: 'hotkeys', hotkeys: await tauriInvoke<unknown>('desktop_get_hotkeys') })
+      return
+    }
+    case 'setHotkeys': {
+      emitMessageToApp({ type: 'hotkeys', hotkeys: await tauriInvoke<unknown>('desktop_set_hotkeys', { hotkeys: msg.hotkeys }) })
+      return
+    }
+    case 'getNotificationHistory': {
+      emitMessageToApp({ type: 'notificationHistory', entries: await tauriInvoke<unknown>('desktop_get_notification_history', { limit: Number(msg.limit) || null }) })
+      return
+    }
+    case 'getQuietHours': {
+      emitMessageToApp({ type: 'quietHours', quietHours: await tauriInvoke<unknown>('desktop_get_quiet_hours') })
+      return
+    }
+    case 'setQuietHours': {
+      emitMessageToApp({ type: 'quietHours', quietHours: await tauriInvoke<unknown>('desktop_set_quiet_hours', { quietHours: msg.quietHours }) })
+      return
+    }
+    case 'getWebhooks': {
+      emitMessageToApp({ type: 'webhooks', webhooks: await tauriInvoke<unknown>('desktop_get_webhooks') })
+      return
+    }
+    case 'setWebhooks':
+    case 'testWebhook': {
+      try {
+        if (msg.type === 'setWebhooks') {
+          emitMessageToApp({ type: 'webhooks', webhooks: await tauriInvoke<unknown>('desktop_set_webhooks', { webhooks: msg.webhooks }) })
+          emitMessageToApp({ type: 'webhookStatus', message: 'Webhooks saved' })
+        } else {
+          await tauriInvoke('desktop_test_webhook', { webhook: msg.webhook })
+          emitMessageToApp({ type: 'webhookStatus', message: 'Test webhook delivered' })
+        }
+      } catch (error) {
+        const detail = error instanceof TauriCommandError
+          ? error.detail
+          : error instanceof Error ? error.message : String(error)
+        emitMessageToApp({ type: 'webhookStatus', message: detail })
+      }
+      return
+    }
+    case 'getPushSettings': {
+      emitMessageToApp({ type: 'pushSettings', settings: await tauriInvoke<unknown>('desktop_get_push_settings') })
+      return
+    }
+    case 'setPushSettings':
+    case 'testPush': {
+      try {
+        if (msg.type === 'setPushSettings') {
+          emitMessageToApp({ type: 'pushSettings', settings: await tauriInvoke<unknown>('desktop_set_push_settings', { settings: msg.settings ?? null }) })
+          emitMessageToApp({ type: 'pushStatus', message: msg.settings ? 'Push settings saved' : 'Push disabled' })
+        } else {
+          await tauriInvoke('desktop_test_push', { settings: msg.settings })
+          emitMessageToApp({ type: 'pushStatus', message: 'Test push delivered' })
+        }
+      } catch (error) {
+        const detail = error instanceof TauriCommandError
+          ? error.detail
+          : error instanceof Error ? error.message : String(error)
+        emitMessageToApp({ type: 'pushStatus', message: detail })
+      }
+      return
+    }
+    case 'getCloseToTray': {
+      emitMessageToApp({ type: 'closeToTray', enabled: await tauriInvoke<boolean>('desktop_get_close_to_tray') })
+      return
+    }
+    case 'setCloseToTray': {
+      await tauriInvoke('desktop_set_close_to_tray', { enabled: Boolean(msg.enabled) })
+      emitMessageToApp({ type: 'closeToTray', enabled: await tauriInvoke<boolean>('desktop_get_close_to_tray') })
+      return
+    }
+    case 'getAutostart': {
+      emitMessageToApp({ type: 'autostartStatus', enabled: autostartEnabled })
+      return
+    }
+    case 'setAutostart': {
+      autostartEnabled = await tauriInvoke<boolean>('desktop_set_autostart', { enabled: Boolean(msg.enabled) })
+      emitMessageToApp({ type: 'autostartStatus', enabled: autostartEnabled })
+      return
+    }
+    case 'checkForUpdates': {
+      try {
+        emitMessageToApp({ type: 'updateInfo', info: await tauriInvoke<unknown>('desktop_check_for_updates') })
+      } catch (error) {
+        const detail = error instanceof TauriCommandError
+          ? error.detail
+          : error instanceof Error ? error.message : String(error)
+        emitMessageToApp({ type: 'updateInfo', info: null, error: detail })
+      }
+      return
+    }
+    case 'getAutoCheckUpdates': {
+      emitMessageToApp({ type: 'autoCheckUpdates', enabled: await tauriInvoke<boolean>('desktop_get_auto_check_updates') })
+      return
+    }
+    case 'setAutoCheckUpdates': {
+      await tauriInvoke('desktop_set_auto_check_updates', { enabled: Boolean(msg.enabled) })
+      emitMessageToApp({ type: 'autoCheckUpdates', enabled: Boolean(msg.enabled) })
+      return
+    }
+    case 'getNotificationSnooze': {
+      emitMessageToApp({ type: 'notificationSnooze', untilMs: await tauriInvoke<number | null>('desktop_get_notification_snooze') })
+      return
+    }
+    case 'listArchivedAgents': {
+      emitMessageToApp({ type: 'archivedAgents', agents: await tauriInvoke<unknown[]>('desktop_list_archived_agents') })
+      return
+    }
     case 'monitorCopyText': {
       if (typeof msg.text === 'string') {
         await tauriInvoke('desktop_copy_text', { text: msg.text })
@@ -163,7 +648,7 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
     }
     case 'monitorRevealTerminal': {
       if (typeof msg.repoPath === 'string' && msg.repoPath.length > 0) {
-        await tauriInvoke('desktop_open_path', { path: msg.repoPath })
+        await tauriInvoke('desktop_open_terminal_at', { path: msg.repoPath })
       }
       return
     }
@@ -171,16 +656,26 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
       await tauriInvoke('desktop_open_url', { url: 'https://docs.anthropic.com/en/docs/claude-code' })
       return
     }
+    case 'openUrl': {
+      if (typeof msg.url === 'string' && /^https?:\/\//.test(msg.url)) {
+        await tauriInvoke('desktop_open_url', { url: msg.url })
+      }
+      return
+    }
     case 'openAgent': {
       const source = typeof msg.source === 'string' ? msg.source : 'claude'
       try {
-        const cwd = await tauriInvoke<string | null>('desktop_choose_repo_folder')
+        const cwd = typeof msg.cwd === 'string' && msg.cwd
+          ? msg.cwd
+          : await tauriInvoke<string | null>('desktop_choose_repo_folder')
         if (!cwd) {
           return
         }
         await tauriInvoke('desktop_launch_agent', { source, cwd })
       } catch (error) {
-        const detail = error instanceof Error ? error.message : String(error)
+        const detail = error instanceof TauriCommandError
+          ? error.detail
+          : error instanceof Error ? error.message : String(error)
         emitMessageToApp({
           type: 'monitorNotification',
           notification: {
@@ -193,6 +688,15 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
       }
       return
     }
+    case 'refreshCliAvailability': {
+      detectedAgents = await tauriInvoke<DetectedAgentPayload[]>('desktop_refresh_cli_availability')
+      emitAgentLauncherStatus()
+      return
+    }
+    case 'getDetectedAgents': {
+      emitAgentLauncherStatus()
+      return
+    }
     case 'openSessionsFolder': {
       const path = await tauriInvoke<string | null>('desktop_sessions_folder')
       if (path) {
@@ -211,6 +715,48 @@ async function handleTauriMessage(msg: AnyRecord): Promise<void> {
       }
       return
     }
+    case 'exportBundle': {
+      await tauriInvoke('desktop_export_bundle')
+      return
+    }
+    case 'importBundle': {
+      const restored = await tauriInvoke<string[] | null>('desktop_import_bundle')
+      if (restored) {
+        emitMessageToApp({ type: 'layoutLoaded', layout: await tauriInvoke<unknown>('desktop_read_layout') })
+        emitMonitorTick(await tauriInvoke<MonitorTickPayload>('desktop_monitor_deep_scan'))
+      }
+      return
+    }
+    case 'listLayouts': {
+      emitMessageToApp({ type: 'layouts', layouts: await tauriInvoke<unknown>('desktop_list_layouts') })
+      return
+    }
+    case 'saveLayoutAs': {
+      if (typeof msg.name === 'string') {
+        emitMessageToApp({ type: 'layouts', layouts: await tauriInvoke<unknown>('desktop_save_layout_as', { name: msg.name }) })
+      }
+      return
+    }
+    case 'switchLayout': {
+      if (typeof msg.name === 'string') {
+        const layout = await tauriInvoke<unknown>('desktop_switch_layout', { name: msg.name })
+        emitMessageToApp({ type: 'layoutLoaded', layout })
+        emitMessageToApp({ type: 'layouts', layouts: await tauriInvoke<unknown>('desktop_list_layouts') })
+      }
+      return
+    }
+    case 'listLayoutBackups': {
+      emitMessageToApp({ type: 'layoutBackups', backups: await tauriInvoke<unknown[]>('desktop_list_layout_backups') })
+      return
+    }
+    case 'restoreLayoutBackup': {
+      if (typeof msg.index === 'number') {
+        const layout = await tauriInvoke<unknown>('desktop_restore_layout_backup', { index: msg.index })
+        emitMessageToApp({ type: 'layoutLoaded', layout })
+        emitMessageToApp({ type: 'layoutBackups', backups: await tauriInvoke<unknown[]>('desktop_list_layout_backups') })
+      }
+      return
+    }
     case 'focusAgent':
     case 'openClaude':
     case 'closeAgent': {